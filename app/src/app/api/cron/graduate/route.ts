@@ -23,7 +23,6 @@ function getConnection(): Connection {
 
 interface CurveData {
   graduated: boolean;
-  migratedToRaydium: boolean;
   realSolReserves: bigint;
   realTokenReserves: bigint;
 }
@@ -42,14 +41,12 @@ async function getCurveState(connection: Connection, mint: string): Promise<Curv
     const data = curveAccount.data;
     // graduated at offset 112
     const graduated = data[112] === 1;
-    // migrated_to_raydium at offset 113
-    const migratedToRaydium = data[113] === 1;
     // real_sol_reserves at offset 88
     const realSolReserves = data.readBigUInt64LE(88);
     // real_token_reserves at offset 96
     const realTokenReserves = data.readBigUInt64LE(96);
     
-    return { graduated, migratedToRaydium, realSolReserves, realTokenReserves };
+    return { graduated, realSolReserves, realTokenReserves };
   } catch {
     return null;
   }
@@ -13,7 +13,13 @@ import {
   createAssociatedTokenAccountInstruction,
   getAccount,
 } from '@solana/spl-token';
-import ClawdVaultClient, { findBondingCurvePDA } from '@/lib/anchor/client';
+import ClawdVaultClient, {
+  CurveStatus,
+  findBondingCurvePDA,
+  findMigrationEscrowPDA,
+  LpHandling,
+  readCurveStatus,
+} from '@/lib/anchor/client';
 import bs58 from 'bs58';
 
 export const dynamic = 'force-dynamic';
@@ -89,10 +95,7 @@ export async function GET(request: Request) {
 
     // Parse curve data (offset 8 for discriminator)
     const data = curveAccount.data;
-    // graduated is at offset: 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 = 112
-    const graduated = data[112] === 1;
-    // migrated_to_raydium is at offset 113
-    const migratedToRaydium = data[113] === 1;
+    const status = readCurveStatus(data);
     // real_sol_reserves at offset 8 + 32 + 32 + 8 + 8 = 88
     const realSolReserves = data.readBigUInt64LE(88);
     // real_token_reserves at offset 96
@@ -102,11 +105,11 @@ export async function GET(request: Request) {
       success: true,
       data: {
         mint,
-        graduated,
-        migratedToRaydium,
+        // null until the curve is resized from the legacy layout
+        status: status === null ? null : CurveStatus[status],
         realSolReserves: realSolReserves.toString(),
         realTokenReserves: realTokenReserves.toString(),
-        canMigrate: graduated && !migratedToRaydium,
+        canMigrate: status === CurveStatus.Graduated,
       }
     });
 
@@ -188,21 +191,16 @@ export async function POST(request: Request) {
 
     // Parse curve data
     const data = curveAccount.data;
-    const graduated = data[112] === 1;
-    const migratedToRaydium = data[113] === 1;
+    const status = readCurveStatus(data);
     const realSolReserves = data.readBigUInt64LE(88);
     const realTokenReserves = data.readBigUInt64LE(96);
 
-    if (!graduated) {
+    if (status !== CurveStatus.Graduated) {
+      const error = status === null
+        ? 'Bonding curve needs resize_curve first'
+        : `Curve is ${CurveStatus[status]}, not Graduated`;
       return NextResponse.json(
-        { success: false, error: 'Token has not graduated yet' },
-        { status: 400 }
-      );
-    }
-
-    if (migratedToRaydium) {
-      return NextResponse.json(
-        { success: false, error: 'Token already migrated to Raydium' },
+        { success: false, error },
         { status: 400 }
       );
     }
@@ -211,14 +209,19 @@ export async function POST(request: Request) {
     console.log(`SOL reserves: ${realSolReserves} lamports`);
     console.log(`Token reserves: ${realTokenReserves}`);
 
+    const client = new ClawdVaultClient(connection);
+    const tokenProgram = await client.getTokenProgram(mintPubkey);
+
     // Step 1: Ensure migration wallet has token account
     const migrationTokenAccount = await getAssociatedTokenAddress(
       mintPubkey,
-      migrationWallet.publicKey
+      migrationWallet.publicKey,
+      false,
+      tokenProgram
     );
     
     try {
-      await getAccount(connection, migrationTokenAccount);
+      await getAccount(connection, migrationTokenAccount, undefined, tokenProgram);
     } catch {
       // Create ATA if it doesn't exist
       console.log('Creating migration token account...');
@@ -226,7 +229,8 @@ export async function POST(request: Request) {
         operatorWallet.publicKey,
         migrationTokenAccount,
         migrationWallet.publicKey,
-        mintPubkey
+        mintPubkey,
+        tokenProgram
       );
       const { blockhash } = await connection.getLatestBlockhash();
       const tx = new (await import('@solana/web3.js')).Transaction()
@@ -236,13 +240,11 @@ export async function POST(request: Request) {
       await sendAndConfirmTransaction(connection, tx, [operatorWallet]);
     }
 
-    // Step 2: Release assets from curve to migration wallet
+    // Step 2: Release assets from curve to the migration escrow
     console.log('Releasing assets from curve...');
-    const client = new ClawdVaultClient(connection);
     const releaseTx = await client.buildReleaseForMigrationTx(
       operatorWallet.publicKey,
       mintPubkey,
-      migrationWallet.publicKey
     );
     
     const releaseSignature = await sendAndConfirmTransaction(
@@ -252,7 +254,18 @@ export async function POST(request: Request) {
     );
     console.log(`✅ Assets released: ${releaseSignature}`);
 
-    // Step 3: Create Raydium CPMM pool
+    // The escrow holds what goes into the pool: the program has already taken the
+    // migration fee / graduation bonus and burned tokens above the final curve price
+    const [escrowPDA] = findMigrationEscrowPDA(mintPubkey);
+    const escrowTokenAccount = await getAssociatedTokenAddress(mintPubkey, escrowPDA, true, tokenProgram);
+    const escrowLamports = BigInt(await connection.getBalance(escrowPDA));
+    const escrowRent = BigInt(await connection.getMinimumBalanceForRentExemption(0));
+    const poolSolAmount = escrowLamports - escrowRent;
+    const poolTokenAmount = (await getAccount(connection, escrowTokenAccount, undefined, tokenProgram)).amount;
+    console.log(`Escrowed for pool: ${poolSolAmount} lamports + ${poolTokenAmount} tokens`);
+
+    // Step 3: Claim escrow, create Raydium CPMM pool and finalize in one transaction
+    // (the program only releases the escrow if finalize_migration verifies the pool)
     console.log('Creating Raydium CPMM pool...');
     
     let raydiumPool = null;
@@ -262,10 +275,33 @@ export async function POST(request: Request) {
     try {
       const { createCpmmPool } = await import('@/lib/raydium');
       
+      const claimIx = await client.buildClaimMigrationEscrowIx(
+        operatorWallet.publicKey,
+        mintPubkey,
+        migrationWallet.publicKey,
+      );
       const poolResult = await createCpmmPool(
         mint,
-        realTokenReserves,
-        realSolReserves
+        poolTokenAmount,
+        poolSolAmount,
+        tokenProgram,
+        {
+          signer: operatorWallet,
+          preInstructions: [claimIx],
+          // finalize_migration burns the migration wallet's LP tokens
+          postInstructions: ({ poolId, lpMint, solVault, tokenVault }) => [
+            client.buildFinalizeMigrationIx(
+              operatorWallet.publicKey,
+              mintPubkey,
+              poolId,
+              solVault,
+              tokenVault,
+              migrationWallet.publicKey,
+              lpMint,
+              LpHandling.Burned,
+            ),
+          ],
+        },
       );
       
       raydiumPool = poolResult.poolId;
@@ -278,8 +314,8 @@ export async function POST(request: Request) {
       
     } catch (poolError) {
       console.error('⚠️ Raydium pool creation failed:', poolError);
-      // Don't fail the whole graduation - assets are released
-      // Pool can be created manually or retried
+      // Don't fail the whole graduation - assets stay in the escrow
+      // refund_migration returns them to the curve after the timeout
     }
 
     // Step 4: Update database
@@ -316,14 +352,14 @@ export async function POST(request: Request) {
         mint,
         releaseSignature,
         migrationWallet: migrationWallet.publicKey.toBase58(),
-        solReleased: realSolReserves.toString(),
-        tokensReleased: realTokenReserves.toString(),
+        solReleased: poolSolAmount.toString(),
+        tokensReleased: poolTokenAmount.toString(),
         raydiumPool,
         lpMint,
         poolTxSignature,
         message: raydiumPool 
           ? 'Token graduated to Raydium successfully!'
-          : 'Assets released. Raydium pool creation failed - can retry manually.',
+          : 'Assets escrowed. Raydium pool creation failed - can retry manually.',
       }
     });
 
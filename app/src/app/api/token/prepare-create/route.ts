@@ -1,6 +1,6 @@
 import { NextResponse } from 'next/server';
 import { Connection, Keypair, PublicKey, clusterApiUrl } from '@solana/web3.js';
-import ClawdVaultClient, { findConfigPDA, PROGRAM_ID } from '@/lib/anchor/client';
+import ClawdVaultClient, { findConfigPDA, findCreatorMintPDA, PROGRAM_ID, TokenProfileInput } from '@/lib/anchor/client';
 import bs58 from 'bs58';
 
 export const dynamic = 'force-dynamic';
@@ -17,6 +17,23 @@ interface PrepareCreateRequest {
   symbol: string;
   uri?: string;         // Metadata URI (optional)
   initialBuy?: number;  // Initial buy in SOL (optional)
+  graduationDeadline?: number;  // Unix timestamp after which the launch is refundable (optional)
+  token2022?: boolean;  // Launch on Token-2022 with native metadata (optional)
+  transferFeeBps?: number;  // Token-2022 only: creator royalty on every transfer, capped by the program config (optional)
+  mintNonce?: string;   // Derive the mint from creator + nonce instead of a fresh keypair (optional)
+  profile?: TokenProfileInput;  // On-chain profile: website, X, Telegram, agent id, description (optional)
+  sponsored?: boolean;  // Platform wallet pays rent and the fee; requires no initial buy (optional)
+}
+
+// Sponsor wallet keypair (pays account rent and fees for sponsored launches)
+function getSponsorWallet(): Keypair | null {
+  const privateKey = process.env.SPONSOR_WALLET_PRIVATE_KEY;
+  if (!privateKey) return null;
+  try {
+    return Keypair.fromSecretKey(bs58.decode(privateKey));
+  } catch {
+    return null;
+  }
 }
 
 /**
@@ -52,6 +69,42 @@ export async function POST(request: Request) {
       );
     }
 
+    if (body.transferFeeBps && !body.token2022) {
+      return NextResponse.json(
+        { success: false, error: 'Transfer fees require a Token-2022 launch' },
+        { status: 400 }
+      );
+    }
+
+    const sponsor = body.sponsored ? getSponsorWallet() : null;
+    if (body.sponsored && !sponsor) {
+      return NextResponse.json(
+        { success: false, error: 'Sponsored launches are not available (sponsor wallet not configured)' },
+        { status: 503 }
+      );
+    }
+
+    if (body.sponsored && body.initialBuy) {
+      return NextResponse.json(
+        { success: false, error: 'Sponsored launches cannot include an initial buy' },
+        { status: 400 }
+      );
+    }
+
+    const profile = body.profile;
+    if (profile && (
+      (profile.website?.length ?? 0) > 64 ||
+      (profile.twitter?.length ?? 0) > 32 ||
+      (profile.telegram?.length ?? 0) > 32 ||
+      (profile.agentId?.length ?? 0) > 32 ||
+      Buffer.byteLength(profile.description ?? '') > 200
+    )) {
+      return NextResponse.json(
+        { success: false, error: 'Profile fields too long (website 64, handles and agent id 32, description 200 bytes)' },
+        { status: 400 }
+      );
+    }
+
     // Verify Anchor program is deployed
     const connection = getConnection();
     const [configPDA] = findConfigPDA();
@@ -67,8 +120,10 @@ export async function POST(request: Request) {
     const client = new ClawdVaultClient(connection);
     const creatorPubkey = new PublicKey(body.creator);
     
-    // Generate new mint keypair
-    const mintKeypair = Keypair.generate();
+    // Generate new mint keypair, unless the mint is derived from the creator and a nonce
+    const mintNonce = body.mintNonce !== undefined ? BigInt(body.mintNonce) : undefined;
+    const mintKeypair = mintNonce === undefined ? Keypair.generate() : null;
+    const mint = mintKeypair?.publicKey ?? findCreatorMintPDA(creatorPubkey, mintNonce!)[0];
     
     // Calculate initial buy in lamports
     const initialBuyLamports = body.initialBuy 
@@ -78,21 +133,68 @@ export async function POST(request: Request) {
     // Construct metadata URI - points to our API endpoint
     // This returns Metaplex-compatible JSON with name, symbol, description, image
     const baseUrl = process.env.NEXT_PUBLIC_APP_URL || 'https://clawdvault.com';
-    const metadataUri = `${baseUrl}/api/metadata/${mintKeypair.publicKey.toBase58()}`;
+    const metadataUri = `${baseUrl}/api/metadata/${mint.toBase58()}`;
     
     // Build transaction
-    const transaction = await client.buildCreateTokenTransaction(
-      creatorPubkey,
-      mintKeypair,
-      body.name,
-      body.symbol,
-      metadataUri,  // Use metadata URI instead of raw image
-      initialBuyLamports
-    );
+    const graduationDeadline = BigInt(Math.floor(body.graduationDeadline ?? 0));
+    const transaction = body.token2022 && !mintKeypair
+      ? await client.buildCreateToken2022PdaTransaction(
+          creatorPubkey,
+          mintNonce!,
+          body.name,
+          body.symbol,
+          metadataUri,
+          initialBuyLamports,
+          graduationDeadline,
+          Math.floor(body.transferFeeBps ?? 0),
+          sponsor?.publicKey,
+          profile
+        )
+      : !mintKeypair
+      ? await client.buildCreateTokenPdaTransaction(
+          creatorPubkey,
+          mintNonce!,
+          body.name,
+          body.symbol,
+          metadataUri,
+          initialBuyLamports,
+          graduationDeadline,
+          sponsor?.publicKey,
+          profile
+        )
+      : body.token2022
+      ? await client.buildCreateToken2022Transaction(
+          creatorPubkey,
+          mintKeypair,
+          body.name,
+          body.symbol,
+          metadataUri,
+          initialBuyLamports,
+          graduationDeadline,
+          Math.floor(body.transferFeeBps ?? 0),
+          sponsor?.publicKey,
+          profile
+        )
+      : await client.buildCreateTokenTransaction(
+          creatorPubkey,
+          mintKeypair,
+          body.name,
+          body.symbol,
+          metadataUri,  // Use metadata URI instead of raw image
+          initialBuyLamports,
+          graduationDeadline,
+          sponsor?.publicKey,
+          profile
+        );
     
-    // The mint keypair needs to sign the transaction
+    // A keypair mint and the sponsor (if any) need to sign the transaction
     // We'll partially sign it here and return it
-    transaction.partialSign(mintKeypair);
+    if (mintKeypair) {
+      transaction.partialSign(mintKeypair);
+    }
+    if (sponsor) {
+      transaction.partialSign(sponsor);
+    }
     
     // Serialize for user to sign
     const serialized = transaction.serialize({
@@ -115,7 +217,7 @@ export async function POST(request: Request) {
     return NextResponse.json({
       success: true,
       transaction: serialized.toString('base64'),
-      mint: mintKeypair.publicKey.toBase58(),
+      mint: mint.toBase58(),
       programId: PROGRAM_ID.toBase58(),
       network: process.env.SOLANA_NETWORK || 'devnet',
       initialBuy: body.initialBuy ? {
@@ -1,7 +1,8 @@
 import { NextResponse } from 'next/server';
-import { Connection, clusterApiUrl, Transaction, PublicKey } from '@solana/web3.js';
+import { Connection, clusterApiUrl, Transaction } from '@solana/web3.js';
 import { getToken, recordTrade, getTradeBySignature } from '@/lib/db';
 import { PROGRAM_ID } from '@/lib/anchor/client';
+import { getProgramEvents, parseTradeEvent } from '@/lib/sync-trades';
 
 export const dynamic = 'force-dynamic';
 
@@ -11,88 +12,6 @@ function getConnection(): Connection {
   return new Connection(rpcUrl, 'confirmed');
 }
 
-// TradeEvent discriminator (first 8 bytes of sha256("event:TradeEvent"))
-const TRADE_EVENT_DISCRIMINATOR = Buffer.from([189, 219, 127, 211, 78, 230, 97, 238]);
-
-interface ParsedTradeEvent {
-  mint: string;
-  trader: string;
-  isBuy: boolean;
-  solAmount: bigint;
-  tokenAmount: bigint;
-  protocolFee: bigint;
-  creatorFee: bigint;
-  virtualSolReserves: bigint;
-  virtualTokenReserves: bigint;
-  timestamp: bigint;
-}
-
-/**
- * Parse TradeEvent from transaction logs
- */
-function parseTradeEventFromLogs(logs: string[]): ParsedTradeEvent | null {
-  for (const log of logs) {
-    // Anchor events are logged as "Program data: <base64>"
-    if (log.startsWith('Program data: ')) {
-      const base64Data = log.slice('Program data: '.length);
-      try {
-        const data = Buffer.from(base64Data, 'base64');
-        
-        // Check discriminator
-        if (data.length >= 8 && data.slice(0, 8).equals(TRADE_EVENT_DISCRIMINATOR)) {
-          // Parse the event data (offset 8 to skip discriminator)
-          let offset = 8;
-          
-          const mint = new PublicKey(data.slice(offset, offset + 32)).toBase58();
-          offset += 32;
-          
-          const trader = new PublicKey(data.slice(offset, offset + 32)).toBase58();
-          offset += 32;
-          
-          const isBuy = data[offset] === 1;
-          offset += 1;
-          
-          const solAmount = data.readBigUInt64LE(offset);
-          offset += 8;
-          
-          const tokenAmount = data.readBigUInt64LE(offset);
-          offset += 8;
-          
-          const protocolFee = data.readBigUInt64LE(offset);
-          offset += 8;
-          
-          const creatorFee = data.readBigUInt64LE(offset);
-          offset += 8;
-          
-          const virtualSolReserves = data.readBigUInt64LE(offset);
-          offset += 8;
-          
-          const virtualTokenReserves = data.readBigUInt64LE(offset);
-          offset += 8;
-          
-          const timestamp = data.readBigInt64LE(offset);
-          
-          return {
-            mint,
-            trader,
-            isBuy,
-            solAmount,
-            tokenAmount,
-            protocolFee,
-            creatorFee,
-            virtualSolReserves,
-            virtualTokenReserves,
-            timestamp,
-          };
-        }
-      } catch (e) {
-        // Not our event, continue
-      }
-    }
-  }
-  return null;
-}
-
 interface ExecuteTradeRequest {
   signedTransaction: string;  // Base64 encoded signed transaction
   mint: string;
@@ -181,11 +100,8 @@ export async function POST(request: Request) {
       maxSupportedTransactionVersion: 0,
     });
     
-    // Parse TradeEvent from logs to get ACTUAL amounts (prevents spoofing)
-    let tradeEvent: ParsedTradeEvent | null = null;
-    if (txDetails?.meta?.logMessages) {
-      tradeEvent = parseTradeEventFromLogs(txDetails.meta.logMessages);
-    }
+    // Parse TradeEvent from program events to get ACTUAL amounts (prevents spoofing)
+    const tradeEvent = txDetails?.meta ? parseTradeEvent(getProgramEvents(txDetails)) : null;
     
     // Record the trade in database using ON-CHAIN data
     let dbTradeId: string | undefined;
@@ -204,7 +120,7 @@ export async function POST(request: Request) {
         dbTradeId = dbTrade?.id;
         console.log(`📊 Trade recorded from on-chain event: ${tradeEvent.isBuy ? 'BUY' : 'SELL'} ${Number(tradeEvent.solAmount) / 1e9} SOL (ID: ${dbTradeId})`);
       } else {
-        console.warn('⚠️ Could not parse TradeEvent from logs - trade not recorded in DB');
+        console.warn('⚠️ Could not parse TradeEvent from transaction - trade not recorded in DB');
         // Don't fall back to client data - that would defeat the purpose
       }
     } catch (dbError) {
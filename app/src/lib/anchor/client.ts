@@ -11,12 +11,16 @@ import {
   Transaction,
   TransactionInstruction,
   SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
   LAMPORTS_PER_SOL,
 } from '@solana/web3.js';
 import {
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccountIdempotentInstruction,
 } from '@solana/spl-token';
 
 // Program ID - DEPLOYED TO DEVNET 2026-02-02
@@ -35,6 +39,16 @@ export function findMetadataPDA(mint: PublicKey): [PublicKey, number] {
   );
 }
 
+/**
+ * Find the master edition PDA for a mint
+ */
+export function findMasterEditionPDA(mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('metadata'), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer(), Buffer.from('edition')],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+}
+
 /**
  * Build CreateMetadataAccountV3 instruction
  */
@@ -99,6 +113,21 @@ function buildCreateMetadataInstruction(
 const CONFIG_SEED = Buffer.from('config');
 const CURVE_SEED = Buffer.from('bonding_curve');
 const VAULT_SEED = Buffer.from('sol_vault');
+const EVENT_AUTHORITY_SEED = Buffer.from('__event_authority');
+const MIGRATION_ESCROW_SEED = Buffer.from('migration_escrow');
+const TRANSFER_FEE_SEED = Buffer.from('transfer_fee');
+const COLLECTION_SEED = Buffer.from('collection');
+const TOKEN_GROUP_SEED = Buffer.from('token_group');
+const MINT_SEED = Buffer.from('mint');
+const SYMBOL_SEED = Buffer.from('symbol');
+const PROFILE_SEED = Buffer.from('profile');
+const POOL_SEED = Buffer.from('pool');
+const POOL_VAULT_SEED = Buffer.from('pool_sol_vault');
+const LP_MINT_SEED = Buffer.from('lp_mint');
+const LP_LOCK_SEED = Buffer.from('lp_lock');
+
+// Initial pool LP tokens are minted to this address's ATA (see LP_BURN_ADDRESS in program)
+const LP_BURN_ADDRESS = new PublicKey('1nc1nerator11111111111111111111111111111111');
 
 // Constants matching the program
 export const TOTAL_SUPPLY = BigInt('1000000000000000'); // 1B * 10^6
@@ -110,6 +139,54 @@ export const CREATOR_FEE_BPS = 50;
 export const TOTAL_FEE_BPS = 100;
 export const BPS_DENOMINATOR = 10000;
 
+/**
+ * Bonding curve lifecycle (matches CurveStatus enum in program)
+ */
+export enum CurveStatus {
+  Trading = 0,
+  Paused = 1,
+  Graduated = 2,
+  Migrating = 3,
+  Migrated = 4,
+  Refunding = 5,
+  Closed = 6,
+}
+
+// `status` is the first byte after the legacy 124-byte curve layout
+const CURVE_STATUS_OFFSET = 124;
+
+/**
+ * Read a bonding curve's status from its account data
+ * Returns null for legacy curves that haven't been through resize_curve yet
+ */
+export function readCurveStatus(data: Buffer): CurveStatus | null {
+  return data.length > CURVE_STATUS_OFFSET ? data[CURVE_STATUS_OFFSET] : null;
+}
+
+/**
+ * DEX a graduated curve migrated to (matches MigrationDex enum in program)
+ */
+export enum MigrationDex {
+  RaydiumCpmm = 0,
+  ClawdVault = 1,
+}
+
+/**
+ * What happened to the migration pool's LP tokens (matches LpHandling enum in program)
+ */
+export enum LpHandling {
+  Burned = 0,
+  Locked = 1,
+}
+
+/**
+ * What happens to token metadata after graduation (matches MetadataPolicy enum in program)
+ */
+export enum MetadataPolicy {
+  Immutable = 0,
+  CreatorAuthority = 1,
+}
+
 /**
  * Find the config PDA
  */
@@ -137,14 +214,125 @@ export function findSolVaultPDA(mint: PublicKey): [PublicKey, number] {
   );
 }
 
+/**
+ * Find the migration escrow PDA for a mint (holds released assets until the pool is finalized)
+ */
+export function findMigrationEscrowPDA(mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [MIGRATION_ESCROW_SEED, mint.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Find the LP lock PDA for a mint (holds a migration pool's LP tokens for LpHandling.Locked)
+ */
+export function findLpLockPDA(mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [LP_LOCK_SEED, mint.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Find the PDA that withdraws a Token-2022 mint's withheld transfer fees for its creator
+ */
+export function findTransferFeeAuthorityPDA(mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [TRANSFER_FEE_SEED, mint.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Find the protocol collection NFT mint (every `create_token` launch is verified into it)
+ */
+export function findCollectionMintPDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([COLLECTION_SEED], PROGRAM_ID);
+}
+
+/**
+ * Find the protocol Token-2022 group mint (every `create_token_2022` launch joins it)
+ * A mint whose TokenGroupMember extension points here was launched through ClawdVault
+ */
+export function findTokenGroupPDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([TOKEN_GROUP_SEED], PROGRAM_ID);
+}
+
+/**
+ * Find the on-chain profile of a token
+ */
+export function findTokenProfilePDA(mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([PROFILE_SEED, mint.toBuffer()], PROGRAM_ID);
+}
+
+/**
+ * Find the ticker registry record for a symbol (normalized to ASCII uppercase, as on-chain)
+ */
+export function findSymbolRecordPDA(symbol: string): [PublicKey, number] {
+  const normalized = symbol.replace(/[a-z]/g, (c) => c.toUpperCase());
+  return PublicKey.findProgramAddressSync(
+    [SYMBOL_SEED, Buffer.from(normalized)],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Find the mint PDA `create_token_pda` / `create_token_2022_pda` creates for a creator and nonce
+ */
+export function findCreatorMintPDA(creator: PublicKey, nonce: bigint): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [MINT_SEED, creator.toBuffer(), writeU64(nonce)],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Search nonces for a PDA mint address ending in `suffix` (off-chain vanity grind)
+ * Returns null if none is found within `maxAttempts`
+ */
+export function findVanityMintNonce(
+  creator: PublicKey,
+  suffix: string,
+  startNonce: bigint = BigInt(0),
+  maxAttempts: number = 1_000_000
+): { nonce: bigint; mint: PublicKey } | null {
+  for (let i = 0; i < maxAttempts; i++) {
+    const nonce = startNonce + BigInt(i);
+    const [mint] = findCreatorMintPDA(creator, nonce);
+    if (mint.toBase58().endsWith(suffix)) {
+      return { nonce, mint };
+    }
+  }
+  return null;
+}
+
+/**
+ * Find the ClawdVault pool PDA for a mint, plus its SOL vault and LP mint
+ */
+export function findPoolPDAs(mint: PublicKey): { pool: PublicKey; solVault: PublicKey; lpMint: PublicKey } {
+  const [pool] = PublicKey.findProgramAddressSync([POOL_SEED, mint.toBuffer()], PROGRAM_ID);
+  const [solVault] = PublicKey.findProgramAddressSync([POOL_VAULT_SEED, mint.toBuffer()], PROGRAM_ID);
+  const [lpMint] = PublicKey.findProgramAddressSync([LP_MINT_SEED, pool.toBuffer()], PROGRAM_ID);
+  return { pool, solVault, lpMint };
+}
+
+/**
+ * Find the event authority PDA (signs self-CPI events, see `emit_cpi!`)
+ */
+export function findEventAuthorityPDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([EVENT_AUTHORITY_SEED], PROGRAM_ID);
+}
+
 /**
  * Find the token vault address (ATA of bonding curve)
  */
 export async function findTokenVaultAddress(
   mint: PublicKey,
-  bondingCurve: PublicKey
+  bondingCurve: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID
 ): Promise<PublicKey> {
-  return getAssociatedTokenAddress(mint, bondingCurve, true);
+  return getAssociatedTokenAddress(mint, bondingCurve, true, tokenProgram);
 }
 
 /**
@@ -262,6 +450,60 @@ function writeU64(value: bigint): Buffer {
   return buf;
 }
 
+/**
+ * Write u16 to buffer (little-endian)
+ */
+function writeU16(value: number): Buffer {
+  const buf = Buffer.alloc(2);
+  buf.writeUInt16LE(value);
+  return buf;
+}
+
+/**
+ * Write i64 to buffer (little-endian)
+ */
+function writeI64(value: bigint): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigInt64LE(value);
+  return buf;
+}
+
+/**
+ * Write a borsh string (u32 length prefix + UTF-8 bytes)
+ */
+function writeString(value: string): Buffer {
+  const bytes = Buffer.from(value);
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(bytes.length);
+  return Buffer.concat([len, bytes]);
+}
+
+/**
+ * Fields of a token's on-chain profile (empty string = not set)
+ */
+export interface TokenProfileInput {
+  website?: string;
+  twitter?: string;
+  telegram?: string;
+  agentId?: string;      // Agent id or model tag
+  description?: string;
+}
+
+/**
+ * Borsh `Option<TokenProfileArgs>` for the create instructions
+ */
+function writeProfileOption(profile?: TokenProfileInput): Buffer {
+  if (!profile) return Buffer.from([0]);
+  return Buffer.concat([
+    Buffer.from([1]),
+    writeString(profile.website ?? ''),
+    writeString(profile.twitter ?? ''),
+    writeString(profile.telegram ?? ''),
+    writeString(profile.agentId ?? ''),
+    writeString(profile.description ?? ''),
+  ]);
+}
+
 /**
  * ClawdVault client for building transactions
  */
@@ -276,8 +518,12 @@ export class ClawdVaultClient {
    * Build a create token transaction with metadata and optional initial buy
    * 
    * The mint keypair must be generated client-side and signed by the user.
-   * Anchor handles mint account creation via `init` constraint.
+   * The program creates the mint account (paid by `payer`).
    * Now includes Metaplex metadata creation and optional initial buy.
+   * A non-zero graduationDeadline (unix seconds) makes the launch refundable if it misses it.
+   * A separate `payer` (e.g. the platform) funds account creation and the transaction fee,
+   * so a creator with no SOL can launch without an initial buy.
+   * An optional `profile` is written to the token's profile PDA in the same instruction.
    */
   async buildCreateTokenTransaction(
     creator: PublicKey,
@@ -285,17 +531,36 @@ export class ClawdVaultClient {
     name: string,
     symbol: string,
     uri: string,
-    initialBuyLamports: bigint = BigInt(0)
+    initialBuyLamports: bigint = BigInt(0),
+    graduationDeadline: bigint = BigInt(0),
+    payer: PublicKey = creator,
+    profile?: TokenProfileInput,
+    mintNonce?: bigint  // Set by buildCreateTokenPdaTransaction: the mint is a PDA, not a signer
   ): Promise<Transaction> {
     const [configPDA] = findConfigPDA();
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
     const [curvePDA] = findBondingCurvePDA(mintKeypair.publicKey);
     const [solVaultPDA] = findSolVaultPDA(mintKeypair.publicKey);
     const [metadataPDA] = findMetadataPDA(mintKeypair.publicKey);
     const tokenVault = await findTokenVaultAddress(mintKeypair.publicKey, curvePDA);
     const creatorTokenAccount = await getAssociatedTokenAddress(mintKeypair.publicKey, creator);
     
+    // Collection accounts are optional until the protocol collection exists (program ID = none)
+    const [collectionMint] = findCollectionMintPDA();
+    const hasCollection = (await this.connection.getAccountInfo(collectionMint)) !== null;
+    const collectionKeys = hasCollection
+      ? [
+          { pubkey: collectionMint, isSigner: false, isWritable: false },
+          { pubkey: findMetadataPDA(collectionMint)[0], isSigner: false, isWritable: true },
+          { pubkey: findMasterEditionPDA(collectionMint)[0], isSigner: false, isWritable: false },
+        ]
+      : Array(3).fill({ pubkey: PROGRAM_ID, isSigner: false, isWritable: false });
+    
     // Anchor discriminator for "create_token" = first 8 bytes of sha256("global:create_token")
-    const discriminator = Buffer.from([84, 52, 204, 228, 24, 140, 234, 75]);
+    // and for "create_token_pda" = first 8 bytes of sha256("global:create_token_pda")
+    const discriminator = mintNonce === undefined
+      ? Buffer.from([84, 52, 204, 228, 24, 140, 234, 75])
+      : Buffer.from([176, 206, 209, 49, 47, 222, 84, 253]);
     
     // Encode strings with length prefix (Borsh format)
     const nameBytes = Buffer.from(name);
@@ -311,37 +576,211 @@ export class ClawdVaultClient {
       Buffer.from([uriBytes.length, 0, 0, 0]), // u32 length
       uriBytes,
       writeU64(initialBuyLamports), // initial_buy_lamports: u64
+      writeI64(graduationDeadline), // graduation_deadline: i64
+      ...(mintNonce === undefined ? [] : [writeU64(mintNonce)]), // nonce: u64 (create_token_pda)
+      writeProfileOption(profile), // profile: Option<TokenProfileArgs>
     ]);
     
-    // Account order must match CreateToken struct in program
+    // Account order must match CreateToken struct in program (shared by create_token_pda)
     const createTokenIx = new TransactionInstruction({
       programId: PROGRAM_ID,
       keys: [
         { pubkey: creator, isSigner: true, isWritable: true },
+        { pubkey: payer, isSigner: true, isWritable: true },
         { pubkey: configPDA, isSigner: false, isWritable: true },
-        { pubkey: mintKeypair.publicKey, isSigner: true, isWritable: true },
+        { pubkey: mintKeypair.publicKey, isSigner: mintNonce === undefined, isWritable: true },
         { pubkey: metadataPDA, isSigner: false, isWritable: true },
         { pubkey: curvePDA, isSigner: false, isWritable: true },
         { pubkey: solVaultPDA, isSigner: false, isWritable: true },
         { pubkey: tokenVault, isSigner: false, isWritable: true },
         { pubkey: creatorTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: findSymbolRecordPDA(symbol)[0], isSigner: false, isWritable: true },
+        profile
+          ? { pubkey: findTokenProfilePDA(mintKeypair.publicKey)[0], isSigner: false, isWritable: true }
+          : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+        ...collectionKeys,
         { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
         { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
         { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
         { pubkey: new PublicKey('SysvarRent111111111111111111111111111111111'), isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
       ],
       data,
     });
     
     const tx = new Transaction().add(createTokenIx);
     
-    tx.feePayer = creator;
+    tx.feePayer = payer;
     tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
     
     return tx;
   }
   
+  /**
+   * Build a create_token_pda transaction
+   * 
+   * Same as buildCreateTokenTransaction, but the mint is a PDA of the creator and `nonce`
+   * (see findCreatorMintPDA / findVanityMintNonce), so no mint keypair signs.
+   */
+  async buildCreateTokenPdaTransaction(
+    creator: PublicKey,
+    nonce: bigint,
+    name: string,
+    symbol: string,
+    uri: string,
+    initialBuyLamports: bigint = BigInt(0),
+    graduationDeadline: bigint = BigInt(0),
+    payer: PublicKey = creator,
+    profile?: TokenProfileInput
+  ): Promise<Transaction> {
+    const [mint] = findCreatorMintPDA(creator, nonce);
+    return this.buildCreateTokenTransaction(
+      creator,
+      { publicKey: mint },
+      name,
+      symbol,
+      uri,
+      initialBuyLamports,
+      graduationDeadline,
+      payer,
+      profile,
+      nonce
+    );
+  }
+  
+  /**
+   * Build a create_token_2022 transaction
+   * 
+   * Same as buildCreateTokenTransaction, but the mint is a Token-2022 mint that
+   * stores its own metadata (metadata-pointer + token-metadata extensions) and joins
+   * the protocol token group once it exists.
+   */
+  async buildCreateToken2022Transaction(
+    creator: PublicKey,
+    mintKeypair: { publicKey: PublicKey },
+    name: string,
+    symbol: string,
+    uri: string,
+    initialBuyLamports: bigint = BigInt(0),
+    graduationDeadline: bigint = BigInt(0),
+    transferFeeBps: number = 0,
+    payer: PublicKey = creator,
+    profile?: TokenProfileInput,
+    mintNonce?: bigint  // Set by buildCreateToken2022PdaTransaction: the mint is a PDA, not a signer
+  ): Promise<Transaction> {
+    const [configPDA] = findConfigPDA();
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    const [curvePDA] = findBondingCurvePDA(mintKeypair.publicKey);
+    const [solVaultPDA] = findSolVaultPDA(mintKeypair.publicKey);
+    const tokenVault = await findTokenVaultAddress(mintKeypair.publicKey, curvePDA, TOKEN_2022_PROGRAM_ID);
+    const creatorTokenAccount = await getAssociatedTokenAddress(
+      mintKeypair.publicKey, creator, false, TOKEN_2022_PROGRAM_ID
+    );
+    
+    // Anchor discriminator for "create_token_2022" = first 8 bytes of sha256("global:create_token_2022")
+    // and for "create_token_2022_pda" = first 8 bytes of sha256("global:create_token_2022_pda")
+    const discriminator = mintNonce === undefined
+      ? Buffer.from([122, 75, 16, 217, 248, 141, 155, 169])
+      : Buffer.from([180, 182, 74, 46, 13, 51, 156, 152]);
+    
+    const nameBytes = Buffer.from(name);
+    const symbolBytes = Buffer.from(symbol);
+    const uriBytes = Buffer.from(uri);
+    
+    const data = Buffer.concat([
+      discriminator,
+      Buffer.from([nameBytes.length, 0, 0, 0]), // u32 length
+      nameBytes,
+      Buffer.from([symbolBytes.length, 0, 0, 0]), // u32 length
+      symbolBytes,
+      Buffer.from([uriBytes.length, 0, 0, 0]), // u32 length
+      uriBytes,
+      writeU64(initialBuyLamports), // initial_buy_lamports: u64
+      writeI64(graduationDeadline), // graduation_deadline: i64
+      writeU16(transferFeeBps), // transfer_fee_bps: u16
+      ...(mintNonce === undefined ? [] : [writeU64(mintNonce)]), // nonce: u64 (create_token_2022_pda)
+      writeProfileOption(profile), // profile: Option<TokenProfileArgs>
+    ]);
+    
+    // Account order must match CreateToken2022 struct in program (shared by create_token_2022_pda)
+    const createTokenIx = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: creator, isSigner: true, isWritable: true },
+        { pubkey: payer, isSigner: true, isWritable: true },
+        { pubkey: configPDA, isSigner: false, isWritable: true },
+        { pubkey: mintKeypair.publicKey, isSigner: mintNonce === undefined, isWritable: true },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: solVaultPDA, isSigner: false, isWritable: true },
+        { pubkey: tokenVault, isSigner: false, isWritable: true },
+        { pubkey: creatorTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: findSymbolRecordPDA(symbol)[0], isSigner: false, isWritable: true },
+        profile
+          ? { pubkey: findTokenProfilePDA(mintKeypair.publicKey)[0], isSigner: false, isWritable: true }
+          : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: findTokenGroupPDA()[0], isSigner: false, isWritable: true },
+        { pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data,
+    });
+    
+    const tx = new Transaction().add(createTokenIx);
+    
+    tx.feePayer = payer;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+    
+    return tx;
+  }
+
+  /**
+   * Build a create_token_2022_pda transaction
+   * 
+   * Same as buildCreateToken2022Transaction, but the mint is a PDA of the creator and `nonce`
+   * (see findCreatorMintPDA / findVanityMintNonce), so no mint keypair signs.
+   */
+  async buildCreateToken2022PdaTransaction(
+    creator: PublicKey,
+    nonce: bigint,
+    name: string,
+    symbol: string,
+    uri: string,
+    initialBuyLamports: bigint = BigInt(0),
+    graduationDeadline: bigint = BigInt(0),
+    transferFeeBps: number = 0,
+    payer: PublicKey = creator,
+    profile?: TokenProfileInput
+  ): Promise<Transaction> {
+    const [mint] = findCreatorMintPDA(creator, nonce);
+    return this.buildCreateToken2022Transaction(
+      creator,
+      { publicKey: mint },
+      name,
+      symbol,
+      uri,
+      initialBuyLamports,
+      graduationDeadline,
+      transferFeeBps,
+      payer,
+      profile,
+      nonce
+    );
+  }
+
+  /**
+   * Token program that owns a mint (SPL Token or Token-2022)
+   */
+  async getTokenProgram(mint: PublicKey): Promise<PublicKey> {
+    const mintAccount = await this.connection.getAccountInfo(mint);
+    if (!mintAccount) throw new Error(`Mint not found: ${mint.toBase58()}`);
+    return mintAccount.owner;
+  }
+
   /**
    * Fetch bonding curve state
    */
@@ -384,11 +823,13 @@ export class ClawdVaultClient {
     creator: PublicKey,
     feeRecipient: PublicKey
   ): Promise<Transaction> {
+    const tokenProgram = await this.getTokenProgram(mint);
     const [configPDA] = findConfigPDA();
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
     const [curvePDA] = findBondingCurvePDA(mint);
     const [solVaultPDA] = findSolVaultPDA(mint);
-    const tokenVault = await findTokenVaultAddress(mint, curvePDA);
-    const buyerTokenAccount = await getAssociatedTokenAddress(mint, buyer);
+    const tokenVault = await findTokenVaultAddress(mint, curvePDA, tokenProgram);
+    const buyerTokenAccount = await getAssociatedTokenAddress(mint, buyer, false, tokenProgram);
     
     // Anchor discriminator for "buy" = first 8 bytes of sha256("global:buy")
     const discriminator = Buffer.from([102, 6, 61, 18, 1, 218, 235, 234]);
@@ -411,9 +852,11 @@ export class ClawdVaultClient {
         { pubkey: buyerTokenAccount, isSigner: false, isWritable: true },
         { pubkey: feeRecipient, isSigner: false, isWritable: true },
         { pubkey: creator, isSigner: false, isWritable: true },
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
         { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
       ],
       data,
     });
@@ -438,11 +881,13 @@ export class ClawdVaultClient {
     creator: PublicKey,
     feeRecipient: PublicKey
   ): Promise<Transaction> {
+    const tokenProgram = await this.getTokenProgram(mint);
     const [configPDA] = findConfigPDA();
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
     const [curvePDA] = findBondingCurvePDA(mint);
     const [solVaultPDA] = findSolVaultPDA(mint);
-    const tokenVault = await findTokenVaultAddress(mint, curvePDA);
-    const sellerTokenAccount = await getAssociatedTokenAddress(mint, seller);
+    const tokenVault = await findTokenVaultAddress(mint, curvePDA, tokenProgram);
+    const sellerTokenAccount = await getAssociatedTokenAddress(mint, seller, false, tokenProgram);
     
     // Anchor discriminator for "sell" = first 8 bytes of sha256("global:sell")
     const discriminator = Buffer.from([51, 230, 133, 164, 1, 127, 131, 173]);
@@ -465,8 +910,10 @@ export class ClawdVaultClient {
         { pubkey: sellerTokenAccount, isSigner: false, isWritable: true },
         { pubkey: feeRecipient, isSigner: false, isWritable: true },
         { pubkey: creator, isSigner: false, isWritable: true },
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
       ],
       data,
     });
@@ -480,27 +927,28 @@ export class ClawdVaultClient {
 
   /**
    * Build a release_for_migration transaction
-   * Only callable by protocol authority after graduation threshold is hit
-   */
-  /**
-   * Build a release_for_migration transaction
-   * Can be signed by either authority or migration_operator
+   * Moves the curve's assets into the migration escrow (authority or migration_operator)
    */
   async buildReleaseForMigrationTx(
     operator: PublicKey,  // Either authority or migration_operator
     mint: PublicKey,
-    migrationWallet: PublicKey,
+    dex: MigrationDex = MigrationDex.RaydiumCpmm,
+    excessTokenAccount?: PublicKey,  // Required when config.excess_token_destination is set
   ): Promise<Transaction> {
+    const tokenProgram = await this.getTokenProgram(mint);
     const [curvePDA] = findBondingCurvePDA(mint);
     const [configPDA] = findConfigPDA();
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
     const [solVaultPDA] = findSolVaultPDA(mint);
-    const tokenVault = await findTokenVaultAddress(mint, curvePDA);
+    const [escrowPDA] = findMigrationEscrowPDA(mint);
+    const tokenVault = await findTokenVaultAddress(mint, curvePDA, tokenProgram);
+    const escrowTokenAccount = await getAssociatedTokenAddress(mint, escrowPDA, true, tokenProgram);
     
-    // Migration wallet's token account
-    const migrationTokenAccount = await getAssociatedTokenAddress(
-      mint,
-      migrationWallet
-    );
+    // Migration fee goes to the config fee recipient, graduation bonus to the creator
+    const configAccount = await this.connection.getAccountInfo(configPDA);
+    const curve = await this.getBondingCurve(mint);
+    if (!configAccount || !curve) throw new Error('Config or bonding curve not found');
+    const feeRecipient = new PublicKey(configAccount.data.slice(40, 72));
     
     // release_for_migration discriminator (first 8 bytes of sha256("global:release_for_migration"))
     const discriminator = Buffer.from([0xcd, 0x7d, 0xe7, 0x0e, 0x00, 0xb7, 0xca, 0xe0]);
@@ -513,13 +961,730 @@ export class ClawdVaultClient {
         { pubkey: curvePDA, isSigner: false, isWritable: true },
         { pubkey: solVaultPDA, isSigner: false, isWritable: true },
         { pubkey: tokenVault, isSigner: false, isWritable: true },
-        { pubkey: mint, isSigner: false, isWritable: false },
-        { pubkey: migrationWallet, isSigner: false, isWritable: true },
-        { pubkey: migrationTokenAccount, isSigner: false, isWritable: true },
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: mint, isSigner: false, isWritable: true },
+        { pubkey: escrowPDA, isSigner: false, isWritable: true },
+        { pubkey: escrowTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: feeRecipient, isSigner: false, isWritable: true },
+        { pubkey: curve.creator, isSigner: false, isWritable: true },
+        // Optional account: the program ID stands in for None (excess tokens are burned)
+        { pubkey: excessTokenAccount ?? PROGRAM_ID, isSigner: false, isWritable: !!excessTokenAccount },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
+        { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
       ],
-      data: discriminator,
+      data: Buffer.concat([discriminator, Buffer.from([dex])]),
+    });
+    
+    const tx = new Transaction().add(instruction);
+    tx.feePayer = operator;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+    
+    return tx;
+  }
+
+  /**
+   * Build a release_for_migration_batch transaction
+   * Releases several graduated curves at once (authority or migration_operator). Curves that
+   * fail validation are skipped; per-curve results come back as return data and events.
+   * Only burn mode is supported here (config.excess_token_destination unset).
+   */
+  async buildReleaseForMigrationBatchTx(
+    operator: PublicKey,
+    mints: PublicKey[],
+    dex: MigrationDex = MigrationDex.RaydiumCpmm,
+  ): Promise<Transaction> {
+    const [configPDA] = findConfigPDA();
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    
+    const configAccount = await this.connection.getAccountInfo(configPDA);
+    if (!configAccount) throw new Error('Config not found');
+    const feeRecipient = new PublicKey(configAccount.data.slice(40, 72));
+    
+    // Every curve in a batch must use the same token program
+    const tokenProgram = await this.getTokenProgram(mints[0]);
+    const tx = new Transaction();
+    const groupKeys = [];
+    for (const mint of mints) {
+      const [curvePDA] = findBondingCurvePDA(mint);
+      const [solVaultPDA] = findSolVaultPDA(mint);
+      const [escrowPDA] = findMigrationEscrowPDA(mint);
+      const tokenVault = await findTokenVaultAddress(mint, curvePDA, tokenProgram);
+      const escrowTokenAccount = await getAssociatedTokenAddress(mint, escrowPDA, true, tokenProgram);
+      const curve = await this.getBondingCurve(mint);
+      if (!curve) throw new Error(`Bonding curve not found for ${mint.toBase58()}`);
+      
+      // The batch instruction expects escrow token accounts to exist
+      tx.add(createAssociatedTokenAccountIdempotentInstruction(operator, escrowTokenAccount, escrowPDA, mint, tokenProgram));
+      
+      // Group order matches RELEASE_BATCH_GROUP_LEN accounts in program
+      groupKeys.push(
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: true },
+        { pubkey: solVaultPDA, isSigner: false, isWritable: true },
+        { pubkey: tokenVault, isSigner: false, isWritable: true },
+        { pubkey: escrowPDA, isSigner: false, isWritable: true },
+        { pubkey: escrowTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: curve.creator, isSigner: false, isWritable: true },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },  // No excess token account
+      );
+    }
+    
+    // release_for_migration_batch discriminator (first 8 bytes of sha256("global:release_for_migration_batch"))
+    const discriminator = Buffer.from([0x80, 0xa6, 0x6d, 0x7f, 0xfe, 0xdd, 0x74, 0x34]);
+    
+    tx.add(new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: operator, isSigner: true, isWritable: true },
+        { pubkey: configPDA, isSigner: false, isWritable: false },
+        { pubkey: feeRecipient, isSigner: false, isWritable: true },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+        ...groupKeys,
+      ],
+      data: Buffer.concat([discriminator, Buffer.from([dex])]),
+    }));
+    tx.feePayer = operator;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+    
+    return tx;
+  }
+
+  /**
+   * Build a crank_migration transaction
+   * Permissionless: moves a graduated curve into its ClawdVault pool. The caller fronts
+   * the pool account rent (refunded from the reserves) and earns a bounty out of the migration fee.
+   */
+  async buildCrankMigrationTx(
+    caller: PublicKey,
+    mint: PublicKey,
+    excessTokenAccount?: PublicKey,  // Required when config.excess_token_destination is set
+  ): Promise<Transaction> {
+    const tokenProgram = await this.getTokenProgram(mint);
+    const [curvePDA] = findBondingCurvePDA(mint);
+    const [configPDA] = findConfigPDA();
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    const [solVaultPDA] = findSolVaultPDA(mint);
+    const tokenVault = await findTokenVaultAddress(mint, curvePDA, tokenProgram);
+    const { pool, solVault: poolSolVault, lpMint } = findPoolPDAs(mint);
+    const poolTokenVault = await getAssociatedTokenAddress(mint, pool, true, tokenProgram);
+    const lpBurnAccount = await getAssociatedTokenAddress(lpMint, LP_BURN_ADDRESS, true, tokenProgram);
+    
+    const configAccount = await this.connection.getAccountInfo(configPDA);
+    const curve = await this.getBondingCurve(mint);
+    if (!configAccount || !curve) throw new Error('Config or bonding curve not found');
+    const feeRecipient = new PublicKey(configAccount.data.slice(40, 72));
+    
+    // crank_migration discriminator (first 8 bytes of sha256("global:crank_migration"))
+    const discriminator = Buffer.from([0xba, 0xa7, 0x64, 0x82, 0xfb, 0x82, 0xaa, 0x88]);
+    
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: caller, isSigner: true, isWritable: true },
+        { pubkey: configPDA, isSigner: false, isWritable: false },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: true },
+        { pubkey: solVaultPDA, isSigner: false, isWritable: true },
+        { pubkey: tokenVault, isSigner: false, isWritable: true },
+        { pubkey: pool, isSigner: false, isWritable: true },
+        { pubkey: poolSolVault, isSigner: false, isWritable: true },
+        { pubkey: poolTokenVault, isSigner: false, isWritable: true },
+        { pubkey: lpMint, isSigner: false, isWritable: true },
+        { pubkey: LP_BURN_ADDRESS, isSigner: false, isWritable: false },
+        { pubkey: lpBurnAccount, isSigner: false, isWritable: true },
+        { pubkey: feeRecipient, isSigner: false, isWritable: true },
+        { pubkey: curve.creator, isSigner: false, isWritable: true },
+        // Optional account: the program ID stands in for None (excess tokens are burned)
+        { pubkey: excessTokenAccount ?? PROGRAM_ID, isSigner: false, isWritable: !!excessTokenAccount },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
+        { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data: discriminator,
+    });
+    
+    const tx = new Transaction().add(instruction);
+    tx.feePayer = caller;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+    
+    return tx;
+  }
+
+  /**
+   * Build a claim_migration_escrow instruction
+   * Moves escrowed assets to an allow-listed migration wallet. The program rejects it unless a
+   * finalize_migration instruction for the same curve follows in the same transaction.
+   */
+  async buildClaimMigrationEscrowIx(
+    operator: PublicKey,
+    mint: PublicKey,
+    migrationWallet: PublicKey,
+  ): Promise<TransactionInstruction> {
+    const tokenProgram = await this.getTokenProgram(mint);
+    const [curvePDA] = findBondingCurvePDA(mint);
+    const [configPDA] = findConfigPDA();
+    const [escrowPDA] = findMigrationEscrowPDA(mint);
+    const escrowTokenAccount = await getAssociatedTokenAddress(mint, escrowPDA, true, tokenProgram);
+    const migrationTokenAccount = await getAssociatedTokenAddress(mint, migrationWallet, false, tokenProgram);
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    
+    // claim_migration_escrow discriminator (first 8 bytes of sha256("global:claim_migration_escrow"))
+    const discriminator = Buffer.from([0xc3, 0xef, 0x31, 0xff, 0x36, 0xc4, 0x2a, 0x60]);
+    
+    return new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: operator, isSigner: true, isWritable: false },
+        { pubkey: configPDA, isSigner: false, isWritable: false },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: false },
+        { pubkey: escrowPDA, isSigner: false, isWritable: true },
+        { pubkey: escrowTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: migrationWallet, isSigner: false, isWritable: true },
+        { pubkey: migrationTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data: discriminator,
+    });
+  }
+
+  /**
+   * Build a finalize_migration instruction
+   * Verifies the pool holds the released assets, burns or locks all of the migration
+   * wallet's LP tokens and marks the curve migrated. The migration wallet must sign, and
+   * for LpHandling.Locked the LP lock PDA's token account must exist.
+   */
+  buildFinalizeMigrationIx(
+    operator: PublicKey,
+    mint: PublicKey,
+    pool: PublicKey,
+    poolSolVault: PublicKey,
+    poolTokenVault: PublicKey,
+    migrationWallet: PublicKey,
+    lpMint: PublicKey,
+    lpHandling: LpHandling,
+    lpTokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  ): TransactionInstruction {
+    const [curvePDA] = findBondingCurvePDA(mint);
+    const [configPDA] = findConfigPDA();
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    const [lpLockPDA] = findLpLockPDA(mint);
+    const lpTokenAccount = getAssociatedTokenAddressSync(lpMint, migrationWallet, false, lpTokenProgram);
+    const lpLockTokenAccount = lpHandling === LpHandling.Locked
+      ? getAssociatedTokenAddressSync(lpMint, lpLockPDA, true, lpTokenProgram)
+      : PROGRAM_ID;
+    
+    // finalize_migration discriminator (first 8 bytes of sha256("global:finalize_migration"))
+    const discriminator = Buffer.from([0x22, 0xe8, 0xe4, 0xfc, 0x9f, 0x0e, 0x60, 0xcb]);
+    
+    return new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: operator, isSigner: true, isWritable: false },
+        { pubkey: configPDA, isSigner: false, isWritable: false },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: pool, isSigner: false, isWritable: false },
+        { pubkey: poolSolVault, isSigner: false, isWritable: false },
+        { pubkey: poolTokenVault, isSigner: false, isWritable: false },
+        { pubkey: migrationWallet, isSigner: true, isWritable: false },
+        { pubkey: lpMint, isSigner: false, isWritable: true },
+        { pubkey: lpTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: lpLockTokenAccount, isSigner: false, isWritable: lpHandling === LpHandling.Locked },  // Program id unless locked
+        { pubkey: lpLockPDA, isSigner: false, isWritable: false },
+        { pubkey: lpTokenProgram, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.concat([discriminator, Buffer.from([lpHandling])]),
+    });
+  }
+
+  /**
+   * Build a refund_migration transaction
+   * Returns escrowed assets to the curve once the migration timeout has passed (anyone can call)
+   */
+  async buildRefundMigrationTx(
+    signer: PublicKey,
+    mint: PublicKey,
+  ): Promise<Transaction> {
+    const tokenProgram = await this.getTokenProgram(mint);
+    const [curvePDA] = findBondingCurvePDA(mint);
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    const [solVaultPDA] = findSolVaultPDA(mint);
+    const [escrowPDA] = findMigrationEscrowPDA(mint);
+    const tokenVault = await findTokenVaultAddress(mint, curvePDA, tokenProgram);
+    const escrowTokenAccount = await getAssociatedTokenAddress(mint, escrowPDA, true, tokenProgram);
+    
+    // refund_migration discriminator (first 8 bytes of sha256("global:refund_migration"))
+    const discriminator = Buffer.from([0xb2, 0xb9, 0x6f, 0x59, 0x61, 0x88, 0x0d, 0x32]);
+    
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: signer, isSigner: true, isWritable: false },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: false },
+        { pubkey: escrowPDA, isSigner: false, isWritable: true },
+        { pubkey: escrowTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: solVaultPDA, isSigner: false, isWritable: true },
+        { pubkey: tokenVault, isSigner: false, isWritable: true },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data: discriminator,
+    });
+    
+    const tx = new Transaction().add(instruction);
+    tx.feePayer = signer;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+    
+    return tx;
+  }
+
+  /**
+   * Build an update_token_metadata transaction
+   * Only the creator can update, and only while the curve is still trading. A new symbol is
+   * claimed in the ticker registry and the token's claim on `currentSymbol` is released,
+   * refunding its rent to whoever paid for the record
+   */
+  async buildUpdateTokenMetadataTx(
+    creator: PublicKey,
+    mint: PublicKey,
+    name: string,
+    symbol: string,
+    uri: string,
+    currentSymbol: string,
+  ): Promise<Transaction> {
+    const tokenProgram = await this.getTokenProgram(mint);
+    const [configPDA] = findConfigPDA();
+    const [curvePDA] = findBondingCurvePDA(mint);
+    const [metadataPDA] = findMetadataPDA(mint);
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    const [oldSymbolRecord] = findSymbolRecordPDA(currentSymbol);
+    
+    // The old record refunds its rent payer (symbol string, then mint, then payer)
+    const oldRecordInfo = await this.connection.getAccountInfo(oldSymbolRecord);
+    const payerOffset = oldRecordInfo ? 8 + 4 + oldRecordInfo.data.readUInt32LE(8) + 32 : 0;
+    const oldSymbolPayer = oldRecordInfo
+      ? new PublicKey(oldRecordInfo.data.subarray(payerOffset, payerOffset + 32))
+      : creator;
+    
+    // update_token_metadata discriminator (first 8 bytes of sha256("global:update_token_metadata"))
+    const discriminator = Buffer.from([0xf3, 0x06, 0x08, 0x17, 0x7e, 0xb5, 0xfb, 0x9e]);
+    
+    const nameBytes = Buffer.from(name);
+    const symbolBytes = Buffer.from(symbol);
+    const uriBytes = Buffer.from(uri);
+    
+    const data = Buffer.concat([
+      discriminator,
+      Buffer.from([nameBytes.length, 0, 0, 0]), // u32 length
+      nameBytes,
+      Buffer.from([symbolBytes.length, 0, 0, 0]), // u32 length
+      symbolBytes,
+      Buffer.from([uriBytes.length, 0, 0, 0]), // u32 length
+      uriBytes,
+    ]);
+    
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: creator, isSigner: true, isWritable: true },
+        { pubkey: configPDA, isSigner: false, isWritable: false },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: true },
+        { pubkey: findSymbolRecordPDA(symbol)[0], isSigner: false, isWritable: true },
+        { pubkey: oldSymbolRecord, isSigner: false, isWritable: true },
+        { pubkey: oldSymbolPayer, isSigner: false, isWritable: true },
+        { pubkey: metadataPDA, isSigner: false, isWritable: true },
+        { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data,
+    });
+    
+    const tx = new Transaction().add(instruction);
+    tx.feePayer = creator;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+    
+    return tx;
+  }
+
+  /**
+   * Build a lock_metadata transaction
+   * Applies the config's metadata policy once a curve has graduated (permissionless)
+   */
+  async buildLockMetadataTx(
+    signer: PublicKey,
+    mint: PublicKey,
+  ): Promise<Transaction> {
+    const tokenProgram = await this.getTokenProgram(mint);
+    const [configPDA] = findConfigPDA();
+    const [curvePDA] = findBondingCurvePDA(mint);
+    const [metadataPDA] = findMetadataPDA(mint);
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    
+    // lock_metadata discriminator (first 8 bytes of sha256("global:lock_metadata"))
+    const discriminator = Buffer.from([0x8e, 0x5c, 0xdb, 0x4e, 0xba, 0x7e, 0xa5, 0x6e]);
+    
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: signer, isSigner: true, isWritable: false },
+        { pubkey: configPDA, isSigner: false, isWritable: false },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: true },
+        { pubkey: metadataPDA, isSigner: false, isWritable: true },
+        { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data: discriminator,
+    });
+    
+    const tx = new Transaction().add(instruction);
+    tx.feePayer = signer;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+    
+    return tx;
+  }
+
+  /**
+   * Build a start_refund transaction
+   * Permissionless once the curve's graduation deadline has passed
+   */
+  async buildStartRefundTx(
+    signer: PublicKey,
+    mint: PublicKey,
+  ): Promise<Transaction> {
+    const [curvePDA] = findBondingCurvePDA(mint);
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    
+    // start_refund discriminator (first 8 bytes of sha256("global:start_refund"))
+    const discriminator = Buffer.from([0x5f, 0x42, 0xee, 0x26, 0x67, 0xa5, 0xb2, 0x0f]);
+    
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: signer, isSigner: true, isWritable: false },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data: discriminator,
+    });
+    
+    const tx = new Transaction().add(instruction);
+    tx.feePayer = signer;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+    
+    return tx;
+  }
+
+  /**
+   * Build a redeem transaction
+   * Burns tokens of a refunding curve for a pro-rata share of its SOL
+   * `harvestSources` are token accounts whose withheld Token-2022 transfer fees are harvested first
+   */
+  async buildRedeemTx(
+    user: PublicKey,
+    mint: PublicKey,
+    tokenAmount: bigint,
+    harvestSources: PublicKey[] = [],
+  ): Promise<Transaction> {
+    const tokenProgram = await this.getTokenProgram(mint);
+    const [curvePDA] = findBondingCurvePDA(mint);
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    const [solVaultPDA] = findSolVaultPDA(mint);
+    const tokenVault = await findTokenVaultAddress(mint, curvePDA, tokenProgram);
+    const userTokenAccount = await getAssociatedTokenAddress(mint, user, false, tokenProgram);
+    
+    // redeem discriminator (first 8 bytes of sha256("global:redeem"))
+    const discriminator = Buffer.from([0xb8, 0x0c, 0x56, 0x95, 0x46, 0xc4, 0x61, 0xe1]);
+    
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: user, isSigner: true, isWritable: true },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: true },
+        { pubkey: solVaultPDA, isSigner: false, isWritable: true },
+        { pubkey: tokenVault, isSigner: false, isWritable: true },
+        { pubkey: userTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+        ...harvestSources.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+      ],
+      data: Buffer.concat([discriminator, writeU64(tokenAmount)]),
+    });
+    
+    const tx = new Transaction().add(instruction);
+    tx.feePayer = user;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+    
+    return tx;
+  }
+
+  /**
+   * Build a close_refund transaction (permissionless)
+   * Closes a refunding curve once nothing is left circulating, or with tokens unredeemed after
+   * the grace period; unredeemed SOL goes to the fee recipient
+   * `harvestSources` are token accounts whose withheld Token-2022 transfer fees are harvested first
+   */
+  async buildCloseRefundTx(
+    signer: PublicKey,
+    mint: PublicKey,
+    feeRecipient: PublicKey,
+    harvestSources: PublicKey[] = [],
+  ): Promise<Transaction> {
+    const tokenProgram = await this.getTokenProgram(mint);
+    const [configPDA] = findConfigPDA();
+    const [curvePDA] = findBondingCurvePDA(mint);
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    const [solVaultPDA] = findSolVaultPDA(mint);
+    const tokenVault = await findTokenVaultAddress(mint, curvePDA, tokenProgram);
+    
+    // close_refund discriminator (first 8 bytes of sha256("global:close_refund"))
+    const discriminator = Buffer.from([0xff, 0x4e, 0x2c, 0x3a, 0xe1, 0x0c, 0x86, 0xb6]);
+    
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: signer, isSigner: true, isWritable: false },
+        { pubkey: configPDA, isSigner: false, isWritable: false },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: true },
+        { pubkey: solVaultPDA, isSigner: false, isWritable: true },
+        { pubkey: tokenVault, isSigner: false, isWritable: false },
+        { pubkey: feeRecipient, isSigner: false, isWritable: true },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+        ...harvestSources.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+      ],
+      data: discriminator,
+    });
+    
+    const tx = new Transaction().add(instruction);
+    tx.feePayer = signer;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+    
+    return tx;
+  }
+
+  /**
+   * Build a set_token_profile transaction (creator only)
+   * Creates the profile if the launch didn't (creator pays rent); every call replaces all fields
+   */
+  async buildSetTokenProfileTx(
+    creator: PublicKey,
+    mint: PublicKey,
+    profile: TokenProfileInput,
+  ): Promise<Transaction> {
+    const [curvePDA] = findBondingCurvePDA(mint);
+    const [profilePDA] = findTokenProfilePDA(mint);
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    
+    // set_token_profile discriminator (first 8 bytes of sha256("global:set_token_profile"))
+    const discriminator = Buffer.from([0x19, 0xf7, 0x7c, 0x1d, 0x9a, 0x3c, 0x79, 0x48]);
+    
+    const data = Buffer.concat([
+      discriminator,
+      writeString(profile.website ?? ''),
+      writeString(profile.twitter ?? ''),
+      writeString(profile.telegram ?? ''),
+      writeString(profile.agentId ?? ''),
+      writeString(profile.description ?? ''),
+    ]);
+    
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: creator, isSigner: true, isWritable: true },
+        { pubkey: curvePDA, isSigner: false, isWritable: false },
+        { pubkey: profilePDA, isSigner: false, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data,
+    });
+    
+    const tx = new Transaction().add(instruction);
+    tx.feePayer = creator;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+    
+    return tx;
+  }
+
+  /**
+   * Build a propose_creator transaction (current creator only)
+   * The new creator takes over once it sends `buildAcceptCreatorTx`; PublicKey.default cancels a proposal
+   */
+  async buildProposeCreatorTx(
+    creator: PublicKey,
+    mint: PublicKey,
+    newCreator: PublicKey,
+  ): Promise<Transaction> {
+    const [curvePDA] = findBondingCurvePDA(mint);
+    const tokenProgram = await this.getTokenProgram(mint);
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    
+    // propose_creator discriminator (first 8 bytes of sha256("global:propose_creator"))
+    const discriminator = Buffer.from([0x60, 0xdc, 0xf0, 0x78, 0xda, 0xbd, 0x30, 0xaa]);
+    
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: creator, isSigner: true, isWritable: false },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: true },
+        { pubkey: findMetadataPDA(mint)[0], isSigner: false, isWritable: true },
+        { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.concat([discriminator, newCreator.toBuffer()]),
+    });
+    
+    const tx = new Transaction().add(instruction);
+    tx.feePayer = creator;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+    
+    return tx;
+  }
+
+  /**
+   * Build an accept_creator transaction (proposed creator only)
+   * For legacy tokens the new creator also becomes the verified Metaplex creator, and
+   * metadata locked under CreatorAuthority hands its update authority to the new creator
+   */
+  async buildAcceptCreatorTx(
+    newCreator: PublicKey,
+    mint: PublicKey,
+  ): Promise<Transaction> {
+    const [curvePDA] = findBondingCurvePDA(mint);
+    const tokenProgram = await this.getTokenProgram(mint);
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    
+    // accept_creator discriminator (first 8 bytes of sha256("global:accept_creator"))
+    const discriminator = Buffer.from([0xe4, 0xd6, 0x2c, 0xd6, 0x84, 0x32, 0x14, 0x38]);
+    
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: newCreator, isSigner: true, isWritable: false },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: true },
+        { pubkey: findMetadataPDA(mint)[0], isSigner: false, isWritable: true },
+        { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data: discriminator,
+    });
+    
+    const tx = new Transaction().add(instruction);
+    tx.feePayer = newCreator;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+    
+    return tx;
+  }
+
+  /**
+   * Build a claim_transfer_fees transaction (creator only)
+   * Harvests withheld Token-2022 transfer fees from `sources` into the mint, then withdraws them to the creator
+   */
+  async buildClaimTransferFeesTx(
+    creator: PublicKey,
+    mint: PublicKey,
+    sources: PublicKey[] = [],
+  ): Promise<Transaction> {
+    const [curvePDA] = findBondingCurvePDA(mint);
+    const [feeAuthorityPDA] = findTransferFeeAuthorityPDA(mint);
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    const creatorTokenAccount = await getAssociatedTokenAddress(mint, creator, false, TOKEN_2022_PROGRAM_ID);
+    
+    // claim_transfer_fees discriminator (first 8 bytes of sha256("global:claim_transfer_fees"))
+    const discriminator = Buffer.from([0xff, 0x32, 0xfc, 0x6d, 0xe7, 0xfc, 0xf8, 0x47]);
+    
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: creator, isSigner: true, isWritable: true },
+        { pubkey: curvePDA, isSigner: false, isWritable: false },
+        { pubkey: mint, isSigner: false, isWritable: true },
+        { pubkey: feeAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: creatorTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+        ...sources.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+      ],
+      data: discriminator,
+    });
+    
+    const tx = new Transaction().add(instruction);
+    tx.feePayer = creator;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+    
+    return tx;
+  }
+
+  /**
+   * Build a record_migration transaction
+   * Backfills the pool for curves migrated before it was recorded on-chain (authority or migration_operator)
+   * The LP mint is read from the pool on-chain
+   */
+  async buildRecordMigrationTx(
+    operator: PublicKey,
+    mint: PublicKey,
+    pool: PublicKey,
+    lpHandling: LpHandling,
+  ): Promise<Transaction> {
+    const [curvePDA] = findBondingCurvePDA(mint);
+    const [configPDA] = findConfigPDA();
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
+    
+    // record_migration discriminator (first 8 bytes of sha256("global:record_migration"))
+    const discriminator = Buffer.from([0xc1, 0x3a, 0x2c, 0xfe, 0x0b, 0x03, 0x4e, 0x75]);
+    
+    const data = Buffer.concat([
+      discriminator,
+      Buffer.from([lpHandling]),
+    ]);
+    
+    const instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: operator, isSigner: true, isWritable: false },
+        { pubkey: configPDA, isSigner: false, isWritable: false },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: pool, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data,
     });
     
     const tx = new Transaction().add(instruction);
@@ -538,6 +1703,7 @@ export class ClawdVaultClient {
     newAuthority: PublicKey,
   ): Promise<Transaction> {
     const [configPDA] = findConfigPDA();
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
     
     // transfer_authority discriminator (first 8 bytes of sha256("global:transfer_authority"))
     const discriminator = Buffer.from([0x52, 0xb6, 0x2e, 0x79, 0x3f, 0x1b, 0x9c, 0xda]);
@@ -553,6 +1719,8 @@ export class ClawdVaultClient {
       keys: [
         { pubkey: currentAuthority, isSigner: true, isWritable: false },
         { pubkey: configPDA, isSigner: false, isWritable: true },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
       ],
       data,
     });
@@ -573,6 +1741,7 @@ export class ClawdVaultClient {
     newOperator: PublicKey,
   ): Promise<Transaction> {
     const [configPDA] = findConfigPDA();
+    const [eventAuthorityPDA] = findEventAuthorityPDA();
     
     // set_migration_operator discriminator (first 8 bytes of sha256("global:set_migration_operator"))
     const discriminator = Buffer.from([0x8b, 0x3a, 0xc0, 0x45, 0x6f, 0x29, 0x1d, 0x58]);
@@ -588,6 +1757,8 @@ export class ClawdVaultClient {
       keys: [
         { pubkey: authority, isSigner: true, isWritable: false },
         { pubkey: configPDA, isSigner: false, isWritable: true },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
       ],
       data,
     });
@@ -12,10 +12,17 @@ import {
   getCpmmPdaAmmConfigId,
 } from '@raydium-io/raydium-sdk-v2';
 import { 
+  AddressLookupTableAccount,
+  AddressLookupTableProgram,
   Connection, 
   Keypair, 
   PublicKey,
+  Transaction,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
   clusterApiUrl,
+  sendAndConfirmTransaction,
 } from '@solana/web3.js';
 import { TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, NATIVE_MINT } from '@solana/spl-token';
 import BN from 'bn.js';
@@ -45,6 +52,56 @@ const getMigrationWallet = (): Keypair | null => {
 
 let raydiumInstance: Raydium | null = null;
 
+// Addresses per extend_lookup_table instruction (keeps each extend transaction under the size limit)
+const LOOKUP_TABLE_EXTEND_CHUNK = 20;
+
+/**
+ * Create an address lookup table holding every non-signer account of `instructions`
+ * and wait until it can be used
+ */
+async function createLookupTable(
+  connection: Connection,
+  authority: Keypair,
+  instructions: TransactionInstruction[],
+): Promise<AddressLookupTableAccount> {
+  const addresses = new Map<string, PublicKey>();
+  for (const ix of instructions) {
+    for (const key of ix.keys) {
+      if (!key.isSigner) addresses.set(key.pubkey.toBase58(), key.pubkey);
+    }
+  }
+
+  const recentSlot = await connection.getSlot('finalized');
+  const [createIx, tableAddress] = AddressLookupTableProgram.createLookupTable({
+    authority: authority.publicKey,
+    payer: authority.publicKey,
+    recentSlot,
+  });
+
+  const keys = [...addresses.values()];
+  for (let i = 0; i < keys.length; i += LOOKUP_TABLE_EXTEND_CHUNK) {
+    const extendIx = AddressLookupTableProgram.extendLookupTable({
+      lookupTable: tableAddress,
+      authority: authority.publicKey,
+      payer: authority.publicKey,
+      addresses: keys.slice(i, i + LOOKUP_TABLE_EXTEND_CHUNK),
+    });
+    const tx = i === 0 ? new Transaction().add(createIx, extendIx) : new Transaction().add(extendIx);
+    await sendAndConfirmTransaction(connection, tx, [authority]);
+  }
+
+  // Extended addresses become usable from the next slot
+  const extendedAt = await connection.getSlot('confirmed');
+  while ((await connection.getSlot('confirmed')) <= extendedAt) {
+    await new Promise((resolve) => setTimeout(resolve, 400));
+  }
+
+  const table = (await connection.getAddressLookupTable(tableAddress)).value;
+  if (!table) throw new Error(`Lookup table not found: ${tableAddress.toBase58()}`);
+  console.log(`  Lookup table: ${tableAddress.toBase58()} (${keys.length} addresses)`);
+  return table;
+}
+
 /**
  * Initialize Raydium SDK
  */
@@ -79,12 +136,26 @@ export async function initRaydium(): Promise<Raydium> {
  * @param tokenMint - The token mint address
  * @param tokenAmount - Amount of tokens (in base units)
  * @param solAmount - Amount of SOL (in lamports)
+ * @param tokenProgram - Token program of the mint (SPL Token or Token-2022)
+ * @param wrap - Instructions to run around pool creation in the same transaction
+ *   (claim the migration escrow before, finalize the migration after)
  * @returns Pool creation result
  */
 export async function createCpmmPool(
   tokenMint: string,
   tokenAmount: bigint,
   solAmount: bigint,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  wrap?: {
+    signer: Keypair;
+    preInstructions: TransactionInstruction[];
+    postInstructions: (pool: {
+      poolId: PublicKey;
+      lpMint: PublicKey;
+      solVault: PublicKey;
+      tokenVault: PublicKey;
+    }) => TransactionInstruction[];
+  },
 ): Promise<{
   poolId: string;
   lpMint: string;
@@ -122,7 +193,7 @@ export async function createCpmmPool(
   // Token info for our token
   const tokenMintInfo = {
     address: tokenMint,
-    programId: TOKEN_PROGRAM_ID.toBase58(),
+    programId: tokenProgram.toBase58(),
     decimals: 6, // Our tokens use 6 decimals
   };
 
@@ -150,7 +221,7 @@ export async function createCpmmPool(
   console.log(`  Fee config: ${feeConfig.id}`);
 
   // Create pool
-  const { execute, extInfo } = await raydium.cpmm.createPool({
+  const { execute, extInfo, transaction, signers } = await raydium.cpmm.createPool({
     programId,
     poolFeeAccount,
     mintA,
@@ -163,15 +234,61 @@ export async function createCpmmPool(
     ownerInfo: {
       useSOLBalance: true, // Use native SOL balance
     },
-    txVersion: TxVersion.V0,
+    // Legacy output when wrapping, only to get at the instructions; they're sent as v0 below
+    txVersion: wrap ? TxVersion.LEGACY : TxVersion.V0,
     computeBudgetConfig: {
       units: 600000,
       microLamports: 100000, // Priority fee
     },
   });
 
-  // Execute transaction
-  const { txId } = await execute({ sendAndConfirm: true });
+  let txId: string;
+  if (wrap) {
+    // Pool creation must be atomic with the escrow claim and finalization. Together they
+    // reference too many accounts for a legacy transaction, so send v0 with a lookup table.
+    const { vaultA, vaultB, poolId, lpMint } = extInfo.address;
+    const instructions = [
+      ...wrap.preInstructions,
+      ...(transaction as Transaction).instructions,
+      ...wrap.postInstructions({
+        poolId,
+        lpMint,
+        solVault: isTokenMintA ? vaultB : vaultA,
+        tokenVault: isTokenMintA ? vaultA : vaultB,
+      }),
+    ];
+
+    const connection = getConnection();
+    const payer = getMigrationWallet()!;
+    const lookupTable = await createLookupTable(connection, payer, instructions);
+    const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+    const message = new TransactionMessage({
+      payerKey: payer.publicKey,
+      recentBlockhash: blockhash,
+      instructions,
+    }).compileToV0Message([lookupTable]);
+    const tx = new VersionedTransaction(message);
+
+    // Operator and migration wallet may be the same key
+    const txSigners = new Map<string, Keypair>();
+    for (const signer of [payer, wrap.signer, ...(signers as Keypair[])]) {
+      txSigners.set(signer.publicKey.toBase58(), signer);
+    }
+    tx.sign([...txSigners.values()]);
+    console.log(`  Transaction size: ${tx.serialize().length} bytes`);
+
+    txId = await connection.sendTransaction(tx);
+    const confirmation = await connection.confirmTransaction(
+      { signature: txId, blockhash, lastValidBlockHeight },
+      'confirmed',
+    );
+    if (confirmation.value.err) {
+      throw new Error(`Pool transaction failed: ${JSON.stringify(confirmation.value.err)}`);
+    }
+  } else {
+    // Execute transaction
+    ({ txId } = await execute({ sendAndConfirm: true }));
+  }
 
   console.log(`✅ CPMM pool created!`);
   console.log(`  Pool ID: ${extInfo.address.poolId.toBase58()}`);
@@ -2,8 +2,9 @@
  * Core trade sync logic - shared between API route and cron
  */
 
-import { Connection, PublicKey, clusterApiUrl, LAMPORTS_PER_SOL } from '@solana/web3.js';
-import { PROGRAM_ID } from '@/lib/anchor/client';
+import { Connection, PublicKey, clusterApiUrl, LAMPORTS_PER_SOL, VersionedTransactionResponse } from '@solana/web3.js';
+import bs58 from 'bs58';
+import { PROGRAM_ID, findEventAuthorityPDA } from '@/lib/anchor/client';
 import { getTradeBySignature, recordTrade, getToken } from '@/lib/db';
 import { announceTrade } from '@/lib/moltx';
 
@@ -11,6 +12,16 @@ import { announceTrade } from '@/lib/moltx';
 const TRADE_EVENT_DISCRIMINATOR = Buffer.from([189, 219, 127, 211, 78, 230, 97, 238]);
 const TOKEN_CREATED_DISCRIMINATOR = Buffer.from([96, 122, 113, 138, 50, 227, 149, 57]); // sha256("event:TokenCreatedEvent")
 
+// Prefix of self-CPI event instruction data (anchor_lang::event::EVENT_IX_TAG_LE)
+const EVENT_IX_TAG = Buffer.from([0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d]);
+
+// Only our program can sign self-CPI events with this PDA
+const [EVENT_AUTHORITY] = findEventAuthorityPDA();
+
+// First slot of the self-CPI event build; log-based events are only read before it
+// (unset = never, so "Program data:" logs are not trusted at all)
+const EVENT_CPI_CUTOVER_SLOT = Number(process.env.EVENT_CPI_CUTOVER_SLOT ?? 0);
+
 interface ParsedTradeEvent {
   mint: string;
   trader: string;
@@ -30,6 +41,8 @@ interface ParsedTokenCreatedEvent {
   name: string;
   symbol: string;
   uri: string;
+  graduationDeadline: bigint;  // 0 = no deadline
+  transferFeeBps: number;      // 0 = no Token-2022 transfer fee
   timestamp: bigint;
 }
 
@@ -49,112 +62,178 @@ function getConnection(): Connection {
 }
 
 /**
- * Parse TradeEvent from transaction logs
+ * Collect raw program events (discriminator + data) from a transaction.
+ *
+ * The program emits events via self-CPI (`emit_cpi!`), so they land in inner
+ * instruction data, which cannot be truncated like logs. Only inner instructions
+ * to our program signed by our event authority are trusted; anyone can log
+ * "Program data:" lines from their own program.
+ *
+ * Transactions from before the switch (slot < EVENT_CPI_CUTOVER_SLOT) only carry
+ * log lines, which are read while our program is the innermost running program.
+ */
+export function getProgramEvents(tx: VersionedTransactionResponse): Buffer[] {
+  const events: Buffer[] = [];
+  if (!tx.meta || tx.meta.err) {
+    return events;
+  }
+  
+  const accountKeys = tx.transaction.message.getAccountKeys({
+    accountKeysFromLookups: tx.meta.loadedAddresses,
+  });
+  for (const inner of tx.meta.innerInstructions ?? []) {
+    for (const ix of inner.instructions) {
+      if (
+        !accountKeys.get(ix.programIdIndex)?.equals(PROGRAM_ID) ||
+        ix.accounts.length !== 1 ||
+        !accountKeys.get(ix.accounts[0])?.equals(EVENT_AUTHORITY)
+      ) {
+        continue;
+      }
+      const data = Buffer.from(bs58.decode(ix.data));
+      if (data.length >= 16 && data.subarray(0, 8).equals(EVENT_IX_TAG)) {
+        events.push(data.subarray(8));
+      }
+    }
+  }
+  
+  if (tx.slot < EVENT_CPI_CUTOVER_SLOT) {
+    events.push(...getLegacyLogEvents(tx.meta.logMessages ?? []));
+  }
+  
+  return events;
+}
+
+/**
+ * "Program data:" lines logged by our program itself, tracked through the
+ * "Program <id> invoke [n]" / "Program <id> success|failed" frames
  */
-function parseTradeEventFromLogs(logs: string[]): ParsedTradeEvent | null {
+function getLegacyLogEvents(logs: string[]): Buffer[] {
+  const events: Buffer[] = [];
+  const programId = PROGRAM_ID.toBase58();
+  const stack: string[] = [];
+  
   for (const log of logs) {
-    if (log.startsWith('Program data: ')) {
-      const base64Data = log.slice('Program data: '.length);
-      try {
-        const data = Buffer.from(base64Data, 'base64');
+    const invoke = log.match(/^Program (\w+) invoke \[\d+\]$/);
+    if (invoke) {
+      stack.push(invoke[1]);
+      continue;
+    }
+    if (/^Program \w+ (success|failed)/.test(log)) {
+      stack.pop();
+      continue;
+    }
+    if (log.startsWith('Program data: ') && stack[stack.length - 1] === programId) {
+      events.push(Buffer.from(log.slice('Program data: '.length), 'base64'));
+    }
+  }
+  
+  return events;
+}
+
+/**
+ * Parse TradeEvent from program events
+ */
+export function parseTradeEvent(events: Buffer[]): ParsedTradeEvent | null {
+  for (const data of events) {
+    try {
+      if (data.length >= 8 && data.slice(0, 8).equals(TRADE_EVENT_DISCRIMINATOR)) {
+        let offset = 8;
         
-        if (data.length >= 8 && data.slice(0, 8).equals(TRADE_EVENT_DISCRIMINATOR)) {
-          let offset = 8;
-          
-          const mint = new PublicKey(data.slice(offset, offset + 32)).toBase58();
-          offset += 32;
-          
-          const trader = new PublicKey(data.slice(offset, offset + 32)).toBase58();
-          offset += 32;
-          
-          const isBuy = data[offset] === 1;
-          offset += 1;
-          
-          const solAmount = data.readBigUInt64LE(offset);
-          offset += 8;
-          
-          const tokenAmount = data.readBigUInt64LE(offset);
-          offset += 8;
-          
-          const protocolFee = data.readBigUInt64LE(offset);
-          offset += 8;
-          
-          const creatorFee = data.readBigUInt64LE(offset);
-          offset += 8;
-          
-          const virtualSolReserves = data.readBigUInt64LE(offset);
-          offset += 8;
-          
-          const virtualTokenReserves = data.readBigUInt64LE(offset);
-          offset += 8;
-          
-          const timestamp = data.readBigInt64LE(offset);
-          
-          return {
-            mint,
-            trader,
-            isBuy,
-            solAmount,
-            tokenAmount,
-            protocolFee,
-            creatorFee,
-            virtualSolReserves,
-            virtualTokenReserves,
-            timestamp,
-          };
-        }
-      } catch (e) {
-        // Not our event
+        const mint = new PublicKey(data.slice(offset, offset + 32)).toBase58();
+        offset += 32;
+        
+        const trader = new PublicKey(data.slice(offset, offset + 32)).toBase58();
+        offset += 32;
+        
+        const isBuy = data[offset] === 1;
+        offset += 1;
+        
+        const solAmount = data.readBigUInt64LE(offset);
+        offset += 8;
+        
+        const tokenAmount = data.readBigUInt64LE(offset);
+        offset += 8;
+        
+        const protocolFee = data.readBigUInt64LE(offset);
+        offset += 8;
+        
+        const creatorFee = data.readBigUInt64LE(offset);
+        offset += 8;
+        
+        const virtualSolReserves = data.readBigUInt64LE(offset);
+        offset += 8;
+        
+        const virtualTokenReserves = data.readBigUInt64LE(offset);
+        offset += 8;
+        
+        const timestamp = data.readBigInt64LE(offset);
+        
+        return {
+          mint,
+          trader,
+          isBuy,
+          solAmount,
+          tokenAmount,
+          protocolFee,
+          creatorFee,
+          virtualSolReserves,
+          virtualTokenReserves,
+          timestamp,
+        };
       }
+    } catch (e) {
+      // Not our event
     }
   }
   return null;
 }
 
 /**
- * Parse TokenCreatedEvent from transaction logs
+ * Parse TokenCreatedEvent from program events
  */
-function parseTokenCreatedEventFromLogs(logs: string[]): ParsedTokenCreatedEvent | null {
-  for (const log of logs) {
-    if (log.startsWith('Program data: ')) {
-      const base64Data = log.slice('Program data: '.length);
-      try {
-        const data = Buffer.from(base64Data, 'base64');
+function parseTokenCreatedEvent(events: Buffer[]): ParsedTokenCreatedEvent | null {
+  for (const data of events) {
+    try {
+      if (data.length >= 8 && data.slice(0, 8).equals(TOKEN_CREATED_DISCRIMINATOR)) {
+        let offset = 8;
         
-        if (data.length >= 8 && data.slice(0, 8).equals(TOKEN_CREATED_DISCRIMINATOR)) {
-          let offset = 8;
-          
-          const mint = new PublicKey(data.slice(offset, offset + 32)).toBase58();
-          offset += 32;
-          
-          const creator = new PublicKey(data.slice(offset, offset + 32)).toBase58();
-          offset += 32;
-          
-          // Read name (4-byte length prefix + string)
-          const nameLen = data.readUInt32LE(offset);
-          offset += 4;
-          const name = data.slice(offset, offset + nameLen).toString('utf8');
-          offset += nameLen;
-          
-          // Read symbol (4-byte length prefix + string)
-          const symbolLen = data.readUInt32LE(offset);
-          offset += 4;
-          const symbol = data.slice(offset, offset + symbolLen).toString('utf8');
-          offset += symbolLen;
-          
-          // Read uri (4-byte length prefix + string)
-          const uriLen = data.readUInt32LE(offset);
-          offset += 4;
-          const uri = data.slice(offset, offset + uriLen).toString('utf8');
-          offset += uriLen;
-          
-          const timestamp = data.readBigInt64LE(offset);
-          
-          return { mint, creator, name, symbol, uri, timestamp };
-        }
-      } catch (e) {
-        // Not our event
+        const mint = new PublicKey(data.slice(offset, offset + 32)).toBase58();
+        offset += 32;
+        
+        const creator = new PublicKey(data.slice(offset, offset + 32)).toBase58();
+        offset += 32;
+        
+        // Read name (4-byte length prefix + string)
+        const nameLen = data.readUInt32LE(offset);
+        offset += 4;
+        const name = data.slice(offset, offset + nameLen).toString('utf8');
+        offset += nameLen;
+        
+        // Read symbol (4-byte length prefix + string)
+        const symbolLen = data.readUInt32LE(offset);
+        offset += 4;
+        const symbol = data.slice(offset, offset + symbolLen).toString('utf8');
+        offset += symbolLen;
+        
+        // Read uri (4-byte length prefix + string)
+        const uriLen = data.readUInt32LE(offset);
+        offset += 4;
+        const uri = data.slice(offset, offset + uriLen).toString('utf8');
+        offset += uriLen;
+        
+        const graduationDeadline = data.readBigInt64LE(offset);
+        offset += 8;
+        
+        const transferFeeBps = data.readUInt16LE(offset);
+        offset += 2;
+        
+        const timestamp = data.readBigInt64LE(offset);
+        
+        return { mint, creator, name, symbol, uri, graduationDeadline, transferFeeBps, timestamp };
       }
+    } catch (e) {
+      // Not our event
     }
   }
   return null;
@@ -222,12 +301,14 @@ export async function syncTrades(options: {
           maxSupportedTransactionVersion: 0,
         });
         
-        if (!tx?.meta?.logMessages) {
+        if (!tx?.meta) {
           continue;
         }
         
+        const events = getProgramEvents(tx);
+        
         // Try to parse TradeEvent first
-        const tradeEvent = parseTradeEventFromLogs(tx.meta.logMessages);
+        const tradeEvent = parseTradeEvent(events);
         
         if (tradeEvent) {
           // Filter by mint if specified
@@ -283,7 +364,7 @@ export async function syncTrades(options: {
         }
         
         // Try to parse TokenCreatedEvent (includes initial buy if any)
-        const createEvent = parseTokenCreatedEventFromLogs(tx.meta.logMessages);
+        const createEvent = parseTokenCreatedEvent(events);
         if (createEvent) {
           // Filter by mint if specified
           if (mintFilter && createEvent.mint !== mintFilter) {
@@ -300,7 +381,7 @@ export async function syncTrades(options: {
           }
           
           // Check for initial buy in the same transaction
-          const initialBuy = parseInitialBuyFromLogs(tx.meta.logMessages);
+          const initialBuy = parseInitialBuyFromLogs(tx.meta.logMessages ?? []);
           if (initialBuy && initialBuy.solAmount > 0) {
             console.log(`🎯 Found initial buy: ${initialBuy.solAmount} SOL`);
             
@@ -1,12 +1,49 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, MintTo};
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as sysvar_instructions,
+    load_current_index_checked,
+    load_instruction_at_checked,
+};
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{spl_token::native_mint::ID as NATIVE_MINT, Token};
+use anchor_spl::token_interface::{
+    self,
+    spl_token_2022::{
+        extension::{
+            transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType,
+            StateWithExtensions,
+        },
+        instruction::AuthorityType,
+        state::Mint as MintState,
+    },
+    Burn, GroupMemberPointerInitialize, HarvestWithheldTokensToMint, InitializeMint2, Mint, MetadataPointerInitialize, MintTo,
+    SetAuthority, Token2022, TokenAccount, TokenInterface, TokenMetadataInitialize,
+    TokenGroupInitialize, TokenMemberInitialize, TokenMetadataUpdateAuthority, TokenMetadataUpdateField,
+    TransferChecked, TransferFeeInitialize,
+    WithdrawWithheldTokensFromMint,
+};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken};
+use spl_pod::optional_keys::OptionalNonZeroPubkey;
+use spl_token_metadata_interface::state::{Field, TokenMetadata};
 use anchor_spl::metadata::{
+    create_master_edition_v3,
     create_metadata_accounts_v3,
-    mpl_token_metadata::types::DataV2,
+    remove_creator_verification,
+    sign_metadata,
+    update_metadata_accounts_v2,
+    verify_sized_collection_item,
+    mpl_token_metadata::{
+        accounts::Metadata as MetadataAccountData,
+        types::{Collection, CollectionDetails, Creator, DataV2},
+    },
+    CreateMasterEditionV3,
     CreateMetadataAccountsV3,
     Metadata,
+    RemoveCreatorVerification,
+    SignMetadata,
+    UpdateMetadataAccountsV2,
+    VerifySizedCollectionItem,
 };
 
 #[cfg(not(feature = "no-entrypoint"))]
@@ -25,10 +62,23 @@ security_txt! {
 
 declare_id!("GUyF2TVe32Cid4iGVt2F6wPYDhLSVmTUZBj2974outYM");
 
+/// `msg!` that only runs with the `verbose-logs` feature (devnet builds).
+/// Indexers read events, so mainnet builds skip the formatting cost entirely.
+macro_rules! log {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "verbose-logs") {
+            msg!($($arg)*);
+        }
+    };
+}
+
 // ============================================================================
 // CONSTANTS
 // ============================================================================
 
+/// Token decimals
+pub const TOKEN_DECIMALS: u8 = 6;
+
 /// Total token supply (1 billion with 6 decimals)
 pub const TOTAL_SUPPLY: u64 = 1_000_000_000_000_000; // 1B * 10^6
 
@@ -53,10 +103,137 @@ pub const TOTAL_FEE_BPS: u64 = 100;
 /// Basis points denominator
 pub const BPS_DENOMINATOR: u64 = 10_000;
 
+/// Post-graduation pool swap fee to protocol in basis points (0.15%)
+pub const POOL_PROTOCOL_FEE_BPS: u64 = 15;
+
+/// Post-graduation pool swap fee to creator in basis points (0.10%)
+pub const POOL_CREATOR_FEE_BPS: u64 = 10;
+
+/// Total pool swap fee in basis points (0.25%)
+pub const POOL_TOTAL_FEE_BPS: u64 = 25;
+
+/// LP mint decimals for ClawdVault pools
+pub const LP_DECIMALS: u8 = 9;
+
+/// Initial pool LP tokens are minted to this address's ATA, making the liquidity permanent
+pub const LP_BURN_ADDRESS: Pubkey = pubkey!("1nc1nerator11111111111111111111111111111111");
+
+/// Max bounty paid to the `crank_migration` caller out of the migration fee (0.02 SOL)
+pub const CRANK_BOUNTY_LAMPORTS: u64 = 20_000_000;
+
+/// Accounts per curve in `release_for_migration_batch` remaining accounts
+pub const RELEASE_BATCH_GROUP_LEN: usize = 8;
+
+/// Max curves per `release_for_migration_batch` call
+pub const MAX_RELEASE_BATCH: usize = 8;
+
+/// Max migration fee in basis points (10%)
+pub const MAX_MIGRATION_FEE_BPS: u16 = 1_000;
+
+/// Upper bound for `Config::max_transfer_fee_bps` (10%)
+pub const MAX_TRANSFER_FEE_BPS: u16 = 1_000;
+
+/// Released assets can be refunded to the curve if the migration isn't finalized within this window (24 hours)
+pub const MIGRATION_TIMEOUT_SECONDS: i64 = 24 * 60 * 60;
+
+/// After this long past the graduation deadline a refunding curve can be closed with tokens
+/// still unredeemed (30 days)
+pub const REFUND_GRACE_PERIOD: i64 = 30 * 24 * 60 * 60;
+
+/// Max entries in each `Config` migration allow-list (and in the queue of pending approvals)
+pub const MAX_MIGRATION_DESTINATIONS: usize = 8;
+
+/// Delay between queueing a migration destination and applying it (48 hours)
+/// `short-timelock` builds use 5 seconds so the local-validator tests can wait it out
+pub const MIGRATION_DESTINATION_DELAY: i64 = if cfg!(feature = "short-timelock") { 5 } else { 48 * 60 * 60 };
+
+/// Max entries in `Config::uri_prefixes`
+pub const MAX_URI_PREFIXES: usize = 8;
+
+/// Max length of an allowed metadata URI prefix
+pub const MAX_URI_PREFIX_LEN: usize = 64;
+
+/// `TokenProfile` field limits (bytes)
+pub const MAX_PROFILE_WEBSITE_LEN: usize = 64;
+pub const MAX_PROFILE_HANDLE_LEN: usize = 32;  // X and Telegram
+pub const MAX_PROFILE_AGENT_LEN: usize = 32;
+pub const MAX_PROFILE_DESCRIPTION_LEN: usize = 200;
+
+/// Raydium CPMM programs whose `PoolState` layout `finalize_migration` decodes
+pub const RAYDIUM_CPMM_PROGRAMS: [Pubkey; 2] = [
+    pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP5C"), // Raydium CPMM
+    pubkey!("DRaycpLY18LhpbydsBWbVJtxpNv9oXPgjRSfpF2bWpYb"), // Raydium CPMM (devnet)
+];
+
+/// AMM programs approved on `initialize` / `resize_config` (see `set_migration_destination`)
+/// Only programs with a pool decoder can be allow-listed
+pub const DEFAULT_AMM_PROGRAMS: [Pubkey; 2] = RAYDIUM_CPMM_PROGRAMS;
+
+/// Raydium CPMM `PoolState` account discriminator (sha256("account:PoolState")[..8])
+pub const RAYDIUM_CPMM_POOL_DISCRIMINATOR: [u8; 8] = [247, 237, 227, 245, 215, 195, 222, 70];
+
+/// Compute unit limits per instruction for mainnet builds (without `verbose-logs`).
+/// Upper bounds with headroom over the worst path (ATA creation, initial buy),
+/// meant for `SetComputeUnitLimit` instead of the 200k default. Exported in the IDL;
+/// the local-validator tests check measured usage against them.
+#[constant]
+pub const CU_LIMIT_CREATE_TOKEN: u32 = 300_000;
+#[constant]
+pub const CU_LIMIT_CREATE_TOKEN_2022: u32 = 250_000;
+#[constant]
+pub const CU_LIMIT_BUY: u32 = 100_000;
+#[constant]
+pub const CU_LIMIT_SELL: u32 = 70_000;
+#[constant]
+pub const CU_LIMIT_RELEASE_FOR_MIGRATION: u32 = 60_000;
+#[constant]
+pub const CU_LIMIT_RELEASE_FOR_MIGRATION_BATCH_PER_CURVE: u32 = 70_000;  // Plus CU_LIMIT_ADMIN per batch
+#[constant]
+pub const CU_LIMIT_CRANK_MIGRATION: u32 = 250_000;
+#[constant]
+pub const CU_LIMIT_SWAP: u32 = 120_000;
+#[constant]
+pub const CU_LIMIT_DEPOSIT: u32 = 120_000;
+#[constant]
+pub const CU_LIMIT_WITHDRAW: u32 = 100_000;
+#[constant]
+pub const CU_LIMIT_REDEEM: u32 = 80_000;  // Plus ~5,000 per extra harvested token account
+#[constant]
+pub const CU_LIMIT_CLOSE_REFUND: u32 = 50_000;  // Plus ~5,000 per harvested token account
+#[constant]
+pub const CU_LIMIT_ADMIN: u32 = 20_000;
+
 /// Seeds for PDAs
 pub const CURVE_SEED: &[u8] = b"bonding_curve";
 pub const VAULT_SEED: &[u8] = b"sol_vault";
 pub const TOKEN_VAULT_SEED: &[u8] = b"token_vault";
+pub const POOL_SEED: &[u8] = b"pool";
+pub const POOL_VAULT_SEED: &[u8] = b"pool_sol_vault";
+pub const LP_MINT_SEED: &[u8] = b"lp_mint";
+pub const MIGRATION_ESCROW_SEED: &[u8] = b"migration_escrow";
+pub const TRANSFER_FEE_SEED: &[u8] = b"transfer_fee";
+pub const COLLECTION_SEED: &[u8] = b"collection";
+pub const TOKEN_GROUP_SEED: &[u8] = b"token_group";
+pub const MINT_SEED: &[u8] = b"mint";
+pub const SYMBOL_SEED: &[u8] = b"symbol";
+pub const PROFILE_SEED: &[u8] = b"profile";
+pub const LP_LOCK_SEED: &[u8] = b"lp_lock";
+
+/// Character classes for `Config::name_charset` / `symbol_charset` (0 = unrestricted)
+pub const CHARSET_UPPER: u8 = 1 << 0;  // A-Z
+pub const CHARSET_LOWER: u8 = 1 << 1;  // a-z
+pub const CHARSET_DIGIT: u8 = 1 << 2;  // 0-9
+pub const CHARSET_SPACE: u8 = 1 << 3;  // ASCII space
+pub const CHARSET_PUNCT: u8 = 1 << 4;  // ASCII punctuation
+pub const CHARSET_ALL: u8 = CHARSET_UPPER | CHARSET_LOWER | CHARSET_DIGIT | CHARSET_SPACE | CHARSET_PUNCT;
+
+/// `BondingCurve::flags` bits
+pub const FLAG_IMPERSONATION: u8 = 1 << 0;  // Symbol collides with a verified or reserved ticker
+pub const FLAG_DELISTED: u8 = 1 << 1;       // Violates protocol policy; frontends should hide it
+pub const FLAG_NSFW: u8 = 1 << 2;           // Not safe for work
+pub const FLAG_VERIFIED: u8 = 1 << 3;       // Vetted by a moderator
+pub const FLAG_BUYS_HALTED: u8 = 1 << 4;    // Buys rejected, sells still allowed so holders can exit
+pub const FLAG_ALL: u8 = FLAG_IMPERSONATION | FLAG_DELISTED | FLAG_NSFW | FLAG_VERIFIED | FLAG_BUYS_HALTED;
 
 // ============================================================================
 // PROGRAM
@@ -76,15 +253,39 @@ pub mod clawdvault {
         config.total_volume_sol = 0;
         config.bump = ctx.bumps.config;
         
-        msg!("ClawdVault initialized!");
-        msg!("Authority: {}", config.authority);
-        msg!("Fee recipient: {}", config.fee_recipient);
-        msg!("Migration operator: {}", config.migration_operator);
+        log!("ClawdVault initialized!");
+        log!("Authority: {}", config.authority);
+        log!("Fee recipient: {}", config.fee_recipient);
+        log!("Migration operator: {}", config.migration_operator);
+        config.upgrade_legacy();
+        
+        // Initial governance state, so the audit trail starts from genesis
+        let signer = ctx.accounts.authority.key();
+        let new_fee_recipient = config.fee_recipient;
+        let timestamp = Clock::get()?.unix_timestamp;
+        emit_cpi!(AuthorityChanged {
+            old_authority: Pubkey::default(),
+            new_authority: signer,
+            signer,
+            timestamp,
+        });
+        emit_cpi!(OperatorChanged {
+            old_operator: Pubkey::default(),
+            new_operator: signer,
+            signer,
+            timestamp,
+        });
+        emit_cpi!(FeeConfigChanged {
+            old_fee_recipient: Pubkey::default(),
+            new_fee_recipient,
+            signer,
+            timestamp,
+        });
         
         Ok(())
     }
 
-    /// Resize config account (one-time migration for adding migration_operator field)
+    /// Resize config account to the current layout (one-time migration per upgrade)
     /// Uses raw account to handle old format -> new format
     pub fn resize_config(ctx: Context<ResizeConfigRaw>) -> Result<()> {
         let config_info = &ctx.accounts.config;
@@ -92,56 +293,51 @@ pub mod clawdvault {
         
         // Read old config data manually
         let data = config_info.try_borrow_data()?;
-        require!(data.len() >= 89, ClawdVaultError::InvalidAccountData);
+        let old_size = data.len();
+        require!(
+            old_size == Config::V0_LEN || old_size == Config::V1_LEN,
+            ClawdVaultError::InvalidAccountData
+        );
+        require!(data[..8] == *Config::DISCRIMINATOR, ClawdVaultError::InvalidAccountData);
         
-        // Parse old format: discriminator(8) + authority(32) + fee_recipient(32) + totals(16) + bump(1)
+        // Both legacy formats start with authority(32) after the discriminator
         let authority = Pubkey::try_from(&data[8..40]).unwrap();
-        let fee_recipient = Pubkey::try_from(&data[40..72]).unwrap();
-        let total_tokens_created = u64::from_le_bytes(data[72..80].try_into().unwrap());
-        let total_volume_sol = u64::from_le_bytes(data[80..88].try_into().unwrap());
-        let bump = data[88];
         
         // Verify caller is authority
         require!(authority_info.key() == authority, ClawdVaultError::Unauthorized);
-        
         drop(data);
         
-        // Calculate rent needed for new size
-        let new_size = 8 + 32 + 32 + 32 + 8 + 8 + 1; // 121
-        let rent = Rent::get()?;
-        let new_min_balance = rent.minimum_balance(new_size);
-        let current_balance = config_info.lamports();
+        // Realloc to new size, topping up rent first
+        let new_size = Config::LEN;
+        fund_rent_exemption(
+            &authority_info.to_account_info(),
+            &config_info.to_account_info(),
+            &ctx.accounts.system_program,
+            new_size,
+        )?;
+        config_info.resize(new_size)?;
         
-        // Transfer rent via CPI if needed (before realloc)
-        if current_balance < new_min_balance {
-            let diff = new_min_balance - current_balance;
-            system_program::transfer(
-                CpiContext::new(
-                    ctx.accounts.system_program.to_account_info(),
-                    system_program::Transfer {
-                        from: authority_info.to_account_info(),
-                        to: config_info.to_account_info(),
-                    },
-                ),
-                diff,
-            )?;
+        if old_size == Config::V0_LEN {
+            // v0: discriminator(8) + authority(32) + fee_recipient(32) + totals(16) + bump(1)
+            // Shift totals/bump past the new migration_operator = authority
+            let mut data = config_info.try_borrow_mut_data()?;
+            data.copy_within(72..89, 104);
+            data[72..104].copy_from_slice(&authority.to_bytes());
+            log!("Migration operator set to authority: {}", authority);
         }
         
-        // Realloc to new size
-        config_info.realloc(new_size, false)?;
+        let mut config = Config::try_deserialize(&mut &config_info.try_borrow_data()?[..])?;
+        config.upgrade_legacy();
+        config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
         
-        // Write new format with migration_operator = authority
-        let mut data = config_info.try_borrow_mut_data()?;
-        // Keep discriminator (first 8 bytes)
-        data[8..40].copy_from_slice(&authority.to_bytes());
-        data[40..72].copy_from_slice(&fee_recipient.to_bytes());
-        data[72..104].copy_from_slice(&authority.to_bytes()); // migration_operator = authority
-        data[104..112].copy_from_slice(&total_tokens_created.to_le_bytes());
-        data[112..120].copy_from_slice(&total_volume_sol.to_le_bytes());
-        data[120] = bump;
+        log!("Config resized from {} to {} bytes!", old_size, new_size);
         
-        msg!("Config resized from 89 to 121 bytes!");
-        msg!("Migration operator set to authority: {}", authority);
+        emit_cpi!(ConfigMigrated {
+            old_size: old_size as u64,
+            new_size: new_size as u64,
+            signer: authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         
         Ok(())
     }
@@ -153,77 +349,391 @@ pub mod clawdvault {
         
         config.migration_operator = new_operator;
         
-        msg!("Migration operator updated!");
-        msg!("Old operator: {}", old_operator);
-        msg!("New operator: {}", new_operator);
+        log!("Migration operator updated!");
+        log!("Old operator: {}", old_operator);
+        log!("New operator: {}", new_operator);
+        
+        emit_cpi!(OperatorChanged {
+            old_operator,
+            new_operator,
+            signer: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         
         Ok(())
     }
 
-    /// Transfer protocol authority to a new wallet
-    pub fn transfer_authority(ctx: Context<TransferAuthority>, new_authority: Pubkey) -> Result<()> {
+    /// Queue or revoke a migration destination (authority only)
+    /// Wallets may claim migration escrows; AMM programs may own finalized pools. Approvals are
+    /// queued and only join the allow-list through `apply_migration_destination` once
+    /// `MIGRATION_DESTINATION_DELAY` has passed; revocations (including of queued entries) are immediate
+    pub fn set_migration_destination(
+        ctx: Context<SetMigrationDestination>,
+        kind: MigrationDestinationKind,
+        destination: Pubkey,
+        allowed: bool,
+    ) -> Result<()> {
         let config = &mut ctx.accounts.config;
-        let old_authority = config.authority;
+        let timestamp = Clock::get()?.unix_timestamp;
+        let pending = config
+            .pending_migration_destinations
+            .iter()
+            .position(|entry| entry.kind == kind && entry.destination == destination);
         
-        config.authority = new_authority;
+        if allowed {
+            // `finalize_migration` could never verify a pool owned by anything else
+            require!(
+                kind != MigrationDestinationKind::AmmProgram || RAYDIUM_CPMM_PROGRAMS.contains(&destination),
+                ClawdVaultError::UnsupportedMigrationDex
+            );
+            // Already approved or queued
+            if config.migration_destinations(kind).contains(&destination) || pending.is_some() {
+                return Ok(());
+            }
+            require!(
+                config.pending_migration_destinations.len() < MAX_MIGRATION_DESTINATIONS,
+                ClawdVaultError::TooManyMigrationDestinations
+            );
+            let activates_at = timestamp
+                .checked_add(MIGRATION_DESTINATION_DELAY)
+                .ok_or(ClawdVaultError::MathOverflow)?;
+            config.pending_migration_destinations.push(PendingMigrationDestination {
+                kind,
+                destination,
+                activates_at,
+            });
+            
+            log!("Migration destination {:?} {} queued until {}", kind, destination, activates_at);
+            
+            emit_cpi!(MigrationDestinationQueued {
+                kind,
+                destination,
+                activates_at,
+                signer: ctx.accounts.authority.key(),
+                timestamp,
+            });
+            return Ok(());
+        }
+        
+        let list = config.migration_destinations_mut(kind);
+        let existing = list.iter().position(|key| *key == destination);
+        if let Some(index) = existing {
+            list.remove(index);
+        }
+        if let Some(index) = pending {
+            config.pending_migration_destinations.remove(index);
+        }
+        // Neither approved nor queued
+        if existing.is_none() && pending.is_none() {
+            return Ok(());
+        }
+        
+        log!("Migration destination {:?} {}: revoked", kind, destination);
         
-        msg!("Authority transferred!");
-        msg!("Old authority: {}", old_authority);
-        msg!("New authority: {}", new_authority);
+        emit_cpi!(MigrationDestinationChanged {
+            kind,
+            destination,
+            allowed: false,
+            signer: ctx.accounts.authority.key(),
+            timestamp,
+        });
         
         Ok(())
     }
 
-    /// Create a new token with bonding curve, metadata, and optional initial buy
-    pub fn create_token(
-        ctx: Context<CreateToken>,
+    /// Move a queued migration destination onto its allow-list once its delay has passed (authority only)
+    pub fn apply_migration_destination(
+        ctx: Context<SetMigrationDestination>,
+        kind: MigrationDestinationKind,
+        destination: Pubkey,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let timestamp = Clock::get()?.unix_timestamp;
+        let index = config
+            .pending_migration_destinations
+            .iter()
+            .position(|entry| entry.kind == kind && entry.destination == destination)
+            .ok_or(ClawdVaultError::MigrationDestinationNotQueued)?;
+        require!(
+            timestamp >= config.pending_migration_destinations[index].activates_at,
+            ClawdVaultError::TimelockNotElapsed
+        );
+        
+        let list = config.migration_destinations_mut(kind);
+        require!(
+            list.len() < MAX_MIGRATION_DESTINATIONS,
+            ClawdVaultError::TooManyMigrationDestinations
+        );
+        list.push(destination);
+        config.pending_migration_destinations.remove(index);
+        
+        log!("Migration destination {:?} {}: approved", kind, destination);
+        
+        emit_cpi!(MigrationDestinationChanged {
+            kind,
+            destination,
+            allowed: true,
+            signer: ctx.accounts.authority.key(),
+            timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Set the protocol migration fee and creator graduation bonus (authority only)
+    /// Fee = `fee_lamports` + `fee_bps` of the raised SOL; both paid out of the curve at migration
+    pub fn set_migration_fees(
+        ctx: Context<SetMigrationFees>,
+        fee_lamports: u64,
+        fee_bps: u16,
+        graduation_bonus: u64,
+    ) -> Result<()> {
+        require!(fee_bps <= MAX_MIGRATION_FEE_BPS, ClawdVaultError::InvalidFeeConfig);
+        
+        let config = &mut ctx.accounts.config;
+        let old_fee_lamports = config.migration_fee_lamports;
+        let old_fee_bps = config.migration_fee_bps;
+        let old_graduation_bonus = config.graduation_bonus;
+        config.migration_fee_lamports = fee_lamports;
+        config.migration_fee_bps = fee_bps;
+        config.graduation_bonus = graduation_bonus;
+        
+        log!("Migration fee: {} lamports + {} bps, graduation bonus: {} lamports", fee_lamports, fee_bps, graduation_bonus);
+        
+        emit_cpi!(MigrationFeesChanged {
+            old_fee_lamports,
+            new_fee_lamports: fee_lamports,
+            old_fee_bps,
+            new_fee_bps: fee_bps,
+            old_graduation_bonus,
+            new_graduation_bonus: graduation_bonus,
+            signer: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Set where excess curve tokens go at migration (authority only)
+    /// `Pubkey::default()` burns them
+    pub fn set_excess_token_destination(
+        ctx: Context<SetExcessTokenDestination>,
+        new_destination: Pubkey,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_destination = config.excess_token_destination;
+        config.excess_token_destination = new_destination;
+        
+        log!("Excess token destination: {} -> {}", old_destination, new_destination);
+        
+        emit_cpi!(ExcessTokenDestinationChanged {
+            old_destination,
+            new_destination,
+            signer: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Set what happens to token metadata once a curve graduates (authority only)
+    pub fn set_metadata_policy(
+        ctx: Context<SetMetadataPolicy>,
+        new_policy: MetadataPolicy,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_policy = config.metadata_policy;
+        config.metadata_policy = new_policy;
+        
+        log!("Metadata policy: {:?} -> {:?}", old_policy, new_policy);
+        
+        emit_cpi!(MetadataPolicyChanged {
+            old_policy,
+            new_policy,
+            signer: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Set how launches colliding with a verified or reserved ticker are handled (authority only)
+    pub fn set_symbol_policy(ctx: Context<SetSymbolPolicy>, new_policy: SymbolPolicy) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_policy = config.symbol_policy;
+        config.symbol_policy = new_policy;
+        
+        log!("Symbol policy: {:?} -> {:?}", old_policy, new_policy);
+        
+        emit_cpi!(SymbolPolicyChanged {
+            old_policy,
+            new_policy,
+            signer: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Verify, reserve or release a ticker in the symbol registry (authority only)
+    /// `mint` is the token a verified ticker belongs to (ignored for an existing claim)
+    pub fn set_symbol_status(
+        ctx: Context<SetSymbolStatus>,
+        symbol: String,
+        status: SymbolStatus,
+        mint: Pubkey,
+    ) -> Result<()> {
+        let symbol = registry_symbol(&symbol)?;
+        
+        let record = &mut ctx.accounts.symbol_record;
+        let old_status = record.status;
+        if record.symbol.is_empty() {
+            record.symbol = symbol;
+            record.mint = mint;
+            record.payer = ctx.accounts.authority.key();
+            record.bump = ctx.bumps.symbol_record;
+        }
+        record.status = status;
+        
+        log!("Symbol {}: {:?} -> {:?}", record.symbol, old_status, status);
+        
+        let symbol = record.symbol.clone();
+        let mint = record.mint;
+        emit_cpi!(SymbolStatusChanged {
+            symbol,
+            mint,
+            old_status,
+            new_status: status,
+            signer: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Set the character classes allowed in token names and symbols (authority only)
+    /// Each is a mask of `CHARSET_*` bits; 0 allows any characters
+    pub fn set_metadata_charsets(
+        ctx: Context<SetMetadataCharsets>,
+        name_charset: u8,
+        symbol_charset: u8,
+    ) -> Result<()> {
+        require!(
+            name_charset & !CHARSET_ALL == 0 && symbol_charset & !CHARSET_ALL == 0,
+            ClawdVaultError::InvalidCharset
+        );
+        
+        let config = &mut ctx.accounts.config;
+        let old_name_charset = config.name_charset;
+        let old_symbol_charset = config.symbol_charset;
+        config.name_charset = name_charset;
+        config.symbol_charset = symbol_charset;
+        
+        log!("Metadata charsets: name {:#04x}, symbol {:#04x}", name_charset, symbol_charset);
+        
+        emit_cpi!(MetadataCharsetsChanged {
+            old_name_charset,
+            new_name_charset: name_charset,
+            old_symbol_charset,
+            new_symbol_charset: symbol_charset,
+            signer: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Allow or remove a metadata URI prefix (authority only)
+    /// With no prefixes configured, any URI is accepted
+    pub fn set_uri_prefix(ctx: Context<SetUriPrefix>, prefix: String, allowed: bool) -> Result<()> {
+        require!(!prefix.is_empty(), ClawdVaultError::UriPrefixEmpty);
+        require!(prefix.len() <= MAX_URI_PREFIX_LEN, ClawdVaultError::UriPrefixTooLong);
+        
+        let list = &mut ctx.accounts.config.uri_prefixes;
+        let existing = list.iter().position(|entry| *entry == prefix);
+        let old_allowed = existing.is_some();
+        match (allowed, existing) {
+            (true, None) => {
+                require!(list.len() < MAX_URI_PREFIXES, ClawdVaultError::TooManyUriPrefixes);
+                list.push(prefix.clone());
+            }
+            (false, Some(index)) => {
+                list.remove(index);
+            }
+            // Already in the requested state
+            _ => return Ok(()),
+        }
+        
+        log!("URI prefix {}: {}", prefix, if allowed { "allowed" } else { "removed" });
+        
+        emit_cpi!(UriPrefixChanged {
+            prefix,
+            old_allowed,
+            new_allowed: allowed,
+            signer: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Set the highest transfer fee a Token-2022 launch may choose (authority only)
+    /// 0 disables transfer-fee launches
+    pub fn set_max_transfer_fee(ctx: Context<SetMaxTransferFee>, max_bps: u16) -> Result<()> {
+        require!(max_bps <= MAX_TRANSFER_FEE_BPS, ClawdVaultError::InvalidFeeConfig);
+        
+        let config = &mut ctx.accounts.config;
+        let old_max_bps = config.max_transfer_fee_bps;
+        config.max_transfer_fee_bps = max_bps;
+        
+        log!("Max transfer fee: {} -> {} bps", old_max_bps, max_bps);
+        
+        emit_cpi!(MaxTransferFeeChanged {
+            old_max_bps,
+            new_max_bps: max_bps,
+            signer: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Create the protocol collection NFT every new token is verified into (authority only, once)
+    /// The config PDA holds the NFT and is its mint, update and collection authority
+    pub fn create_collection(
+        ctx: Context<CreateCollection>,
         name: String,
         symbol: String,
         uri: String,
-        initial_buy_lamports: u64,  // 0 for no initial buy
     ) -> Result<()> {
-        require!(name.len() <= 32, ClawdVaultError::NameTooLong);
-        require!(symbol.len() <= 10, ClawdVaultError::SymbolTooLong);
-        require!(uri.len() <= 200, ClawdVaultError::UriTooLong);
-
-        // Capture values before mutable borrow
-        let bump = ctx.bumps.bonding_curve;
-        let sol_vault_bump = ctx.bumps.sol_vault;
-        let mint_key = ctx.accounts.mint.key();
-        let creator_key = ctx.accounts.creator.key();
+        validate_token_metadata(&ctx.accounts.config, &name, &symbol, &uri)?;
         
-        // Build signer seeds for bonding curve PDA
-        let seeds = &[
-            CURVE_SEED,
-            mint_key.as_ref(),
-            &[bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        let config_seeds = &[b"config".as_ref(), &[ctx.accounts.config.bump]];
+        let signer_seeds = &[&config_seeds[..]];
+        let config_info = ctx.accounts.config.to_account_info();
         
-        // Mint total supply to token vault (needs bonding_curve as signer)
-        token::mint_to(
+        token_interface::mint_to(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 MintTo {
-                    mint: ctx.accounts.mint.to_account_info(),
-                    to: ctx.accounts.token_vault.to_account_info(),
-                    authority: ctx.accounts.bonding_curve.to_account_info(),
+                    mint: ctx.accounts.collection_mint.to_account_info(),
+                    to: ctx.accounts.collection_token_account.to_account_info(),
+                    authority: config_info.clone(),
                 },
                 signer_seeds,
             ),
-            TOTAL_SUPPLY,
+            1,
         )?;
         
-        // Create Metaplex metadata for the token
         create_metadata_accounts_v3(
             CpiContext::new_with_signer(
                 ctx.accounts.metadata_program.to_account_info(),
                 CreateMetadataAccountsV3 {
-                    metadata: ctx.accounts.metadata.to_account_info(),
-                    mint: ctx.accounts.mint.to_account_info(),
-                    mint_authority: ctx.accounts.bonding_curve.to_account_info(),
-                    payer: ctx.accounts.creator.to_account_info(),
-                    update_authority: ctx.accounts.bonding_curve.to_account_info(),
+                    metadata: ctx.accounts.collection_metadata.to_account_info(),
+                    mint: ctx.accounts.collection_mint.to_account_info(),
+                    mint_authority: config_info.clone(),
+                    payer: ctx.accounts.authority.to_account_info(),
+                    update_authority: config_info.clone(),
                     system_program: ctx.accounts.system_program.to_account_info(),
                     rent: ctx.accounts.rent.to_account_info(),
                 },
@@ -231,7 +741,7 @@ pub mod clawdvault {
             ),
             DataV2 {
                 name: name.clone(),
-                symbol: symbol.clone(),
+                symbol,
                 uri,
                 seller_fee_basis_points: 0,
                 creators: None,
@@ -240,169 +750,678 @@ pub mod clawdvault {
             },
             true,  // is_mutable
             true,  // update_authority_is_signer
-            None,  // collection_details
+            Some(CollectionDetails::V1 { size: 0 }),
         )?;
         
-        // Get account infos for transfers BEFORE mutable borrow of curve
-        let bonding_curve_info = ctx.accounts.bonding_curve.to_account_info();
-        let token_program_info = ctx.accounts.token_program.to_account_info();
-        let token_vault_info = ctx.accounts.token_vault.to_account_info();
-        let creator_token_info = ctx.accounts.creator_token_account.to_account_info();
-        let system_program_info = ctx.accounts.system_program.to_account_info();
-        let creator_info = ctx.accounts.creator.to_account_info();
-        let sol_vault_info = ctx.accounts.sol_vault.to_account_info();
-        
-        // Initialize bonding curve state
-        let curve = &mut ctx.accounts.bonding_curve;
-        curve.creator = creator_key;
-        curve.mint = mint_key;
-        curve.virtual_sol_reserves = INITIAL_VIRTUAL_SOL;
-        curve.virtual_token_reserves = INITIAL_VIRTUAL_TOKENS;
-        curve.real_sol_reserves = 0;
-        curve.real_token_reserves = TOTAL_SUPPLY;
-        curve.token_total_supply = TOTAL_SUPPLY;
-        curve.graduated = false;
-        curve.migrated_to_raydium = false;
-        curve.created_at = Clock::get()?.unix_timestamp;
-        curve.bump = bump;
-        curve.sol_vault_bump = sol_vault_bump;
-        
-        // Update protocol stats
-        let config = &mut ctx.accounts.config;
-        config.total_tokens_created = config.total_tokens_created.checked_add(1)
-            .ok_or(ClawdVaultError::MathOverflow)?;
+        // Master edition with zero max supply takes over the mint authority: exactly one NFT
+        create_master_edition_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.metadata_program.to_account_info(),
+                CreateMasterEditionV3 {
+                    edition: ctx.accounts.collection_master_edition.to_account_info(),
+                    mint: ctx.accounts.collection_mint.to_account_info(),
+                    update_authority: config_info.clone(),
+                    mint_authority: config_info.clone(),
+                    payer: ctx.accounts.authority.to_account_info(),
+                    metadata: ctx.accounts.collection_metadata.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            Some(0),
+        )?;
         
-        msg!("🐺 Token created: {} ({})", name, symbol);
-        msg!("Mint: {}", mint_key);
-        msg!("Creator: {}", creator_key);
+        let collection_mint = ctx.accounts.collection_mint.key();
+        ctx.accounts.config.collection_mint = collection_mint;
         
-        // Handle initial buy if specified (do transfers before curve borrow ends)
-        if initial_buy_lamports > 0 {
-            // Calculate tokens out using bonding curve math
-            let sol_after_fee = initial_buy_lamports
-                .checked_mul(BPS_DENOMINATOR - TOTAL_FEE_BPS)
-                .ok_or(ClawdVaultError::MathOverflow)?
-                .checked_div(BPS_DENOMINATOR)
-                .ok_or(ClawdVaultError::MathOverflow)?;
-            
-            let new_virtual_sol = INITIAL_VIRTUAL_SOL
-                .checked_add(sol_after_fee)
-                .ok_or(ClawdVaultError::MathOverflow)?;
-            
-            let invariant = (INITIAL_VIRTUAL_SOL as u128)
-                .checked_mul(INITIAL_VIRTUAL_TOKENS as u128)
-                .ok_or(ClawdVaultError::MathOverflow)?;
-            
-            let new_virtual_tokens = invariant
-                .checked_div(new_virtual_sol as u128)
-                .ok_or(ClawdVaultError::MathOverflow)? as u64;
-            
-            let tokens_out = INITIAL_VIRTUAL_TOKENS
-                .checked_sub(new_virtual_tokens)
-                .ok_or(ClawdVaultError::MathOverflow)?;
-            
-            // Transfer SOL from creator to sol_vault
-            system_program::transfer(
-                CpiContext::new(
-                    system_program_info.clone(),
-                    system_program::Transfer {
-                        from: creator_info.clone(),
-                        to: sol_vault_info.clone(),
-                    },
-                ),
-                initial_buy_lamports,
-            )?;
-            
-            // Transfer tokens from vault to creator's token account
-            token::transfer(
-                CpiContext::new_with_signer(
-                    token_program_info.clone(),
-                    Transfer {
-                        from: token_vault_info.clone(),
-                        to: creator_token_info.clone(),
-                        authority: bonding_curve_info.clone(),
-                    },
-                    signer_seeds,
-                ),
-                tokens_out,
-            )?;
-            
-            // Update curve state
-            curve.virtual_sol_reserves = new_virtual_sol;
-            curve.virtual_token_reserves = new_virtual_tokens;
-            curve.real_sol_reserves = initial_buy_lamports;
-            curve.real_token_reserves = TOTAL_SUPPLY
-                .checked_sub(tokens_out)
-                .ok_or(ClawdVaultError::MathOverflow)?;
-            
-            // Calculate fees (for logging)
-            let total_fee = initial_buy_lamports
-                .checked_mul(TOTAL_FEE_BPS)
-                .ok_or(ClawdVaultError::MathOverflow)?
-                .checked_div(BPS_DENOMINATOR)
-                .ok_or(ClawdVaultError::MathOverflow)?;
-            
-            msg!("🎯 Initial buy: {} lamports -> {} tokens (fee: {} lamports)", 
-                initial_buy_lamports, tokens_out, total_fee);
-            
-            // Emit TradeEvent for initial buy (so sync-trades catches it!)
-            let protocol_fee = total_fee / 2;
-            let creator_fee = total_fee - protocol_fee; // Remainder to avoid rounding loss
-            
-            emit!(TradeEvent {
-                mint: mint_key,
-                trader: creator_key,
-                is_buy: true,
-                sol_amount: initial_buy_lamports,
-                token_amount: tokens_out,
-                protocol_fee,
-                creator_fee,
-                virtual_sol_reserves: new_virtual_sol,
-                virtual_token_reserves: new_virtual_tokens,
-                timestamp: Clock::get()?.unix_timestamp,
-            });
-        }
+        log!("🖼️ Collection created: {} ({})", name, collection_mint);
         
-        msg!("Initial price: {} lamports/token", 
-            curve.virtual_sol_reserves / (curve.virtual_token_reserves / 1_000_000));
+        emit_cpi!(CollectionCreated {
+            collection_mint,
+            signer: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         
         Ok(())
     }
 
-    /// Buy tokens from bonding curve
-    pub fn buy(ctx: Context<Buy>, sol_amount: u64, min_tokens_out: u64) -> Result<()> {
-        require!(sol_amount > 0, ClawdVaultError::ZeroAmount);
+    /// Create the Token-2022 group every new `create_token_2022` launch joins (authority only, once)
+    /// The config PDA is the group's mint and update authority, so only the program can add members
+    pub fn create_token_group(ctx: Context<CreateTokenGroup>) -> Result<()> {
+        let config_seeds = &[b"config".as_ref(), &[ctx.accounts.config.bump]];
+        let signer_seeds = &[&config_seeds[..]];
+        let group_info = ctx.accounts.token_group.to_account_info();
+        let token_program_info = ctx.accounts.token_program.to_account_info();
+        let config_key = ctx.accounts.config.key();
         
-        // Read curve state (immutable first)
-        let curve = &ctx.accounts.bonding_curve;
-        require!(!curve.graduated, ClawdVaultError::AlreadyGraduated);
+        // The token program appends the group on initialize, so the mint is funded for it up front
+        fund_rent_exemption(
+            &ctx.accounts.authority.to_account_info(),
+            &group_info,
+            &ctx.accounts.system_program,
+            ExtensionType::try_calculate_account_len::<MintState>(&[
+                ExtensionType::GroupPointer,
+                ExtensionType::TokenGroup,
+            ])?,
+        )?;
         
-        // Capture values we need before any borrows
-        let mint_key = curve.mint;
-        let curve_bump = curve.bump;
-        let old_virtual_sol = curve.virtual_sol_reserves;
-        let old_virtual_tokens = curve.virtual_token_reserves;
-        let old_real_tokens = curve.real_token_reserves;
+        token_interface::token_group_initialize(
+            CpiContext::new_with_signer(
+                token_program_info.clone(),
+                TokenGroupInitialize {
+                    program_id: token_program_info.clone(),
+                    group: group_info.clone(),
+                    mint: group_info.clone(),
+                    mint_authority: ctx.accounts.config.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            Some(config_key),
+            u64::MAX,
+        )?;
         
-        // Calculate tokens out using constant product formula
-        let new_virtual_sol = old_virtual_sol
-            .checked_add(sol_amount)
-            .ok_or(ClawdVaultError::MathOverflow)?;
+        let group_mint = ctx.accounts.token_group.key();
         
-        let invariant = (old_virtual_sol as u128)
-            .checked_mul(old_virtual_tokens as u128)
-            .ok_or(ClawdVaultError::MathOverflow)?;
+        log!("🖼️ Token group created: {}", group_mint);
         
-        let new_virtual_tokens = invariant
-            .checked_div(new_virtual_sol as u128)
-            .ok_or(ClawdVaultError::MathOverflow)? as u64;
+        emit_cpi!(TokenGroupCreated {
+            group_mint,
+            signer: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         
-        let tokens_out = old_virtual_tokens
-            .checked_sub(new_virtual_tokens)
-            .ok_or(ClawdVaultError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Resize a bonding curve created before the `status` field (one-time migration per curve)
+    /// Permissionless - the payer only funds the extra rent
+    pub fn resize_curve(ctx: Context<ResizeCurveRaw>) -> Result<()> {
+        let curve_info = ctx.accounts.bonding_curve.to_account_info();
         
-        require!(tokens_out >= min_tokens_out, ClawdVaultError::SlippageExceeded);
-        require!(tokens_out <= old_real_tokens, ClawdVaultError::InsufficientLiquidity);
+        require!(
+            curve_info.data_len() == BondingCurve::LEGACY_LEN,
+            ClawdVaultError::InvalidAccountData
+        );
+        require!(
+            curve_info.try_borrow_data()?[..8] == *BondingCurve::DISCRIMINATOR,
+            ClawdVaultError::InvalidAccountData
+        );
+        
+        fund_rent_exemption(
+            &ctx.accounts.payer.to_account_info(),
+            &curve_info,
+            &ctx.accounts.system_program,
+            BondingCurve::LEN,
+        )?;
+        curve_info.resize(BondingCurve::LEN)?;
+        
+        // New fields are zeroed - derive them from the legacy layout
+        let mut curve = BondingCurve::try_deserialize(&mut &curve_info.try_borrow_data()?[..])?;
+        curve.upgrade_legacy();
+        curve.try_serialize(&mut &mut curve_info.try_borrow_mut_data()?[..])?;
+        
+        log!("Curve resized from {} to {} bytes", BondingCurve::LEGACY_LEN, BondingCurve::LEN);
+        log!("Status: {:?}", curve.status);
+        
+        Ok(())
+    }
+
+    /// Transfer protocol authority to a new wallet
+    pub fn transfer_authority(ctx: Context<TransferAuthority>, new_authority: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_authority = config.authority;
+        
+        config.authority = new_authority;
+        
+        log!("Authority transferred!");
+        log!("Old authority: {}", old_authority);
+        log!("New authority: {}", new_authority);
+        
+        emit_cpi!(AuthorityChanged {
+            old_authority,
+            new_authority,
+            signer: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Set the moderator wallet that can flag curves (authority only)
+    /// Pubkey::default() leaves moderation to the authority
+    pub fn set_moderator(ctx: Context<SetModerator>, new_moderator: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_moderator = config.moderator;
+        config.moderator = new_moderator;
+        
+        log!("Moderator: {} -> {}", old_moderator, new_moderator);
+        
+        emit_cpi!(ModeratorChanged {
+            old_moderator,
+            new_moderator,
+            signer: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Replace a curve's moderation flags (moderator or authority)
+    /// `FLAG_BUYS_HALTED` stops buys while still letting holders sell
+    pub fn set_curve_flags(ctx: Context<SetCurveFlags>, flags: u8) -> Result<()> {
+        require!(flags & !FLAG_ALL == 0, ClawdVaultError::InvalidCurveFlags);
+        
+        let curve = &mut ctx.accounts.bonding_curve;
+        let old_flags = curve.flags;
+        curve.flags = flags;
+        
+        log!("Curve {} flags: {:#04x} -> {:#04x}", curve.mint, old_flags, flags);
+        
+        let mint = curve.mint;
+        emit_cpi!(CurveFlagsChanged {
+            mint,
+            old_flags,
+            new_flags: flags,
+            signer: ctx.accounts.moderator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Pause or resume trading on a single curve (authority only)
+    pub fn set_curve_paused(ctx: Context<SetCurvePaused>, paused: bool) -> Result<()> {
+        let curve = &mut ctx.accounts.bonding_curve;
+        let old_paused = curve.status == CurveStatus::Paused;
+        
+        curve.transition(if paused { CurveStatus::Paused } else { CurveStatus::Trading })?;
+        
+        log!("Curve {} {}", curve.mint, if paused { "paused" } else { "resumed" });
+        
+        let mint = curve.mint;
+        emit_cpi!(PauseChanged {
+            mint,
+            old_paused,
+            new_paused: paused,
+            signer: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Create a new token with bonding curve, metadata, and optional initial buy
+    /// The mint is a fresh keypair that signs the transaction
+    pub fn create_token(
+        ctx: Context<CreateToken>,
+        name: String,
+        symbol: String,
+        uri: String,
+        initial_buy_lamports: u64,  // 0 for no initial buy
+        graduation_deadline: i64,   // Unix timestamp, 0 for no deadline
+        profile: Option<TokenProfileArgs>,  // Requires the token_profile account
+    ) -> Result<()> {
+        create_legacy_token(ctx, None, name, symbol, uri, initial_buy_lamports, graduation_deadline, profile)
+    }
+
+    /// Same as `create_token`, but the mint is a PDA of the creator and a creator-chosen nonce,
+    /// so the address is known before creation and no mint keypair has to sign
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_token_pda(
+        ctx: Context<CreateToken>,
+        name: String,
+        symbol: String,
+        uri: String,
+        initial_buy_lamports: u64,  // 0 for no initial buy
+        graduation_deadline: i64,   // Unix timestamp, 0 for no deadline
+        nonce: u64,
+        profile: Option<TokenProfileArgs>,  // Requires the token_profile account
+    ) -> Result<()> {
+        let creator_key = ctx.accounts.creator.key();
+        let nonce_bytes = nonce.to_le_bytes();
+        let (mint_key, mint_bump) = creator_mint_pda(&creator_key, nonce);
+        require_keys_eq!(ctx.accounts.mint.key(), mint_key, ClawdVaultError::InvalidAccountData);
+        
+        log!("Mint PDA nonce: {}", nonce);
+        
+        let mint_seeds: &[&[u8]] = &[MINT_SEED, creator_key.as_ref(), &nonce_bytes, &[mint_bump]];
+        create_legacy_token(ctx, Some(mint_seeds), name, symbol, uri, initial_buy_lamports, graduation_deadline, profile)
+    }
+
+    /// Create a new Token-2022 token whose metadata lives in the mint (metadata-pointer + token-metadata extensions)
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_token_2022(
+        ctx: Context<CreateToken2022>,
+        name: String,
+        symbol: String,
+        uri: String,
+        initial_buy_lamports: u64,  // 0 for no initial buy
+        graduation_deadline: i64,   // Unix timestamp, 0 for no deadline
+        transfer_fee_bps: u16,      // Creator royalty on every transfer, 0 for none
+        profile: Option<TokenProfileArgs>,  // Requires the token_profile account
+    ) -> Result<()> {
+        create_2022_token(ctx, None, name, symbol, uri, initial_buy_lamports, graduation_deadline, transfer_fee_bps, profile)
+    }
+
+    /// Same as `create_token_2022`, but the mint is a PDA of the creator and a creator-chosen
+    /// nonce, like `create_token_pda`
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_token_2022_pda(
+        ctx: Context<CreateToken2022>,
+        name: String,
+        symbol: String,
+        uri: String,
+        initial_buy_lamports: u64,  // 0 for no initial buy
+        graduation_deadline: i64,   // Unix timestamp, 0 for no deadline
+        transfer_fee_bps: u16,      // Creator royalty on every transfer, 0 for none
+        nonce: u64,
+        profile: Option<TokenProfileArgs>,  // Requires the token_profile account
+    ) -> Result<()> {
+        let creator_key = ctx.accounts.creator.key();
+        let nonce_bytes = nonce.to_le_bytes();
+        let (mint_key, mint_bump) = creator_mint_pda(&creator_key, nonce);
+        require_keys_eq!(ctx.accounts.mint.key(), mint_key, ClawdVaultError::InvalidAccountData);
+        
+        log!("Mint PDA nonce: {}", nonce);
+        
+        let mint_seeds: &[&[u8]] = &[MINT_SEED, creator_key.as_ref(), &nonce_bytes, &[mint_bump]];
+        create_2022_token(
+            ctx,
+            Some(mint_seeds),
+            name,
+            symbol,
+            uri,
+            initial_buy_lamports,
+            graduation_deadline,
+            transfer_fee_bps,
+            profile,
+        )
+    }
+
+    /// Fix a token's name, symbol or URI before it graduates (creator only)
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        validate_token_metadata(&ctx.accounts.config, &name, &symbol, &uri)?;
+        ctx.accounts.bonding_curve.require_status(CurveStatus::Trading)?;
+        
+        // A new ticker goes through the registry like a launch, and frees the old claim
+        let current_symbol = token_symbol(
+            &ctx.accounts.bonding_curve,
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.metadata,
+        )?;
+        let symbol_changed = normalize_symbol(&current_symbol) != normalize_symbol(&symbol);
+        let symbol_collision = if symbol_changed || ctx.accounts.symbol_record.is_some() {
+            claim_symbol(
+                &ctx.accounts.config,
+                ctx.accounts.symbol_record.as_deref_mut(),
+                &mut ctx.accounts.bonding_curve,
+                &symbol,
+                ctx.bumps.symbol_record,
+                ctx.accounts.creator.key(),
+            )?
+        } else {
+            None
+        };
+        if symbol_changed {
+            release_symbol(
+                ctx.accounts.old_symbol_record.as_ref(),
+                &current_symbol,
+                ctx.accounts.mint.key(),
+                ctx.accounts.old_symbol_payer.as_ref(),
+            )?;
+        }
+        
+        let curve = &ctx.accounts.bonding_curve;
+        let mint_key = ctx.accounts.mint.key();
+        let seeds = &[CURVE_SEED, mint_key.as_ref(), &[curve.bump]];
+        let signer_seeds = &[&seeds[..]];
+        
+        if curve.token_program == Token2022::id() {
+            // Metadata lives in the mint: fund it for the new size before rewriting
+            let mint_info = ctx.accounts.mint.to_account_info();
+            let new_size = {
+                let data = mint_info.try_borrow_data()?;
+                let current = StateWithExtensions::<MintState>::unpack(&data)?
+                    .get_variable_len_extension::<TokenMetadata>()?;
+                let current_size = current.tlv_size_of()?;
+                let updated = TokenMetadata {
+                    name: name.clone(),
+                    symbol: symbol.clone(),
+                    uri: uri.clone(),
+                    ..current
+                };
+                (data.len() + updated.tlv_size_of()?).saturating_sub(current_size)
+            };
+            fund_rent_exemption(
+                &ctx.accounts.creator.to_account_info(),
+                &mint_info,
+                &ctx.accounts.system_program,
+                new_size,
+            )?;
+            
+            for (field, value) in [(Field::Name, &name), (Field::Symbol, &symbol), (Field::Uri, &uri)] {
+                token_interface::token_metadata_update_field(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        TokenMetadataUpdateField {
+                            program_id: ctx.accounts.token_program.to_account_info(),
+                            metadata: mint_info.clone(),
+                            update_authority: ctx.accounts.bonding_curve.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    field,
+                    value.clone(),
+                )?;
+            }
+        } else {
+            // Keep the verified creator and collection; only the name, symbol and URI change
+            let current = MetadataAccountData::safe_deserialize(&ctx.accounts.metadata.try_borrow_data()?)
+                .map_err(|_| ClawdVaultError::InvalidAccountData)?;
+            update_metadata_accounts_v2(
+                CpiContext::new_with_signer(
+                    ctx.accounts.metadata_program.to_account_info(),
+                    UpdateMetadataAccountsV2 {
+                        metadata: ctx.accounts.metadata.to_account_info(),
+                        update_authority: ctx.accounts.bonding_curve.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                None,
+                Some(DataV2 {
+                    name: name.clone(),
+                    symbol: symbol.clone(),
+                    uri: uri.clone(),
+                    seller_fee_basis_points: current.seller_fee_basis_points,
+                    creators: current.creators,
+                    collection: current.collection,
+                    uses: current.uses,
+                }),
+                None,
+                None,
+            )?;
+        }
+        
+        log!("📝 Metadata updated: {} ({})", name, symbol);
+        
+        if let Some(collision) = symbol_collision {
+            emit_cpi!(collision);
+        }
+        emit_cpi!(TokenMetadataUpdatedEvent {
+            mint: mint_key,
+            creator: ctx.accounts.creator.key(),
+            name,
+            symbol,
+            uri,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Write the token's on-chain profile (creator only)
+    /// Created on first call, rent paid by the creator; each call replaces every field
+    pub fn set_token_profile(
+        ctx: Context<SetTokenProfile>,
+        website: String,
+        twitter: String,
+        telegram: String,
+        agent_id: String,
+        description: String,
+    ) -> Result<()> {
+        let mint = ctx.accounts.bonding_curve.mint;
+        let event = write_token_profile(
+            &mut ctx.accounts.token_profile,
+            mint,
+            ctx.accounts.creator.key(),
+            TokenProfileArgs { website, twitter, telegram, agent_id, description },
+            ctx.bumps.token_profile,
+        )?;
+        
+        log!("Profile updated for {}", mint);
+        
+        emit_cpi!(event);
+        
+        Ok(())
+    }
+
+    /// Propose a new creator for a token (creator only)
+    /// Takes effect once the proposed wallet calls `accept_creator`; Pubkey::default() cancels
+    /// Metaplex won't drop a verified creator, so a legacy token's creator is unverified
+    /// while a handoff is pending (if the metadata can still follow it) and verified again
+    /// if it's cancelled
+    pub fn propose_creator(ctx: Context<ProposeCreator>, new_creator: Pubkey) -> Result<()> {
+        let cancel = new_creator == Pubkey::default();
+        let curve = &ctx.accounts.bonding_curve;
+        if curve.token_program != Token2022::id() && (cancel || metadata_follows_creator(curve)) {
+            set_creator_verified(
+                &ctx.accounts.metadata,
+                &ctx.accounts.metadata_program,
+                &ctx.accounts.creator,
+                cancel,
+            )?;
+        }
+        
+        // A creator-held update authority sits with the curve while the handoff is pending
+        let pending = curve.pending_creator != Pubkey::default();
+        if creator_holds_update_authority(curve) && cancel == pending {
+            let mint_key = curve.mint;
+            let seeds = &[CURVE_SEED, mint_key.as_ref(), &[curve.bump]];
+            let (current_authority, signer_seeds, new_authority): (_, &[&[&[u8]]], _) = if cancel {
+                (curve.to_account_info(), &[&seeds[..]], ctx.accounts.creator.key())
+            } else {
+                (ctx.accounts.creator.to_account_info(), &[], curve.key())
+            };
+            set_update_authority(
+                curve,
+                &ctx.accounts.mint,
+                &ctx.accounts.metadata,
+                &ctx.accounts.metadata_program,
+                &ctx.accounts.token_program,
+                current_authority,
+                signer_seeds,
+                new_authority,
+            )?;
+        }
+        
+        let curve = &mut ctx.accounts.bonding_curve;
+        curve.pending_creator = new_creator;
+        
+        log!("Creator of {} proposed: {}", curve.mint, new_creator);
+        
+        let mint = curve.mint;
+        emit_cpi!(CreatorProposed {
+            mint,
+            creator: ctx.accounts.creator.key(),
+            pending_creator: new_creator,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Take over creator rights proposed by the current creator
+    /// Creator fees, transfer-fee claims, profile and metadata edits follow the new creator.
+    /// A legacy token's Metaplex creator is replaced by the new creator, verified, unless its
+    /// metadata was locked immutable; a creator-held update authority moves to the new creator
+    pub fn accept_creator(ctx: Context<AcceptCreator>) -> Result<()> {
+        let curve = &ctx.accounts.bonding_curve;
+        let mint_key = curve.mint;
+        let seeds = &[CURVE_SEED, mint_key.as_ref(), &[curve.bump]];
+        let handoff_authority = creator_holds_update_authority(curve).then(|| ctx.accounts.new_creator.key());
+        if metadata_follows_creator(curve) {
+            let current = MetadataAccountData::safe_deserialize(&ctx.accounts.metadata.try_borrow_data()?)
+                .map_err(|_| ClawdVaultError::InvalidAccountData)?;
+            update_metadata_accounts_v2(
+                CpiContext::new_with_signer(
+                    ctx.accounts.metadata_program.to_account_info(),
+                    UpdateMetadataAccountsV2 {
+                        metadata: ctx.accounts.metadata.to_account_info(),
+                        update_authority: ctx.accounts.bonding_curve.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ),
+                handoff_authority,
+                Some(DataV2 {
+                    name: current.name.trim_end_matches('\0').to_string(),
+                    symbol: current.symbol.trim_end_matches('\0').to_string(),
+                    uri: current.uri.trim_end_matches('\0').to_string(),
+                    seller_fee_basis_points: current.seller_fee_basis_points,
+                    creators: Some(vec![Creator {
+                        address: ctx.accounts.new_creator.key(),
+                        verified: false,  // Verified by the new creator's signature below
+                        share: 100,
+                    }]),
+                    collection: current.collection,
+                    uses: current.uses,
+                }),
+                None,
+                None,
+            )?;
+            sign_metadata(CpiContext::new(
+                ctx.accounts.metadata_program.to_account_info(),
+                SignMetadata {
+                    creator: ctx.accounts.new_creator.to_account_info(),
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                },
+            ))?;
+        } else if let Some(new_authority) = handoff_authority {
+            set_update_authority(
+                curve,
+                &ctx.accounts.mint,
+                &ctx.accounts.metadata,
+                &ctx.accounts.metadata_program,
+                &ctx.accounts.token_program,
+                curve.to_account_info(),
+                &[&seeds[..]],
+                new_authority,
+            )?;
+        }
+        
+        let curve = &mut ctx.accounts.bonding_curve;
+        let old_creator = curve.creator;
+        curve.creator = curve.pending_creator;
+        curve.pending_creator = Pubkey::default();
+        
+        log!("Creator of {}: {} -> {}", curve.mint, old_creator, curve.creator);
+        
+        let mint = curve.mint;
+        let new_creator = curve.creator;
+        emit_cpi!(CreatorChanged {
+            mint,
+            old_creator,
+            new_creator,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Withdraw the Token-2022 transfer fees withheld on a token to the creator (creator only)
+    /// Token accounts passed as remaining accounts are harvested into the mint first
+    pub fn claim_transfer_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimTransferFees<'info>>,
+    ) -> Result<()> {
+        let mint_key = ctx.accounts.mint.key();
+        let token_program_info = ctx.accounts.token_program.to_account_info();
+        let mint_info = ctx.accounts.mint.to_account_info();
+        
+        if !ctx.remaining_accounts.is_empty() {
+            token_interface::harvest_withheld_tokens_to_mint(
+                CpiContext::new(
+                    token_program_info.clone(),
+                    HarvestWithheldTokensToMint {
+                        token_program_id: token_program_info.clone(),
+                        mint: mint_info.clone(),
+                    },
+                ),
+                ctx.remaining_accounts.to_vec(),
+            )?;
+        }
+        
+        let amount = transfer_fee_config(&mint_info)?
+            .ok_or(ClawdVaultError::NoTransferFee)?
+            .withheld_amount
+            .into();
+        require!(amount > 0, ClawdVaultError::ZeroAmount);
+        
+        let fee_authority_seeds = &[
+            TRANSFER_FEE_SEED,
+            mint_key.as_ref(),
+            &[ctx.bumps.fee_authority],
+        ];
+        token_interface::withdraw_withheld_tokens_from_mint(
+            CpiContext::new_with_signer(
+                token_program_info.clone(),
+                WithdrawWithheldTokensFromMint {
+                    token_program_id: token_program_info.clone(),
+                    mint: mint_info.clone(),
+                    destination: ctx.accounts.creator_token_account.to_account_info(),
+                    authority: ctx.accounts.fee_authority.to_account_info(),
+                },
+                &[&fee_authority_seeds[..]],
+            ),
+        )?;
+        
+        log!("💰 Transfer fees claimed: {} tokens", amount);
+        
+        emit_cpi!(TransferFeesClaimedEvent {
+            mint: mint_key,
+            creator: ctx.accounts.creator.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Buy tokens from bonding curve
+    pub fn buy(ctx: Context<Buy>, sol_amount: u64, min_tokens_out: u64) -> Result<()> {
+        require!(sol_amount > 0, ClawdVaultError::ZeroAmount);
+        
+        // Read curve state (immutable first)
+        let curve = &ctx.accounts.bonding_curve;
+        curve.require_status(CurveStatus::Trading)?;
+        curve.require_before_deadline()?;
+        require!(curve.flags & FLAG_BUYS_HALTED == 0, ClawdVaultError::BuysHalted);
+        
+        // Capture values we need before any borrows
+        let mint_key = curve.mint;
+        let curve_bump = curve.bump;
+        let old_virtual_sol = curve.virtual_sol_reserves;
+        let old_virtual_tokens = curve.virtual_token_reserves;
+        let old_real_tokens = curve.real_token_reserves;
+        
+        // Calculate tokens out using constant product formula
+        let new_virtual_sol = old_virtual_sol
+            .checked_add(sol_amount)
+            .ok_or(ClawdVaultError::MathOverflow)?;
+        
+        let invariant = (old_virtual_sol as u128)
+            .checked_mul(old_virtual_tokens as u128)
+            .ok_or(ClawdVaultError::MathOverflow)?;
+        
+        let new_virtual_tokens = invariant
+            .checked_div(new_virtual_sol as u128)
+            .ok_or(ClawdVaultError::MathOverflow)? as u64;
+        
+        let tokens_out = old_virtual_tokens
+            .checked_sub(new_virtual_tokens)
+            .ok_or(ClawdVaultError::MathOverflow)?;
+        
+        // Slippage applies to what the buyer receives, after any Token-2022 transfer fee
+        let tokens_received = tokens_out
+            .checked_sub(transfer_fee(&ctx.accounts.mint.to_account_info(), tokens_out)?)
+            .ok_or(ClawdVaultError::MathOverflow)?;
+        require!(tokens_received >= min_tokens_out, ClawdVaultError::SlippageExceeded);
+        require!(tokens_out <= old_real_tokens, ClawdVaultError::InsufficientLiquidity);
         
         // Calculate fees
         let total_fee = sol_amount
@@ -467,17 +1486,19 @@ pub mod clawdvault {
         ];
         let signer_seeds = &[&seeds[..]];
         
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.token_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.buyer_token_account.to_account_info(),
                     authority: ctx.accounts.bonding_curve.to_account_info(),
                 },
                 signer_seeds,
             ),
             tokens_out,
+            ctx.accounts.mint.decimals,
         )?;
         
         // Now update curve state (mutable borrow after CPIs)
@@ -492,30 +1513,44 @@ pub mod clawdvault {
             .ok_or(ClawdVaultError::MathOverflow)?;
         
         // Check for graduation
-        if curve.real_sol_reserves >= GRADUATION_THRESHOLD {
-            curve.graduated = true;
-            msg!("🎓 TOKEN GRADUATED! Ready for Raydium migration");
+        let just_graduated = curve.real_sol_reserves >= GRADUATION_THRESHOLD;
+        if just_graduated {
+            curve.transition(CurveStatus::Graduated)?;
+            log!("🎓 TOKEN GRADUATED! Ready for migration");
         }
         
-        msg!("🟢 BUY: {} lamports -> {} tokens", sol_amount, tokens_out);
-        msg!("Fees: {} protocol, {} creator", protocol_fee, creator_fee);
-        msg!("New price: {} lamports/token", 
+        log!("🟢 BUY: {} lamports -> {} tokens", sol_amount, tokens_out);
+        log!("Fees: {} protocol, {} creator", protocol_fee, creator_fee);
+        log!("New price: {} lamports/token", 
             (curve.virtual_sol_reserves as u128 * 1_000_000 / curve.virtual_token_reserves as u128));
         
+        let virtual_sol_reserves = curve.virtual_sol_reserves;
+        let virtual_token_reserves = curve.virtual_token_reserves;
+        let sol_raised = curve.real_sol_reserves;
+        let timestamp = Clock::get()?.unix_timestamp;
+        
         // Emit event
-        emit!(TradeEvent {
-            mint: curve.mint,
+        emit_cpi!(TradeEvent {
+            mint: mint_key,
             trader: ctx.accounts.buyer.key(),
             is_buy: true,
             sol_amount,
             token_amount: tokens_out,
             protocol_fee,
             creator_fee,
-            virtual_sol_reserves: curve.virtual_sol_reserves,
-            virtual_token_reserves: curve.virtual_token_reserves,
-            timestamp: Clock::get()?.unix_timestamp,
+            virtual_sol_reserves,
+            virtual_token_reserves,
+            timestamp,
         });
         
+        if just_graduated {
+            emit_cpi!(GraduationEvent {
+                mint: mint_key,
+                sol_raised,
+                timestamp,
+            });
+        }
+        
         Ok(())
     }
 
@@ -525,7 +1560,14 @@ pub mod clawdvault {
         
         let curve = &mut ctx.accounts.bonding_curve;
         
-        require!(!curve.graduated, ClawdVaultError::AlreadyGraduated);
+        curve.require_status(CurveStatus::Trading)?;
+        curve.require_before_deadline()?;
+        
+        // The curve is priced on the tokens the vault receives, after any Token-2022 transfer fee
+        let mint_info = ctx.accounts.mint.to_account_info();
+        let tokens_in = token_amount
+            .checked_sub(transfer_fee(&mint_info, token_amount)?)
+            .ok_or(ClawdVaultError::MathOverflow)?;
         
         // Calculate SOL out using constant product formula
         let invariant = (curve.virtual_sol_reserves as u128)
@@ -533,7 +1575,7 @@ pub mod clawdvault {
             .ok_or(ClawdVaultError::MathOverflow)?;
         
         let new_virtual_tokens = curve.virtual_token_reserves
-            .checked_add(token_amount)
+            .checked_add(tokens_in)
             .ok_or(ClawdVaultError::MathOverflow)?;
         
         let new_virtual_sol = invariant
@@ -545,7 +1587,7 @@ pub mod clawdvault {
             .ok_or(ClawdVaultError::MathOverflow)?;
         
         // Cap at available liquidity and recalculate tokens if needed
-        let (sol_out_gross, actual_token_amount) = if sol_out_requested > curve.real_sol_reserves {
+        let (sol_out_gross, actual_token_amount, tokens_received) = if sol_out_requested > curve.real_sol_reserves {
             // Cap SOL output at real reserves
             let capped_sol = curve.real_sol_reserves;
             // Back-calculate max tokens: tokens = k / (virtual_sol - capped_sol) - virtual_tokens
@@ -559,9 +1601,13 @@ pub mod clawdvault {
             let max_tokens = (max_virtual_tokens as u64)
                 .checked_sub(curve.virtual_token_reserves)
                 .ok_or(ClawdVaultError::MathOverflow)?;
-            (capped_sol, max_tokens)
+            let tokens_sent = amount_with_fee(&mint_info, max_tokens)?;
+            let tokens_received = tokens_sent
+                .checked_sub(transfer_fee(&mint_info, tokens_sent)?)
+                .ok_or(ClawdVaultError::MathOverflow)?;
+            (capped_sol, tokens_sent, tokens_received)
         } else {
-            (sol_out_requested, token_amount)
+            (sol_out_requested, token_amount, tokens_in)
         };
         
         // Calculate fees (taken from output)
@@ -586,27 +1632,20 @@ pub mod clawdvault {
         require!(sol_out_net >= min_sol_out, ClawdVaultError::SlippageExceeded);
         
         // Transfer tokens from seller to vault (use actual_token_amount which may be capped)
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.seller_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.token_vault.to_account_info(),
                     authority: ctx.accounts.seller.to_account_info(),
                 },
             ),
             actual_token_amount,
+            ctx.accounts.mint.decimals,
         )?;
         
-        // Transfer SOL from vault to seller
-        let mint_key = curve.mint;
-        let vault_seeds = &[
-            VAULT_SEED,
-            mint_key.as_ref(),
-            &[curve.sol_vault_bump],
-        ];
-        let vault_signer = &[&vault_seeds[..]];
-        
         // Transfer net SOL to seller
         **ctx.accounts.sol_vault.to_account_info().try_borrow_mut_lamports()? -= sol_out_net;
         **ctx.accounts.seller.to_account_info().try_borrow_mut_lamports()? += sol_out_net;
@@ -619,9 +1658,9 @@ pub mod clawdvault {
         **ctx.accounts.sol_vault.to_account_info().try_borrow_mut_lamports()? -= creator_fee;
         **ctx.accounts.creator.to_account_info().try_borrow_mut_lamports()? += creator_fee;
         
-        // Update curve state (recalculate based on actual_token_amount)
+        // Update curve state (recalculate based on the tokens the vault actually received)
         let final_virtual_tokens = curve.virtual_token_reserves
-            .checked_add(actual_token_amount)
+            .checked_add(tokens_received)
             .ok_or(ClawdVaultError::MathOverflow)?;
         let final_virtual_sol = invariant
             .checked_div(final_virtual_tokens as u128)
@@ -633,362 +1672,4854 @@ pub mod clawdvault {
             .checked_sub(sol_out_gross)
             .ok_or(ClawdVaultError::MathOverflow)?;
         curve.real_token_reserves = curve.real_token_reserves
-            .checked_add(actual_token_amount)
+            .checked_add(tokens_received)
             .ok_or(ClawdVaultError::MathOverflow)?;
         
-        msg!("🔴 SELL: {} tokens -> {} lamports (requested: {})", actual_token_amount, sol_out_net, token_amount);
-        msg!("Fees: {} protocol, {} creator", protocol_fee, creator_fee);
+        log!("🔴 SELL: {} tokens -> {} lamports (requested: {})", actual_token_amount, sol_out_net, token_amount);
+        log!("Fees: {} protocol, {} creator", protocol_fee, creator_fee);
+        
+        let mint_key = curve.mint;
+        let virtual_sol_reserves = curve.virtual_sol_reserves;
+        let virtual_token_reserves = curve.virtual_token_reserves;
         
-        emit!(TradeEvent {
-            mint: curve.mint,
+        emit_cpi!(TradeEvent {
+            mint: mint_key,
             trader: ctx.accounts.seller.key(),
             is_buy: false,
             sol_amount: sol_out_net,
             token_amount: actual_token_amount,
             protocol_fee,
             creator_fee,
-            virtual_sol_reserves: curve.virtual_sol_reserves,
-            virtual_token_reserves: curve.virtual_token_reserves,
+            virtual_sol_reserves,
+            virtual_token_reserves,
             timestamp: Clock::get()?.unix_timestamp,
         });
         
         Ok(())
     }
 
-    /// Release graduated token's assets to migration wallet for Raydium pool creation
+    /// Release graduated token's assets to migration wallet for pool creation on `dex`
     /// Only callable by protocol authority after graduation threshold is hit
-    pub fn release_for_migration(ctx: Context<ReleaseForMigration>) -> Result<()> {
-        let curve = &ctx.accounts.bonding_curve;
-        let mint_key = curve.mint;
-        let bump = curve.bump;
-        let sol_vault_bump = curve.sol_vault_bump;
+    /// The curve stays `Migrating` until the pool is recorded with `record_migration`
+    pub fn release_for_migration(ctx: Context<ReleaseForMigration>, dex: MigrationDex) -> Result<()> {
+        ctx.accounts.bonding_curve.require_status(CurveStatus::Graduated)?;
         
-        require!(curve.graduated, ClawdVaultError::NotGraduated);
-        require!(!curve.migrated_to_raydium, ClawdVaultError::AlreadyMigrated);
+        let accounts = &mut *ctx.accounts;
+        let event = release_curve(
+            ReleaseCurveAccounts {
+                config: &accounts.config,
+                bonding_curve: &mut accounts.bonding_curve,
+                token_mint: &accounts.token_mint,
+                sol_vault: &accounts.sol_vault.to_account_info(),
+                token_vault: &accounts.token_vault,
+                migration_escrow: &accounts.migration_escrow.to_account_info(),
+                escrow_token_account: &accounts.escrow_token_account.to_account_info(),
+                fee_recipient: &accounts.fee_recipient.to_account_info(),
+                creator: &accounts.creator.to_account_info(),
+                excess_token_account: accounts.excess_token_account.as_ref(),
+                token_program: &accounts.token_program,
+            },
+            dex,
+        )?;
+        emit_cpi!(event);
         
-        let sol_amount = curve.real_sol_reserves;
-        let token_amount = curve.real_token_reserves;
+        Ok(())
+    }
+
+    /// Release several graduated curves in one transaction (operator or authority)
+    /// `remaining_accounts` holds RELEASE_BATCH_GROUP_LEN accounts per curve:
+    /// bonding_curve, mint, sol_vault, token_vault, migration_escrow, escrow_token_account,
+    /// creator, excess_token_account (program id for none). Escrow token accounts must exist.
+    /// Each curve is validated before anything moves, so one bad curve doesn't fail the batch;
+    /// per-curve results are returned and failures emitted as `MigrationReleaseFailed`.
+    /// An error once a curve's assets have started moving aborts the whole transaction.
+    pub fn release_for_migration_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReleaseForMigrationBatch<'info>>,
+        dex: MigrationDex,
+    ) -> Result<Vec<ReleaseResult>> {
+        let groups = ctx.remaining_accounts.chunks_exact(RELEASE_BATCH_GROUP_LEN);
+        require!(
+            groups.remainder().is_empty() && groups.len() > 0 && groups.len() <= MAX_RELEASE_BATCH,
+            ClawdVaultError::InvalidBatch
+        );
         
-        msg!("🚀 Releasing assets for Raydium migration...");
-        msg!("SOL to transfer: {} lamports", sol_amount);
-        msg!("Tokens to transfer: {}", token_amount);
+        let mut results = Vec::with_capacity(groups.len());
+        for group in groups {
+            let mint = group[1].key();
+            let error_code = match check_batch_group(&ctx, group, dex) {
+                Ok(checked) => {
+                    let event = release_batch_group(&ctx, checked, dex)?;
+                    emit_cpi!(event);
+                    0
+                }
+                Err(err) => {
+                    let error_code = match err {
+                        Error::AnchorError(err) => err.error_code_number,
+                        Error::ProgramError(err) => u64::from(err.program_error) as u32,
+                    };
+                    log!("⚠️ Release failed for {}: {}", mint, error_code);
+                    emit_cpi!(MigrationReleaseFailed {
+                        mint,
+                        error_code,
+                        timestamp: Clock::get()?.unix_timestamp,
+                    });
+                    error_code
+                }
+            };
+            results.push(ReleaseResult { mint, error_code });
+        }
         
-        // Build signer seeds for bonding curve PDA
-        let curve_seeds = &[
-            CURVE_SEED,
+        Ok(results)
+    }
+
+    /// Move escrowed assets to the migration wallet for pool creation (operator or authority)
+    /// Only allowed when `finalize_migration` for the same curve runs later in this transaction
+    pub fn claim_migration_escrow(ctx: Context<ClaimMigrationEscrow>) -> Result<()> {
+        let curve = &ctx.accounts.bonding_curve;
+        curve.require_status(CurveStatus::Migrating)?;
+        
+        // Require a matching finalize_migration after this instruction
+        let instructions = ctx.accounts.instructions.to_account_info();
+        let curve_key = curve.key();
+        let mut index = load_current_index_checked(&instructions)? as usize + 1;
+        let mut finalized = false;
+        while let Ok(ix) = load_instruction_at_checked(index, &instructions) {
+            if ix.program_id == crate::ID
+                && ix.data.starts_with(instruction::FinalizeMigration::DISCRIMINATOR)
+                && ix.accounts.iter().any(|meta| meta.pubkey == curve_key)
+            {
+                finalized = true;
+                break;
+            }
+            index += 1;
+        }
+        require!(finalized, ClawdVaultError::MigrationNotFinalized);
+        
+        let migration_wallet = ctx.accounts.migration_wallet.key();
+        require!(
+            ctx.accounts.config.migration_wallets.contains(&migration_wallet),
+            ClawdVaultError::MigrationDestinationNotAllowed
+        );
+        
+        let mint_key = curve.mint;
+        let sol_amount = curve.migration_sol_amount;
+        let token_amount = curve.migration_token_amount;
+        
+        // Token-2022 transfer fees are withheld on this hop and again on the way into the pool,
+        // so `finalize_migration` checks the pool for what can actually arrive
+        let mint_info = ctx.accounts.mint.to_account_info();
+        let claimed_tokens = token_amount
+            .checked_sub(transfer_fee(&mint_info, token_amount)?)
+            .ok_or(ClawdVaultError::MathOverflow)?;
+        let poolable_tokens = claimed_tokens
+            .checked_sub(transfer_fee(&mint_info, claimed_tokens)?)
+            .ok_or(ClawdVaultError::MathOverflow)?;
+        let curve = &mut ctx.accounts.bonding_curve;
+        curve.migration_wallet = migration_wallet;
+        curve.migration_token_amount = poolable_tokens;
+        
+        **ctx.accounts.migration_escrow.to_account_info().try_borrow_mut_lamports()? -= sol_amount;
+        **ctx.accounts.migration_wallet.to_account_info().try_borrow_mut_lamports()? += sol_amount;
+        
+        let escrow_seeds = &[
+            MIGRATION_ESCROW_SEED,
             mint_key.as_ref(),
-            &[bump],
+            &[ctx.bumps.migration_escrow],
         ];
-        let curve_signer = &[&curve_seeds[..]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.migration_token_account.to_account_info(),
+                    authority: ctx.accounts.migration_escrow.to_account_info(),
+                },
+                &[&escrow_seeds[..]],
+            ),
+            token_amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        
+        log!("📤 Escrow claimed: {} SOL + {} tokens", sol_amount, token_amount);
+        
+        emit_cpi!(MigrationEscrowClaimed {
+            mint: mint_key,
+            migration_wallet,
+            sol_amount,
+            token_amount,
+            signer: ctx.accounts.operator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Verify the pool created from the escrowed assets and mark the curve migrated (operator or authority)
+    /// The pool is decoded for the curve's DEX and must pair the curve mint with wrapped SOL
+    /// through vaults holding at least the released amounts. The migration wallet's LP tokens
+    /// are burned or moved to the LP lock PDA before the handling is recorded.
+    pub fn finalize_migration(ctx: Context<FinalizeMigration>, lp_handling: LpHandling) -> Result<()> {
+        ctx.accounts.bonding_curve.require_status(CurveStatus::Migrating)?;
+        
+        let pool_info = ctx.accounts.pool.to_account_info();
+        require!(
+            ctx.accounts.config.amm_programs.contains(pool_info.owner),
+            ClawdVaultError::InvalidMigrationPool
+        );
+        
+        let curve = &ctx.accounts.bonding_curve;
+        let mint_key = curve.mint;
+        let dex = curve.migration_dex.ok_or(ClawdVaultError::InvalidAccountData)?;
+        require!(
+            ctx.accounts.pool_sol_vault.amount >= curve.migration_sol_amount
+                && ctx.accounts.pool_token_vault.amount >= curve.migration_token_amount,
+            ClawdVaultError::InsufficientLiquidity
+        );
+        
+        // The vaults and LP mint must be the ones the pool itself records
+        let pool_keys = decode_pool(dex, &pool_info)?;
+        require!(
+            pool_keys.vault_for(&NATIVE_MINT) == Some(ctx.accounts.pool_sol_vault.key())
+                && pool_keys.vault_for(&mint_key) == Some(ctx.accounts.pool_token_vault.key())
+                && pool_keys.lp_mint == ctx.accounts.lp_mint.key(),
+            ClawdVaultError::InvalidMigrationPool
+        );
+        
+        // Take the LP out of the migration wallet's hands
+        let lp_amount = ctx.accounts.lp_token_account.amount;
+        match lp_handling {
+            LpHandling::Burned => {
+                token_interface::burn(
+                    CpiContext::new(
+                        ctx.accounts.lp_token_program.to_account_info(),
+                        Burn {
+                            mint: ctx.accounts.lp_mint.to_account_info(),
+                            from: ctx.accounts.lp_token_account.to_account_info(),
+                            authority: ctx.accounts.migration_wallet.to_account_info(),
+                        },
+                    ),
+                    lp_amount,
+                )?;
+            }
+            LpHandling::Locked => {
+                let lp_lock_token_account = ctx.accounts.lp_lock_token_account
+                    .as_ref()
+                    .ok_or(ClawdVaultError::LpNotSecured)?;
+                token_interface::transfer_checked(
+                    CpiContext::new(
+                        ctx.accounts.lp_token_program.to_account_info(),
+                        TransferChecked {
+                            from: ctx.accounts.lp_token_account.to_account_info(),
+                            mint: ctx.accounts.lp_mint.to_account_info(),
+                            to: lp_lock_token_account.to_account_info(),
+                            authority: ctx.accounts.migration_wallet.to_account_info(),
+                        },
+                    ),
+                    lp_amount,
+                    ctx.accounts.lp_mint.decimals,
+                )?;
+            }
+        }
+        
+        // No LP may be left anywhere else
+        ctx.accounts.lp_mint.reload()?;
+        let lp_supply = ctx.accounts.lp_mint.supply;
+        let secured = match lp_handling {
+            LpHandling::Burned => lp_supply == 0,
+            LpHandling::Locked => {
+                let lp_lock_token_account = ctx.accounts.lp_lock_token_account
+                    .as_mut()
+                    .ok_or(ClawdVaultError::LpNotSecured)?;
+                lp_lock_token_account.reload()?;
+                lp_lock_token_account.amount == lp_supply
+            }
+        };
+        require!(secured, ClawdVaultError::LpNotSecured);
+        
+        let pool = pool_info.key();
+        let lp_mint = pool_keys.lp_mint;
+        let curve = &mut ctx.accounts.bonding_curve;
+        curve.transition(CurveStatus::Migrated)?;
+        curve.migration_pool = pool;
+        curve.migration_lp_mint = lp_mint;
+        curve.migration_lp_handling = Some(lp_handling);
+        
+        log!("🏊 Migration finalized: {:?} pool {}", dex, pool);
+        log!("LP mint: {} ({:?} {})", lp_mint, lp_handling, lp_amount);
+        
+        emit_cpi!(MigrationRecordedEvent {
+            mint: mint_key,
+            dex,
+            pool,
+            lp_mint,
+            lp_handling,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Return escrowed assets to the curve if the migration wasn't finalized in time (permissionless)
+    /// The migration fee and graduation bonus stay paid, so a later release doesn't charge them again
+    pub fn refund_migration(ctx: Context<RefundMigration>) -> Result<()> {
+        let curve = &ctx.accounts.bonding_curve;
+        curve.require_status(CurveStatus::Migrating)?;
         
-        // Build signer seeds for SOL vault PDA
-        let vault_seeds = &[
-            VAULT_SEED,
+        let timestamp = Clock::get()?.unix_timestamp;
+        require!(
+            timestamp >= curve.migration_started_at.saturating_add(MIGRATION_TIMEOUT_SECONDS),
+            ClawdVaultError::MigrationTimeoutNotReached
+        );
+        
+        let mint_key = curve.mint;
+        let sol_amount = curve.migration_sol_amount;
+        let token_amount = curve.migration_token_amount;
+        
+        **ctx.accounts.migration_escrow.to_account_info().try_borrow_mut_lamports()? -= sol_amount;
+        **ctx.accounts.sol_vault.to_account_info().try_borrow_mut_lamports()? += sol_amount;
+        
+        let escrow_seeds = &[
+            MIGRATION_ESCROW_SEED,
             mint_key.as_ref(),
-            &[sol_vault_bump],
+            &[ctx.bumps.migration_escrow],
         ];
-        let vault_signer = &[&vault_seeds[..]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.token_vault.to_account_info(),
+                    authority: ctx.accounts.migration_escrow.to_account_info(),
+                },
+                &[&escrow_seeds[..]],
+            ),
+            token_amount,
+            ctx.accounts.mint.decimals,
+        )?;
         
-        // Transfer SOL from vault to migration wallet
-        if sol_amount > 0 {
-            let sol_vault_info = ctx.accounts.sol_vault.to_account_info();
-            let migration_wallet_info = ctx.accounts.migration_wallet.to_account_info();
-            
-            **sol_vault_info.try_borrow_mut_lamports()? -= sol_amount;
-            **migration_wallet_info.try_borrow_mut_lamports()? += sol_amount;
-            
-            msg!("✅ Transferred {} SOL to migration wallet", sol_amount);
+        let curve = &mut ctx.accounts.bonding_curve;
+        curve.transition(CurveStatus::Graduated)?;
+        curve.migration_dex = None;
+        curve.migration_started_at = 0;
+        curve.migration_sol_amount = 0;
+        curve.migration_token_amount = 0;
+        curve.real_sol_reserves = sol_amount;
+        curve.real_token_reserves = token_amount
+            .checked_sub(transfer_fee(&ctx.accounts.mint.to_account_info(), token_amount)?)
+            .ok_or(ClawdVaultError::MathOverflow)?;
+        
+        log!("↩️ Migration refunded: {} SOL + {} tokens back to curve", sol_amount, token_amount);
+        
+        emit_cpi!(MigrationRefundedEvent {
+            mint: mint_key,
+            sol_amount,
+            token_amount,
+            signer: ctx.accounts.signer.key(),
+            timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Backfill the pool for curves migrated before it was recorded on-chain (operator or authority)
+    /// New migrations are recorded by `finalize_migration`. The pool must be owned by the
+    /// curve's DEX and pair the curve mint with wrapped SOL; its LP mint is read from the pool.
+    pub fn record_migration(ctx: Context<RecordMigration>, lp_handling: LpHandling) -> Result<()> {
+        let pool_info = ctx.accounts.pool.to_account_info();
+        let curve = &mut ctx.accounts.bonding_curve;
+        
+        // Legacy curves: status derived on resize, pool never recorded
+        curve.require_status(CurveStatus::Migrated)?;
+        require!(curve.migration_pool == Pubkey::default(), ClawdVaultError::AlreadyMigrated);
+        
+        let dex = curve.migration_dex.ok_or(ClawdVaultError::InvalidAccountData)?;
+        let pool_keys = decode_pool(dex, &pool_info)?;
+        require!(
+            pool_keys.vault_for(&NATIVE_MINT).is_some() && pool_keys.vault_for(&curve.mint).is_some(),
+            ClawdVaultError::InvalidMigrationPool
+        );
+        
+        let pool = pool_info.key();
+        let lp_mint = pool_keys.lp_mint;
+        curve.migration_pool = pool;
+        curve.migration_lp_mint = lp_mint;
+        curve.migration_lp_handling = Some(lp_handling);
+        
+        log!("🏊 Migration recorded: {:?} pool {}", dex, pool);
+        log!("LP mint: {} ({:?})", lp_mint, lp_handling);
+        
+        let mint = curve.mint;
+        emit_cpi!(MigrationRecordedEvent {
+            mint,
+            dex,
+            pool,
+            lp_mint,
+            lp_handling,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Put a curve that missed its graduation deadline into refund mode (permissionless)
+    pub fn start_refund(ctx: Context<StartRefund>) -> Result<()> {
+        let curve = &mut ctx.accounts.bonding_curve;
+        require!(
+            curve.status.can_transition_to(CurveStatus::Refunding),
+            ClawdVaultError::InvalidStatusTransition
+        );
+        
+        let timestamp = Clock::get()?.unix_timestamp;
+        require!(
+            curve.graduation_deadline != 0 && timestamp >= curve.graduation_deadline,
+            ClawdVaultError::DeadlineNotReached
+        );
+        
+        curve.transition(CurveStatus::Refunding)?;
+        
+        log!("⏰ Graduation deadline missed, refunding {} lamports", curve.real_sol_reserves);
+        
+        let mint = curve.mint;
+        let real_sol_reserves = curve.real_sol_reserves;
+        emit_cpi!(RefundStartedEvent {
+            mint,
+            real_sol_reserves,
+            signer: ctx.accounts.signer.key(),
+            timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Burn tokens for a pro-rata share of a refunding curve's SOL
+    /// Closes the curve once every circulating token has been redeemed. Token-2022 transfer
+    /// fees withheld in the vault, the user's account and any token accounts passed as remaining
+    /// accounts are harvested into the mint first; fees withheld in the mint belong to the
+    /// creator and don't count as circulating
+    pub fn redeem<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Redeem<'info>>,
+        token_amount: u64,
+    ) -> Result<()> {
+        require!(token_amount > 0, ClawdVaultError::ZeroAmount);
+        
+        let curve = &ctx.accounts.bonding_curve;
+        curve.require_status(CurveStatus::Refunding)?;
+        
+        let mint_info = ctx.accounts.mint.to_account_info();
+        if transfer_fee_config(&mint_info)?.is_some() {
+            let token_program_info = ctx.accounts.token_program.to_account_info();
+            let mut sources = vec![
+                ctx.accounts.token_vault.to_account_info(),
+                ctx.accounts.user_token_account.to_account_info(),
+            ];
+            sources.extend(ctx.remaining_accounts.iter().cloned());
+            token_interface::harvest_withheld_tokens_to_mint(
+                CpiContext::new(
+                    token_program_info.clone(),
+                    HarvestWithheldTokensToMint {
+                        token_program_id: token_program_info,
+                        mint: mint_info.clone(),
+                    },
+                ),
+                sources,
+            )?;
+        }
+        let withheld: u64 = transfer_fee_config(&mint_info)?
+            .map(|config| config.withheld_amount.into())
+            .unwrap_or(0);
+        
+        // Tokens held outside the curve (redeemed tokens are burned, so supply shrinks)
+        let circulating = circulating_supply(ctx.accounts.mint.supply, ctx.accounts.token_vault.amount, withheld)?;
+        let sol_out = redeem_amount(token_amount, curve.real_sol_reserves, circulating)?;
+        
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            token_amount,
+        )?;
+        
+        **ctx.accounts.sol_vault.to_account_info().try_borrow_mut_lamports()? -= sol_out;
+        **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += sol_out;
+        
+        let curve = &mut ctx.accounts.bonding_curve;
+        curve.real_sol_reserves = curve.real_sol_reserves
+            .checked_sub(sol_out)
+            .ok_or(ClawdVaultError::MathOverflow)?;
+        if token_amount == circulating {
+            curve.transition(CurveStatus::Closed)?;
+            log!("🔒 All tokens redeemed, curve closed");
+        }
+        
+        log!("💸 Redeemed {} tokens for {} lamports", token_amount, sol_out);
+        
+        let mint = curve.mint;
+        emit_cpi!(RedeemEvent {
+            mint,
+            user: ctx.accounts.user.key(),
+            token_amount,
+            sol_amount: sol_out,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Close a refunding curve that `redeem` can't close (permissionless)
+    /// Token-2022 transfer fees withheld in token accounts passed as remaining accounts are
+    /// harvested into the mint first. Closes right away if nothing is left circulating, otherwise
+    /// only once REFUND_GRACE_PERIOD has passed since the deadline. Unredeemed SOL goes to the
+    /// protocol fee recipient
+    pub fn close_refund<'info>(ctx: Context<'_, '_, 'info, 'info, CloseRefund<'info>>) -> Result<()> {
+        let curve = &ctx.accounts.bonding_curve;
+        curve.require_status(CurveStatus::Refunding)?;
+        
+        let mint_info = ctx.accounts.mint.to_account_info();
+        if !ctx.remaining_accounts.is_empty() && transfer_fee_config(&mint_info)?.is_some() {
+            let token_program_info = ctx.accounts.token_program.to_account_info();
+            token_interface::harvest_withheld_tokens_to_mint(
+                CpiContext::new(
+                    token_program_info.clone(),
+                    HarvestWithheldTokensToMint {
+                        token_program_id: token_program_info,
+                        mint: mint_info.clone(),
+                    },
+                ),
+                ctx.remaining_accounts.to_vec(),
+            )?;
+        }
+        let withheld: u64 = transfer_fee_config(&mint_info)?
+            .map(|config| config.withheld_amount.into())
+            .unwrap_or(0);
+        
+        let circulating = circulating_supply(ctx.accounts.mint.supply, ctx.accounts.token_vault.amount, withheld)?;
+        let timestamp = Clock::get()?.unix_timestamp;
+        if circulating > 0 {
+            let grace_end = curve.graduation_deadline
+                .checked_add(REFUND_GRACE_PERIOD)
+                .ok_or(ClawdVaultError::MathOverflow)?;
+            require!(timestamp >= grace_end, ClawdVaultError::RefundGracePeriodActive);
+        }
+        
+        let sol_amount = curve.real_sol_reserves;
+        **ctx.accounts.sol_vault.to_account_info().try_borrow_mut_lamports()? -= sol_amount;
+        **ctx.accounts.fee_recipient.to_account_info().try_borrow_mut_lamports()? += sol_amount;
+        
+        let curve = &mut ctx.accounts.bonding_curve;
+        curve.real_sol_reserves = 0;
+        curve.transition(CurveStatus::Closed)?;
+        
+        log!("🔒 Refund closed, {} unredeemed tokens, {} lamports swept", circulating, sol_amount);
+        
+        let mint = curve.mint;
+        emit_cpi!(RefundClosedEvent {
+            mint,
+            unredeemed_tokens: circulating,
+            sol_amount,
+            signer: ctx.accounts.signer.key(),
+            timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Apply the config's metadata policy to a graduated token (permissionless, once per curve)
+    /// Also revokes the mint authority of tokens created before it was revoked at launch
+    pub fn lock_metadata(ctx: Context<LockMetadata>) -> Result<()> {
+        let curve = &ctx.accounts.bonding_curve;
+        require!(curve.status.has_graduated(), ClawdVaultError::NotGraduated);
+        require!(curve.metadata_policy.is_none(), ClawdVaultError::MetadataAlreadyLocked);
+        
+        let policy = ctx.accounts.config.metadata_policy;
+        let mint_key = ctx.accounts.mint.key();
+        let creator = curve.creator;
+        let seeds = &[CURVE_SEED, mint_key.as_ref(), &[curve.bump]];
+        let signer_seeds = &[&seeds[..]];
+        
+        if ctx.accounts.mint.mint_authority == COption::Some(curve.key()) {
+            token_interface::set_authority(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    SetAuthority {
+                        current_authority: ctx.accounts.bonding_curve.to_account_info(),
+                        account_or_mint: ctx.accounts.mint.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                AuthorityType::MintTokens,
+                None,
+            )?;
         }
         
-        // Transfer tokens from vault to migration wallet's token account
-        if token_amount > 0 {
-            token::transfer(
+        // With a creator handoff pending the curve keeps the authority until it resolves
+        let holder = if curve.pending_creator == Pubkey::default() { creator } else { curve.key() };
+        let (new_update_authority, is_mutable) = match policy {
+            MetadataPolicy::Immutable => (None, Some(false)),
+            MetadataPolicy::CreatorAuthority => (Some(holder), None),
+        };
+        if curve.token_program == Token2022::id() {
+            // Token-2022 metadata is immutable once it has no update authority
+            token_interface::token_metadata_update_authority(
                 CpiContext::new_with_signer(
                     ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.token_vault.to_account_info(),
-                        to: ctx.accounts.migration_token_account.to_account_info(),
-                        authority: ctx.accounts.bonding_curve.to_account_info(),
+                    TokenMetadataUpdateAuthority {
+                        program_id: ctx.accounts.token_program.to_account_info(),
+                        metadata: ctx.accounts.mint.to_account_info(),
+                        current_authority: ctx.accounts.bonding_curve.to_account_info(),
+                        new_authority: ctx.accounts.bonding_curve.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                OptionalNonZeroPubkey::try_from(new_update_authority)?,
+            )?;
+        } else {
+            update_metadata_accounts_v2(
+                CpiContext::new_with_signer(
+                    ctx.accounts.metadata_program.to_account_info(),
+                    UpdateMetadataAccountsV2 {
+                        metadata: ctx.accounts.metadata.to_account_info(),
+                        update_authority: ctx.accounts.bonding_curve.to_account_info(),
                     },
-                    curve_signer,
+                    signer_seeds,
                 ),
-                token_amount,
+                new_update_authority,
+                None,
+                None,
+                is_mutable,
             )?;
-            
-            msg!("✅ Transferred {} tokens to migration wallet", token_amount);
         }
         
-        // Mark as migrated
-        let curve_mut = &mut ctx.accounts.bonding_curve;
-        curve_mut.migrated_to_raydium = true;
-        curve_mut.real_sol_reserves = 0;
-        curve_mut.real_token_reserves = 0;
+        ctx.accounts.bonding_curve.metadata_policy = Some(policy);
         
-        // Emit event
-        emit!(MigrationReleasedEvent {
+        log!("🔏 Metadata locked: {:?}", policy);
+        
+        emit_cpi!(MetadataLockedEvent {
             mint: mint_key,
-            sol_amount,
-            token_amount,
-            migration_wallet: ctx.accounts.migration_wallet.key(),
+            policy,
+            update_authority: new_update_authority.unwrap_or_default(),
             timestamp: Clock::get()?.unix_timestamp,
         });
         
-        msg!("🎓 Assets released for Raydium migration!");
-        
         Ok(())
     }
-}
-
-// ============================================================================
-// ACCOUNT STRUCTURES
-// ============================================================================
 
-/// Global protocol configuration
-#[account]
-pub struct Config {
-    pub authority: Pubkey,
-    pub fee_recipient: Pubkey,
-    pub migration_operator: Pubkey,  // Can trigger migrations (hot wallet)
-    pub total_tokens_created: u64,
-    pub total_volume_sol: u64,
-    pub bump: u8,
+    /// Move a graduated curve's reserves into a permanent ClawdVault AMM pool (permissionless)
+    /// LP tokens for the initial liquidity are minted to the burn address; the caller is
+    /// reimbursed from the reserves for the rent of the pool accounts it creates and
+    /// earns up to CRANK_BOUNTY_LAMPORTS out of the migration fee
+    pub fn crank_migration(ctx: Context<CrankMigration>) -> Result<()> {
+        let curve = &ctx.accounts.bonding_curve;
+        // The operator's release or another crank got there first: nothing to do
+        if matches!(curve.status, CurveStatus::Migrating | CurveStatus::Migrated) {
+            log!("Curve {} is already {:?}, crank skipped", curve.mint, curve.status);
+            return Ok(());
+        }
+        curve.require_status(CurveStatus::Graduated)?;
+        
+        let mint_key = curve.mint;
+        let curve_bump = curve.bump;
+        let pool_key = ctx.accounts.pool.key();
+        let pool_bump = ctx.bumps.pool;
+        let pool_seeds = &[
+            POOL_SEED,
+            mint_key.as_ref(),
+            &[pool_bump],
+        ];
+        
+        // Created here rather than with `init`, so a skipped crank creates nothing
+        let caller_info = ctx.accounts.caller.to_account_info();
+        let token_program_info = ctx.accounts.token_program.to_account_info();
+        let pool_vault_seeds = &[POOL_VAULT_SEED, mint_key.as_ref(), &[ctx.bumps.pool_sol_vault]];
+        let lp_mint_seeds = &[LP_MINT_SEED, pool_key.as_ref(), &[ctx.bumps.lp_mint]];
+        let lp_mint_len = ExtensionType::try_calculate_account_len::<MintState>(&[])?;
+        create_owned_account(
+            &caller_info,
+            &ctx.accounts.pool,
+            &ctx.accounts.system_program,
+            &[&pool_seeds[..]],
+            Pool::LEN,
+            Pool::LEN,
+            &crate::ID,
+        )?;
+        create_owned_account(
+            &caller_info,
+            &ctx.accounts.pool_sol_vault,
+            &ctx.accounts.system_program,
+            &[&pool_vault_seeds[..]],
+            0,
+            0,
+            &crate::ID,
+        )?;
+        create_owned_account(
+            &caller_info,
+            &ctx.accounts.lp_mint,
+            &ctx.accounts.system_program,
+            &[&lp_mint_seeds[..]],
+            lp_mint_len,
+            lp_mint_len,
+            token_program_info.key,
+        )?;
+        token_interface::initialize_mint2(
+            CpiContext::new(
+                token_program_info.clone(),
+                InitializeMint2 {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                },
+            ),
+            LP_DECIMALS,
+            &pool_key,
+            None,
+        )?;
+        for (associated_token, authority, mint) in [
+            (&ctx.accounts.pool_token_vault, ctx.accounts.pool.to_account_info(), ctx.accounts.mint.to_account_info()),
+            (&ctx.accounts.lp_burn_account, ctx.accounts.lp_burn_address.to_account_info(), ctx.accounts.lp_mint.to_account_info()),
+        ] {
+            associated_token::create(CpiContext::new(
+                ctx.accounts.associated_token_program.to_account_info(),
+                associated_token::Create {
+                    payer: caller_info.clone(),
+                    associated_token: associated_token.to_account_info(),
+                    authority,
+                    mint,
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: token_program_info.clone(),
+                },
+            ))?;
+        }
+        let (migration_fee, graduation_bonus, crank_bounty) = pay_migration_fees(
+            &ctx.accounts.config,
+            &mut ctx.accounts.bonding_curve,
+            &ctx.accounts.sol_vault.to_account_info(),
+            &ctx.accounts.fee_recipient.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+            Some(&ctx.accounts.caller.to_account_info()),
+        )?;
+        
+        // Cranking must not cost the caller the pool's rent, even when the migration fee is 0
+        let rent_reimbursed = [
+            ctx.accounts.pool.to_account_info(),
+            ctx.accounts.pool_sol_vault.to_account_info(),
+            ctx.accounts.pool_token_vault.to_account_info(),
+            ctx.accounts.lp_mint.to_account_info(),
+            ctx.accounts.lp_burn_account.to_account_info(),
+        ]
+        .iter()
+        .try_fold(0u64, |total, account| total.checked_add(account.lamports()))
+        .ok_or(ClawdVaultError::MathOverflow)?;
+        
+        let curve = &ctx.accounts.bonding_curve;
+        let paid_out = migration_fee
+            .checked_add(graduation_bonus)
+            .and_then(|paid| paid.checked_add(rent_reimbursed))
+            .ok_or(ClawdVaultError::MathOverflow)?;
+        let sol_amount = curve.real_sol_reserves
+            .checked_sub(paid_out)
+            .ok_or(ClawdVaultError::InsufficientLiquidity)?;
+        let token_amount = curve.listing_token_amount(sol_amount)?;
+        let excess_tokens = curve.real_token_reserves
+            .checked_sub(token_amount)
+            .ok_or(ClawdVaultError::MathOverflow)?;
+        require!(sol_amount > 0 && token_amount > 0, ClawdVaultError::InsufficientLiquidity);
+        
+        // The pool holds what arrives after any Token-2022 transfer fee
+        let pool_tokens = token_amount
+            .checked_sub(transfer_fee(&ctx.accounts.mint.to_account_info(), token_amount)?)
+            .ok_or(ClawdVaultError::MathOverflow)?;
+        
+        // Initial LP supply = sqrt(sol * tokens), all of it burned
+        let lp_amount = isqrt(
+            (sol_amount as u128)
+                .checked_mul(pool_tokens as u128)
+                .ok_or(ClawdVaultError::MathOverflow)?,
+        ) as u64;
+        require!(lp_amount > 0, ClawdVaultError::InsufficientLiquidity);
+        
+        // Move SOL from curve vault to pool vault (both program-owned), rent back to the caller
+        let vault_debit = sol_amount
+            .checked_add(rent_reimbursed)
+            .ok_or(ClawdVaultError::MathOverflow)?;
+        **ctx.accounts.sol_vault.to_account_info().try_borrow_mut_lamports()? -= vault_debit;
+        **ctx.accounts.pool_sol_vault.to_account_info().try_borrow_mut_lamports()? += sol_amount;
+        **ctx.accounts.caller.to_account_info().try_borrow_mut_lamports()? += rent_reimbursed;
+        
+        // Move tokens from curve vault to pool vault
+        let curve_seeds = &[
+            CURVE_SEED,
+            mint_key.as_ref(),
+            &[curve_bump],
+        ];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.pool_token_vault.to_account_info(),
+                    authority: ctx.accounts.bonding_curve.to_account_info(),
+                },
+                &[&curve_seeds[..]],
+            ),
+            token_amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        
+        dispose_excess_tokens(
+            &ctx.accounts.config,
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_vault,
+            ctx.accounts.bonding_curve.to_account_info(),
+            ctx.accounts.excess_token_account.as_deref(),
+            &[&curve_seeds[..]],
+            excess_tokens,
+        )?;
+        
+        // Mint initial LP to the burn address
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.lp_burn_account.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[&pool_seeds[..]],
+            ),
+            lp_amount,
+        )?;
+        
+        let lp_mint_key = ctx.accounts.lp_mint.key();
+        let timestamp = Clock::get()?.unix_timestamp;
+        
+        let pool = Pool {
+            mint: mint_key,
+            lp_mint: lp_mint_key,
+            sol_reserves: sol_amount,
+            token_reserves: pool_tokens,
+            lp_supply: lp_amount,
+            created_at: timestamp,
+            bump: pool_bump,
+            sol_vault_bump: ctx.bumps.pool_sol_vault,
+        };
+        pool.try_serialize(&mut &mut ctx.accounts.pool.try_borrow_mut_data()?[..])?;
+        
+        let curve = &mut ctx.accounts.bonding_curve;
+        curve.transition(CurveStatus::Migrated)?;
+        curve.migration_dex = Some(MigrationDex::ClawdVault);
+        curve.migration_pool = pool_key;
+        curve.migration_lp_mint = lp_mint_key;
+        curve.migration_lp_handling = Some(LpHandling::Burned);
+        curve.real_sol_reserves = 0;
+        curve.real_token_reserves = 0;
+        
+        log!("🏊 Pool created: {} SOL + {} tokens, {} LP burned", sol_amount, token_amount, lp_amount);
+        
+        emit_cpi!(PoolCreatedEvent {
+            mint: mint_key,
+            pool: pool_key,
+            lp_mint: lp_mint_key,
+            sol_amount,
+            token_amount,
+            lp_burned: lp_amount,
+            excess_tokens,
+            migration_fee,
+            graduation_bonus,
+            cranker: ctx.accounts.caller.key(),
+            crank_bounty,
+            rent_reimbursed,
+            timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Swap against a ClawdVault pool. Fees are taken in SOL and split between
+    /// protocol and creator; `amount_in` is lamports when buying, tokens when selling
+    pub fn swap(ctx: Context<Swap>, is_buy: bool, amount_in: u64, min_amount_out: u64) -> Result<()> {
+        require!(amount_in > 0, ClawdVaultError::ZeroAmount);
+        require!(
+            !is_buy || ctx.accounts.bonding_curve.flags & FLAG_BUYS_HALTED == 0,
+            ClawdVaultError::BuysHalted
+        );
+        
+        let pool = &ctx.accounts.pool;
+        let mint_key = pool.mint;
+        let pool_bump = pool.bump;
+        let sol_reserves = pool.sol_reserves;
+        let token_reserves = pool.token_reserves;
+        let mint_info = ctx.accounts.mint.to_account_info();
+        
+        // `token_amount` is what the pool sends or receives; Token-2022 transfer fees come out of the user's side
+        let (sol_amount, token_amount, protocol_fee, creator_fee) = if is_buy {
+            let (protocol_fee, creator_fee) = pool_fees(amount_in)?;
+            let sol_in = amount_in
+                .checked_sub(protocol_fee + creator_fee)
+                .ok_or(ClawdVaultError::MathOverflow)?;
+            let tokens_out = swap_out(sol_reserves, token_reserves, sol_in)?;
+            (sol_in, tokens_out, protocol_fee, creator_fee)
+        } else {
+            let tokens_in = amount_in
+                .checked_sub(transfer_fee(&mint_info, amount_in)?)
+                .ok_or(ClawdVaultError::MathOverflow)?;
+            let sol_out_gross = swap_out(token_reserves, sol_reserves, tokens_in)?;
+            let (protocol_fee, creator_fee) = pool_fees(sol_out_gross)?;
+            (sol_out_gross, tokens_in, protocol_fee, creator_fee)
+        };
+        
+        let amount_out = if is_buy {
+            token_amount
+                .checked_sub(transfer_fee(&mint_info, token_amount)?)
+                .ok_or(ClawdVaultError::MathOverflow)?
+        } else {
+            sol_amount
+                .checked_sub(protocol_fee)
+                .and_then(|sol| sol.checked_sub(creator_fee))
+                .ok_or(ClawdVaultError::MathOverflow)?
+        };
+        require!(amount_out > 0, ClawdVaultError::ZeroAmount);
+        require!(amount_out >= min_amount_out, ClawdVaultError::SlippageExceeded);
+        
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let user = ctx.accounts.user.to_account_info();
+        
+        if is_buy {
+            // SOL in from user (reserve + fees), tokens out from pool
+            for (to, lamports) in [
+                (ctx.accounts.pool_sol_vault.to_account_info(), sol_amount),
+                (ctx.accounts.fee_recipient.to_account_info(), protocol_fee),
+                (ctx.accounts.creator.to_account_info(), creator_fee),
+            ] {
+                system_program::transfer(
+                    CpiContext::new(
+                        system_program.clone(),
+                        system_program::Transfer { from: user.clone(), to },
+                    ),
+                    lamports,
+                )?;
+            }
+            
+            let pool_seeds = &[
+                POOL_SEED,
+                mint_key.as_ref(),
+                &[pool_bump],
+            ];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.pool_token_vault.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.user_token_account.to_account_info(),
+                        authority: ctx.accounts.pool.to_account_info(),
+                    },
+                    &[&pool_seeds[..]],
+                ),
+                token_amount,
+                ctx.accounts.mint.decimals,
+            )?;
+        } else {
+            // Tokens in from user, SOL out from pool (minus fees)
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.user_token_account.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.pool_token_vault.to_account_info(),
+                        authority: user.clone(),
+                    },
+                ),
+                amount_in,
+                ctx.accounts.mint.decimals,
+            )?;
+            
+            **ctx.accounts.pool_sol_vault.to_account_info().try_borrow_mut_lamports()? -= sol_amount;
+            **user.try_borrow_mut_lamports()? += amount_out;
+            **ctx.accounts.fee_recipient.to_account_info().try_borrow_mut_lamports()? += protocol_fee;
+            **ctx.accounts.creator.to_account_info().try_borrow_mut_lamports()? += creator_fee;
+        }
+        
+        let pool = &mut ctx.accounts.pool;
+        if is_buy {
+            pool.sol_reserves = pool.sol_reserves.checked_add(sol_amount).ok_or(ClawdVaultError::MathOverflow)?;
+            pool.token_reserves = pool.token_reserves.checked_sub(token_amount).ok_or(ClawdVaultError::MathOverflow)?;
+        } else {
+            pool.sol_reserves = pool.sol_reserves.checked_sub(sol_amount).ok_or(ClawdVaultError::MathOverflow)?;
+            pool.token_reserves = pool.token_reserves.checked_add(token_amount).ok_or(ClawdVaultError::MathOverflow)?;
+        }
+        
+        log!("{} POOL SWAP: {} in -> {} out", if is_buy { "🟢" } else { "🔴" }, amount_in, amount_out);
+        log!("Fees: {} protocol, {} creator", protocol_fee, creator_fee);
+        
+        let sol_reserves = pool.sol_reserves;
+        let token_reserves = pool.token_reserves;
+        emit_cpi!(PoolSwapEvent {
+            mint: mint_key,
+            trader: ctx.accounts.user.key(),
+            is_buy,
+            sol_amount: if is_buy { amount_in } else { amount_out },
+            token_amount,
+            protocol_fee,
+            creator_fee,
+            sol_reserves,
+            token_reserves,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Add liquidity to a ClawdVault pool at the current ratio, minting `lp_amount` LP tokens
+    pub fn deposit(ctx: Context<Deposit>, lp_amount: u64, max_sol_in: u64, max_tokens_in: u64) -> Result<()> {
+        require!(lp_amount > 0, ClawdVaultError::ZeroAmount);
+        
+        let pool = &ctx.accounts.pool;
+        let mint_key = pool.mint;
+        let pool_bump = pool.bump;
+        
+        let (sol_in, tokens_in) = pool.deposit_amounts(lp_amount)?;
+        // The user covers any Token-2022 transfer fee so the pool receives `tokens_in`
+        let tokens_sent = amount_with_fee(&ctx.accounts.mint.to_account_info(), tokens_in)?;
+        require!(
+            sol_in <= max_sol_in && tokens_sent <= max_tokens_in,
+            ClawdVaultError::SlippageExceeded
+        );
+        
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: ctx.accounts.pool_sol_vault.to_account_info(),
+                },
+            ),
+            sol_in,
+        )?;
+        
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.pool_token_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            tokens_sent,
+            ctx.accounts.mint.decimals,
+        )?;
+        
+        let pool_seeds = &[
+            POOL_SEED,
+            mint_key.as_ref(),
+            &[pool_bump],
+        ];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.user_lp_account.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[&pool_seeds[..]],
+            ),
+            lp_amount,
+        )?;
+        
+        let pool = &mut ctx.accounts.pool;
+        pool.sol_reserves = pool.sol_reserves.checked_add(sol_in).ok_or(ClawdVaultError::MathOverflow)?;
+        pool.token_reserves = pool.token_reserves.checked_add(tokens_in).ok_or(ClawdVaultError::MathOverflow)?;
+        pool.lp_supply = pool.lp_supply.checked_add(lp_amount).ok_or(ClawdVaultError::MathOverflow)?;
+        
+        log!("➕ Deposit: {} lamports + {} tokens -> {} LP", sol_in, tokens_in, lp_amount);
+        
+        emit_cpi!(PoolLiquidityEvent {
+            mint: mint_key,
+            provider: ctx.accounts.user.key(),
+            is_deposit: true,
+            sol_amount: sol_in,
+            token_amount: tokens_in,
+            lp_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Remove liquidity from a ClawdVault pool by burning `lp_amount` LP tokens
+    pub fn withdraw(ctx: Context<Withdraw>, lp_amount: u64, min_sol_out: u64, min_tokens_out: u64) -> Result<()> {
+        require!(lp_amount > 0, ClawdVaultError::ZeroAmount);
+        
+        let pool = &ctx.accounts.pool;
+        let mint_key = pool.mint;
+        let pool_bump = pool.bump;
+        
+        let (sol_out, tokens_out) = pool.withdraw_amounts(lp_amount)?;
+        let tokens_received = tokens_out
+            .checked_sub(transfer_fee(&ctx.accounts.mint.to_account_info(), tokens_out)?)
+            .ok_or(ClawdVaultError::MathOverflow)?;
+        require!(
+            sol_out >= min_sol_out && tokens_received >= min_tokens_out,
+            ClawdVaultError::SlippageExceeded
+        );
+        
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    from: ctx.accounts.user_lp_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            lp_amount,
+        )?;
+        
+        let pool_seeds = &[
+            POOL_SEED,
+            mint_key.as_ref(),
+            &[pool_bump],
+        ];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.pool_token_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[&pool_seeds[..]],
+            ),
+            tokens_out,
+            ctx.accounts.mint.decimals,
+        )?;
+        
+        **ctx.accounts.pool_sol_vault.to_account_info().try_borrow_mut_lamports()? -= sol_out;
+        **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += sol_out;
+        
+        let pool = &mut ctx.accounts.pool;
+        pool.sol_reserves = pool.sol_reserves.checked_sub(sol_out).ok_or(ClawdVaultError::MathOverflow)?;
+        pool.token_reserves = pool.token_reserves.checked_sub(tokens_out).ok_or(ClawdVaultError::MathOverflow)?;
+        pool.lp_supply = pool.lp_supply.checked_sub(lp_amount).ok_or(ClawdVaultError::MathOverflow)?;
+        
+        log!("➖ Withdraw: {} LP -> {} lamports + {} tokens", lp_amount, sol_out, tokens_out);
+        
+        emit_cpi!(PoolLiquidityEvent {
+            mint: mint_key,
+            provider: ctx.accounts.user.key(),
+            is_deposit: false,
+            sol_amount: sol_out,
+            token_amount: tokens_out,
+            lp_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// Length limits shared by `create_token` and `update_token_metadata`
+fn validate_token_metadata(config: &Config, name: &str, symbol: &str, uri: &str) -> Result<()> {
+    require!(name.len() <= 32, ClawdVaultError::NameTooLong);
+    require!(symbol.len() <= 10, ClawdVaultError::SymbolTooLong);
+    require!(uri.len() <= 200, ClawdVaultError::UriTooLong);
+    require!(charset_allows(config.name_charset, name), ClawdVaultError::InvalidNameCharacters);
+    require!(charset_allows(config.symbol_charset, symbol), ClawdVaultError::InvalidSymbolCharacters);
+    require!(
+        config.uri_prefixes.is_empty()
+            || config.uri_prefixes.iter().any(|prefix| uri.starts_with(prefix.as_str())),
+        ClawdVaultError::UriNotAllowed
+    );
+    Ok(())
+}
+
+/// Whether every character of `value` falls in a class of `charset` (0 = any)
+/// Restricted charsets are ASCII-only, which rules out zero-width and look-alike characters
+fn charset_allows(charset: u8, value: &str) -> bool {
+    if charset == 0 {
+        return true;
+    }
+    value.chars().all(|c| {
+        let class = match c {
+            'A'..='Z' => CHARSET_UPPER,
+            'a'..='z' => CHARSET_LOWER,
+            '0'..='9' => CHARSET_DIGIT,
+            ' ' => CHARSET_SPACE,
+            c if c.is_ascii_punctuation() => CHARSET_PUNCT,
+            _ => 0,
+        };
+        charset & class != 0
+    })
+}
+
+/// Top up `account` so it stays rent-exempt at `new_size` (call before resizing)
+fn fund_rent_exemption<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    new_size: usize,
+) -> Result<()> {
+    let new_min_balance = Rent::get()?.minimum_balance(new_size);
+    let current_balance = account.lamports();
+    
+    if current_balance < new_min_balance {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            new_min_balance - current_balance,
+        )?;
+    }
+    
+    Ok(())
+}
+
+/// Registry key for a ticker (ASCII uppercase, so "clawd" and "CLAWD" collide)
+fn normalize_symbol(symbol: &str) -> String {
+    symbol.to_ascii_uppercase()
+}
+
+/// Normalized ticker for a registry record; fails for symbols that can't be registered
+fn registry_symbol(symbol: &str) -> Result<String> {
+    let normalized = normalize_symbol(symbol);
+    require!(!normalized.is_empty(), ClawdVaultError::EmptySymbol);
+    require!(normalized.len() <= 10, ClawdVaultError::SymbolTooLong);
+    Ok(normalized)
+}
+
+/// Free a ticker claimed by `mint` when the token is renamed, refunding the record's rent to
+/// `rent_receiver`, which must be the account that paid it. `record` must be the registry PDA of
+/// `old_symbol`; if it was never created there is nothing to free, and verified or reserved
+/// records stay with the authority
+fn release_symbol<'info>(
+    record: Option<&UncheckedAccount<'info>>,
+    old_symbol: &str,
+    mint: Pubkey,
+    rent_receiver: Option<&UncheckedAccount<'info>>,
+) -> Result<()> {
+    // Symbols from before the registry limits can't have a record
+    let Ok(old_symbol) = registry_symbol(old_symbol) else {
+        return Ok(());
+    };
+    let record = record.ok_or(ClawdVaultError::SymbolRecordRequired)?;
+    let (expected, _) = Pubkey::find_program_address(&[SYMBOL_SEED, old_symbol.as_bytes()], &crate::ID);
+    require!(record.key() == expected, ClawdVaultError::InvalidAccountData);
+    
+    if record.owner != &crate::ID {
+        return Ok(());
+    }
+    let claim = SymbolRecord::try_deserialize(&mut &record.try_borrow_data()?[..])?;
+    if claim.status == SymbolStatus::Claimed && claim.mint == mint {
+        let rent_receiver = rent_receiver
+            .filter(|receiver| receiver.key() == claim.payer)
+            .ok_or(ClawdVaultError::InvalidSymbolPayer)?;
+        log!("Symbol {} released", claim.symbol);
+        let record_info = record.to_account_info();
+        let rent = record_info.lamports();
+        **rent_receiver.try_borrow_mut_lamports()? = rent_receiver
+            .lamports()
+            .checked_add(rent)
+            .ok_or(ClawdVaultError::MathOverflow)?;
+        **record_info.try_borrow_mut_lamports()? = 0;
+        record_info.assign(&system_program::ID);
+        record_info.resize(0)?;
+    }
+    Ok(())
+}
+
+/// Current symbol in a token's metadata (Token-2022 extension or Metaplex account)
+fn token_symbol(curve: &BondingCurve, mint: &AccountInfo, metadata: &AccountInfo) -> Result<String> {
+    if curve.token_program == Token2022::id() {
+        let data = mint.try_borrow_data()?;
+        let metadata = StateWithExtensions::<MintState>::unpack(&data)?
+            .get_variable_len_extension::<TokenMetadata>()?;
+        Ok(metadata.symbol)
+    } else {
+        let metadata = MetadataAccountData::safe_deserialize(&metadata.try_borrow_data()?)
+            .map_err(|_| ClawdVaultError::InvalidAccountData)?;
+        // Metaplex pads strings with NULs
+        Ok(metadata.symbol.trim_end_matches('\0').to_string())
+    }
+}
+
+/// Whether a creator handoff also rewrites the Metaplex creators list: only for legacy tokens
+/// whose metadata is still mutable. Token-2022 metadata has no creators list
+fn metadata_follows_creator(curve: &BondingCurve) -> bool {
+    curve.token_program != Token2022::id() && curve.metadata_policy != Some(MetadataPolicy::Immutable)
+}
+
+/// Whether the creator holds the metadata update authority (locked under `CreatorAuthority`).
+/// While a handoff is pending the curve holds it in escrow, so the new creator receives it on accept
+fn creator_holds_update_authority(curve: &BondingCurve) -> bool {
+    curve.metadata_policy == Some(MetadataPolicy::CreatorAuthority)
+}
+
+/// Hand a token's metadata update authority to `new_authority` (Token-2022 or Metaplex)
+#[allow(clippy::too_many_arguments)]
+fn set_update_authority<'info>(
+    curve: &BondingCurve,
+    mint: &UncheckedAccount<'info>,
+    metadata: &UncheckedAccount<'info>,
+    metadata_program: &Program<'info, Metadata>,
+    token_program: &Interface<'info, TokenInterface>,
+    current_authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    new_authority: Pubkey,
+) -> Result<()> {
+    if curve.token_program == Token2022::id() {
+        token_interface::token_metadata_update_authority(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TokenMetadataUpdateAuthority {
+                    program_id: token_program.to_account_info(),
+                    metadata: mint.to_account_info(),
+                    current_authority: current_authority.clone(),
+                    new_authority: current_authority,
+                },
+                signer_seeds,
+            ),
+            OptionalNonZeroPubkey::try_from(Some(new_authority))?,
+        )
+    } else {
+        update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                metadata_program.to_account_info(),
+                UpdateMetadataAccountsV2 {
+                    metadata: metadata.to_account_info(),
+                    update_authority: current_authority,
+                },
+                signer_seeds,
+            ),
+            Some(new_authority),
+            None,
+            None,
+            None,
+        )
+    }
+}
+
+/// Verify or unverify a creator on a legacy token's Metaplex metadata (no-op if unchanged)
+fn set_creator_verified<'info>(
+    metadata: &UncheckedAccount<'info>,
+    metadata_program: &Program<'info, Metadata>,
+    creator: &Signer<'info>,
+    verified: bool,
+) -> Result<()> {
+    let current = MetadataAccountData::safe_deserialize(&metadata.try_borrow_data()?)
+        .map_err(|_| ClawdVaultError::InvalidAccountData)?;
+    let Some(entry) = current.creators.iter().flatten().find(|c| c.address == creator.key()) else {
+        return Ok(());
+    };
+    if entry.verified == verified {
+        return Ok(());
+    }
+    
+    if verified {
+        sign_metadata(CpiContext::new(
+            metadata_program.to_account_info(),
+            SignMetadata {
+                creator: creator.to_account_info(),
+                metadata: metadata.to_account_info(),
+            },
+        ))
+    } else {
+        remove_creator_verification(CpiContext::new(
+            metadata_program.to_account_info(),
+            RemoveCreatorVerification {
+                creator: creator.to_account_info(),
+                metadata: metadata.to_account_info(),
+            },
+        ))
+    }
+}
+
+/// Validate and store a token profile (shared by `set_token_profile` and the create instructions)
+fn write_token_profile(
+    profile: &mut TokenProfile,
+    mint: Pubkey,
+    creator: Pubkey,
+    args: TokenProfileArgs,
+    bump: u8,
+) -> Result<TokenProfileUpdated> {
+    require!(
+        args.website.len() <= MAX_PROFILE_WEBSITE_LEN
+            && args.twitter.len() <= MAX_PROFILE_HANDLE_LEN
+            && args.telegram.len() <= MAX_PROFILE_HANDLE_LEN
+            && args.agent_id.len() <= MAX_PROFILE_AGENT_LEN,
+        ClawdVaultError::ProfileFieldTooLong
+    );
+    require!(
+        args.description.len() <= MAX_PROFILE_DESCRIPTION_LEN,
+        ClawdVaultError::DescriptionTooLong
+    );
+    
+    let timestamp = Clock::get()?.unix_timestamp;
+    profile.mint = mint;
+    profile.website = args.website;
+    profile.twitter = args.twitter;
+    profile.telegram = args.telegram;
+    profile.agent_id = args.agent_id;
+    profile.description = args.description;
+    profile.updated_at = timestamp;
+    profile.bump = bump;
+    
+    Ok(TokenProfileUpdated { mint, creator, timestamp })
+}
+
+/// Claim the launch's ticker in the symbol registry, or apply the config's symbol policy
+/// if it collides with a verified or reserved ticker
+/// `payer` is the account that funded a new record, refunded if the claim is released
+/// Returns the collision to emit when the launch was flagged
+fn claim_symbol(
+    config: &Config,
+    symbol_record: Option<&mut Account<SymbolRecord>>,
+    bonding_curve: &mut BondingCurve,
+    symbol: &str,
+    bump: Option<u8>,
+    payer: Pubkey,
+) -> Result<Option<SymbolCollisionEvent>> {
+    let Some(record) = symbol_record else {
+        require!(config.symbol_policy == SymbolPolicy::Off, ClawdVaultError::SymbolRecordRequired);
+        return Ok(None);
+    };
+    let symbol = registry_symbol(symbol)?;
+    
+    if record.symbol.is_empty() {
+        record.symbol = symbol;
+        record.mint = bonding_curve.mint;
+        record.payer = payer;
+        record.status = SymbolStatus::Claimed;
+        record.bump = bump.unwrap_or_default();
+        return Ok(None);
+    }
+    
+    if !symbol_collides(config.symbol_policy, record, bonding_curve.mint)? {
+        return Ok(None);
+    }
+    
+    bonding_curve.flags |= FLAG_IMPERSONATION;
+    log!("⚠️ Symbol {} collides with a {:?} ticker", record.symbol, record.status);
+    
+    Ok(Some(SymbolCollisionEvent {
+        mint: bonding_curve.mint,
+        symbol: record.symbol.clone(),
+        registered_mint: record.mint,
+        registered_status: record.status,
+        timestamp: bonding_curve.created_at,
+    }))
+}
+
+/// Whether `mint` taking an already registered ticker is a collision; fails under `SymbolPolicy::Reject`
+/// A record held by `mint` itself (its own claim, or the ticker verified or reserved for it) never is
+fn symbol_collides(policy: SymbolPolicy, record: &SymbolRecord, mint: Pubkey) -> Result<bool> {
+    if record.mint == mint {
+        return Ok(false);
+    }
+    if record.status == SymbolStatus::Claimed || policy == SymbolPolicy::Off {
+        return Ok(false);
+    }
+    require!(policy != SymbolPolicy::Reject, ClawdVaultError::SymbolReserved);
+    Ok(true)
+}
+
+/// Transfer-fee extension of a Token-2022 mint, if it has one
+fn transfer_fee_config(mint: &AccountInfo) -> Result<Option<TransferFeeConfig>> {
+    if *mint.owner != Token2022::id() {
+        return Ok(None);
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    Ok(state.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+/// Token-2022 transfer fee withheld when `amount` is sent (0 for mints without one)
+fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let Some(config) = transfer_fee_config(mint)? else {
+        return Ok(0);
+    };
+    config
+        .calculate_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or_else(|| ClawdVaultError::MathOverflow.into())
+}
+
+/// Amount to send so that at least `net` arrives after the Token-2022 transfer fee
+fn amount_with_fee(mint: &AccountInfo, net: u64) -> Result<u64> {
+    let Some(config) = transfer_fee_config(mint)? else {
+        return Ok(net);
+    };
+    config
+        .get_epoch_fee(Clock::get()?.epoch)
+        .calculate_pre_fee_amount(net)
+        .ok_or_else(|| ClawdVaultError::MathOverflow.into())
+}
+
+/// Mint PDA of `create_token_pda` / `create_token_2022_pda`
+fn creator_mint_pda(creator: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_SEED, creator.as_ref(), &nonce.to_le_bytes()], &crate::ID)
+}
+
+/// Create an account of `len` bytes owned by `owner`, funded for `funded_len` bytes
+/// (Token-2022 mints grow on initialize). `signer` signs for a PDA
+fn create_owned_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    signer: &[&[&[u8]]],
+    funded_len: usize,
+    len: usize,
+    owner: &Pubkey,
+) -> Result<()> {
+    if account.lamports() == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer,
+            ),
+            Rent::get()?.minimum_balance(funded_len),
+            len as u64,
+            owner,
+        )?;
+    } else {
+        // Someone sent lamports to the (predictable) address first - take it over like `init` would
+        fund_rent_exemption(payer, account, system_program, funded_len)?;
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::Allocate {
+                    account_to_allocate: account.clone(),
+                },
+                signer,
+            ),
+            len as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::Assign {
+                    account_to_assign: account.clone(),
+                },
+                signer,
+            ),
+            owner,
+        )?;
+    }
+    Ok(())
+}
+
+/// Shared body of `create_token_2022` and `create_token_2022_pda`: create the Token-2022 mint
+/// with its extensions and in-mint metadata, and the curve. `mint_seeds` signs for a PDA mint;
+/// without it the mint must be a keypair signing the transaction.
+#[allow(clippy::too_many_arguments)]
+fn create_2022_token(
+    ctx: Context<CreateToken2022>,
+    mint_seeds: Option<&[&[u8]]>,
+    name: String,
+    symbol: String,
+    uri: String,
+    initial_buy_lamports: u64,
+    graduation_deadline: i64,
+    transfer_fee_bps: u16,
+    profile: Option<TokenProfileArgs>,
+) -> Result<()> {
+    validate_token_metadata(&ctx.accounts.config, &name, &symbol, &uri)?;
+    require!(
+        transfer_fee_bps <= ctx.accounts.config.max_transfer_fee_bps,
+        ClawdVaultError::TransferFeeTooHigh
+    );
+
+    let bump = ctx.bumps.bonding_curve;
+    let sol_vault_bump = ctx.bumps.sol_vault;
+    let mint_key = ctx.accounts.mint.key();
+    let creator_key = ctx.accounts.creator.key();
+    let curve_key = ctx.accounts.bonding_curve.key();
+    
+    let seeds = &[
+        CURVE_SEED,
+        mint_key.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let curve_info = ctx.accounts.bonding_curve.to_account_info();
+    let token_program_info = ctx.accounts.token_program.to_account_info();
+    
+    // The mint points at itself for metadata (and group membership, once the protocol
+    // group exists); the token program appends both on initialize, so the account is
+    // funded for them up front
+    let join_group = ctx.accounts.token_group.owner == &Token2022::id();
+    let mut extensions = vec![ExtensionType::MetadataPointer];
+    if join_group {
+        extensions.push(ExtensionType::GroupMemberPointer);
+    }
+    if transfer_fee_bps > 0 {
+        extensions.push(ExtensionType::TransferFeeConfig);
+    }
+    let mint_len = ExtensionType::try_calculate_account_len::<MintState>(&extensions)?;
+    if join_group {
+        extensions.push(ExtensionType::TokenGroupMember);
+    }
+    let funded_len = ExtensionType::try_calculate_account_len::<MintState>(&extensions)?;
+    let metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(curve_key))?,
+        mint: mint_key,
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        additional_metadata: vec![],
+    };
+    let mint_signer: &[&[&[u8]]] = match &mint_seeds {
+        Some(seeds) => std::slice::from_ref(seeds),
+        None => &[],
+    };
+    create_owned_account(
+        &ctx.accounts.payer.to_account_info(),
+        &mint_info,
+        &ctx.accounts.system_program,
+        mint_signer,
+        funded_len + metadata.tlv_size_of()?,
+        mint_len,
+        &Token2022::id(),
+    )?;
+    
+    token_interface::metadata_pointer_initialize(
+        CpiContext::new(
+            token_program_info.clone(),
+            MetadataPointerInitialize {
+                token_program_id: token_program_info.clone(),
+                mint: mint_info.clone(),
+            },
+        ),
+        Some(curve_key),
+        Some(mint_key),
+    )?;
+    
+    if join_group {
+        token_interface::group_member_pointer_initialize(
+            CpiContext::new(
+                token_program_info.clone(),
+                GroupMemberPointerInitialize {
+                    token_program_id: token_program_info.clone(),
+                    mint: mint_info.clone(),
+                },
+            ),
+            Some(curve_key),
+            Some(mint_key),
+        )?;
+    }
+    
+    if transfer_fee_bps > 0 {
+        // No config authority, so the fee can never change; withheld fees are
+        // withdrawn by a PDA only the creator can claim through (`claim_transfer_fees`)
+        let (fee_authority, _) = Pubkey::find_program_address(
+            &[TRANSFER_FEE_SEED, mint_key.as_ref()],
+            &crate::ID,
+        );
+        token_interface::transfer_fee_initialize(
+            CpiContext::new(
+                token_program_info.clone(),
+                TransferFeeInitialize {
+                    token_program_id: token_program_info.clone(),
+                    mint: mint_info.clone(),
+                },
+            ),
+            None,
+            Some(&fee_authority),
+            transfer_fee_bps,
+            u64::MAX,
+        )?;
+    }
+    
+    // No freeze authority - removes the scary wallet warning
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            token_program_info.clone(),
+            InitializeMint2 {
+                mint: mint_info.clone(),
+            },
+        ),
+        TOKEN_DECIMALS,
+        &curve_key,
+        None,
+    )?;
+    
+    token_interface::token_metadata_initialize(
+        CpiContext::new_with_signer(
+            token_program_info.clone(),
+            TokenMetadataInitialize {
+                program_id: token_program_info.clone(),
+                metadata: mint_info.clone(),
+                update_authority: curve_info.clone(),
+                mint_authority: curve_info.clone(),
+                mint: mint_info.clone(),
+            },
+            signer_seeds,
+        ),
+        name.clone(),
+        symbol.clone(),
+        uri.clone(),
+    )?;
+    
+    // Membership is the Token-2022 counterpart of the verified collection: the group's
+    // update authority (the config PDA) must sign, so only ClawdVault launches can join
+    if join_group {
+        let config_seeds = &[b"config".as_ref(), &[ctx.accounts.config.bump]];
+        token_interface::token_member_initialize(
+            CpiContext::new_with_signer(
+                token_program_info.clone(),
+                TokenMemberInitialize {
+                    program_id: token_program_info.clone(),
+                    member: mint_info.clone(),
+                    member_mint: mint_info.clone(),
+                    member_mint_authority: curve_info.clone(),
+                    group: ctx.accounts.token_group.to_account_info(),
+                    group_update_authority: ctx.accounts.config.to_account_info(),
+                },
+                &[&seeds[..], &config_seeds[..]],
+            ),
+        )?;
+    }
+    
+    associated_token::create(
+        CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.payer.to_account_info(),
+                associated_token: ctx.accounts.token_vault.to_account_info(),
+                authority: curve_info.clone(),
+                mint: mint_info.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: token_program_info.clone(),
+            },
+        ),
+    )?;
+    
+    associated_token::create_idempotent(
+        CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.payer.to_account_info(),
+                associated_token: ctx.accounts.creator_token_account.to_account_info(),
+                authority: ctx.accounts.creator.to_account_info(),
+                mint: mint_info.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: token_program_info.clone(),
+            },
+        ),
+    )?;
+    
+    let initial_buy = launch_curve(
+        LaunchCurveAccounts {
+            config: &mut ctx.accounts.config,
+            bonding_curve: &mut ctx.accounts.bonding_curve,
+            creator: &ctx.accounts.creator.to_account_info(),
+            mint: &mint_info,
+            sol_vault: &ctx.accounts.sol_vault.to_account_info(),
+            token_vault: &ctx.accounts.token_vault.to_account_info(),
+            creator_token_account: &ctx.accounts.creator_token_account.to_account_info(),
+            token_program: &token_program_info,
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+        bump,
+        sol_vault_bump,
+        initial_buy_lamports,
+        graduation_deadline,
+    )?;
+    
+    let symbol_collision = claim_symbol(
+        &ctx.accounts.config,
+        ctx.accounts.symbol_record.as_mut(),
+        &mut ctx.accounts.bonding_curve,
+        &symbol,
+        ctx.bumps.symbol_record,
+        ctx.accounts.payer.key(),
+    )?;
+    
+    let profile_updated = match (profile, ctx.accounts.token_profile.as_mut()) {
+        (Some(args), Some(token_profile)) => Some(write_token_profile(
+            token_profile,
+            mint_key,
+            creator_key,
+            args,
+            ctx.bumps.token_profile.unwrap_or_default(),
+        )?),
+        (None, None) => None,
+        _ => return err!(ClawdVaultError::InvalidProfileAccount),
+    };
+    
+    log!("🐺 Token-2022 token created: {} ({})", name, symbol);
+    log!("Mint: {}", mint_key);
+    log!("Creator: {}", creator_key);
+    
+    emit_cpi!(TokenCreatedEvent {
+        mint: mint_key,
+        creator: creator_key,
+        name,
+        symbol,
+        uri,
+        graduation_deadline,
+        transfer_fee_bps,
+        timestamp: ctx.accounts.bonding_curve.created_at,
+    });
+    
+    if let Some(collision) = symbol_collision {
+        emit_cpi!(collision);
+    }
+    
+    if let Some(profile_updated) = profile_updated {
+        emit_cpi!(profile_updated);
+    }
+    
+    if let Some(trade) = initial_buy {
+        emit_cpi!(trade);
+    }
+    
+    Ok(())
+}
+
+/// Shared body of `create_token` and `create_token_pda`: create the legacy-token mint,
+/// its Metaplex metadata and the curve. `mint_seeds` signs for a PDA mint; without it
+/// the mint must be a keypair signing the transaction.
+#[allow(clippy::too_many_arguments)]
+fn create_legacy_token(
+    ctx: Context<CreateToken>,
+    mint_seeds: Option<&[&[u8]]>,
+    name: String,
+    symbol: String,
+    uri: String,
+    initial_buy_lamports: u64,
+    graduation_deadline: i64,
+    profile: Option<TokenProfileArgs>,
+) -> Result<()> {
+    validate_token_metadata(&ctx.accounts.config, &name, &symbol, &uri)?;
+
+    // Capture values before mutable borrow
+    let bump = ctx.bumps.bonding_curve;
+    let sol_vault_bump = ctx.bumps.sol_vault;
+    let mint_key = ctx.accounts.mint.key();
+    let creator_key = ctx.accounts.creator.key();
+    
+    // Build signer seeds for bonding curve PDA
+    let seeds = &[
+        CURVE_SEED,
+        mint_key.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let token_program_info = ctx.accounts.token_program.to_account_info();
+    let mint_len = ExtensionType::try_calculate_account_len::<MintState>(&[])?;
+    let mint_signer: &[&[&[u8]]] = match &mint_seeds {
+        Some(seeds) => std::slice::from_ref(seeds),
+        None => &[],
+    };
+    create_owned_account(
+        &ctx.accounts.payer.to_account_info(),
+        &mint_info,
+        &ctx.accounts.system_program,
+        mint_signer,
+        mint_len,
+        mint_len,
+        &Token::id(),
+    )?;
+    
+    // No freeze authority - removes the scary wallet warning
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            token_program_info.clone(),
+            InitializeMint2 {
+                mint: mint_info.clone(),
+            },
+        ),
+        TOKEN_DECIMALS,
+        &ctx.accounts.bonding_curve.key(),
+        None,
+    )?;
+    
+    associated_token::create(
+        CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.payer.to_account_info(),
+                associated_token: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.bonding_curve.to_account_info(),
+                mint: mint_info.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: token_program_info.clone(),
+            },
+        ),
+    )?;
+    
+    associated_token::create_idempotent(
+        CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.payer.to_account_info(),
+                associated_token: ctx.accounts.creator_token_account.to_account_info(),
+                authority: ctx.accounts.creator.to_account_info(),
+                mint: mint_info.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: token_program_info.clone(),
+            },
+        ),
+    )?;
+    
+    create_metaplex_metadata(
+        MetaplexMetadataAccounts {
+            config: &ctx.accounts.config,
+            bonding_curve: &ctx.accounts.bonding_curve.to_account_info(),
+            creator: &ctx.accounts.creator.to_account_info(),
+            payer: &ctx.accounts.payer.to_account_info(),
+            mint: &mint_info,
+            metadata: &ctx.accounts.metadata.to_account_info(),
+            collection_mint: ctx.accounts.collection_mint.as_ref().map(|a| a.to_account_info()),
+            collection_metadata: ctx.accounts.collection_metadata.as_ref().map(|a| a.to_account_info()),
+            collection_master_edition: ctx.accounts.collection_master_edition.as_ref().map(|a| a.to_account_info()),
+            metadata_program: &ctx.accounts.metadata_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            rent: &ctx.accounts.rent.to_account_info(),
+        },
+        signer_seeds,
+        &name,
+        &symbol,
+        &uri,
+    )?;
+    
+    let initial_buy = launch_curve(
+        LaunchCurveAccounts {
+            config: &mut ctx.accounts.config,
+            bonding_curve: &mut ctx.accounts.bonding_curve,
+            creator: &ctx.accounts.creator.to_account_info(),
+            mint: &mint_info,
+            sol_vault: &ctx.accounts.sol_vault.to_account_info(),
+            token_vault: &ctx.accounts.token_vault.to_account_info(),
+            creator_token_account: &ctx.accounts.creator_token_account.to_account_info(),
+            token_program: &token_program_info,
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+        bump,
+        sol_vault_bump,
+        initial_buy_lamports,
+        graduation_deadline,
+    )?;
+    
+    let symbol_collision = claim_symbol(
+        &ctx.accounts.config,
+        ctx.accounts.symbol_record.as_mut(),
+        &mut ctx.accounts.bonding_curve,
+        &symbol,
+        ctx.bumps.symbol_record,
+        ctx.accounts.payer.key(),
+    )?;
+    
+    let profile_updated = match (profile, ctx.accounts.token_profile.as_mut()) {
+        (Some(args), Some(token_profile)) => Some(write_token_profile(
+            token_profile,
+            mint_key,
+            creator_key,
+            args,
+            ctx.bumps.token_profile.unwrap_or_default(),
+        )?),
+        (None, None) => None,
+        _ => return err!(ClawdVaultError::InvalidProfileAccount),
+    };
+    
+    log!("🐺 Token created: {} ({})", name, symbol);
+    log!("Mint: {}", mint_key);
+    log!("Creator: {}", creator_key);
+    
+    emit_cpi!(TokenCreatedEvent {
+        mint: mint_key,
+        creator: creator_key,
+        name,
+        symbol,
+        uri,
+        graduation_deadline,
+        transfer_fee_bps: 0,
+        timestamp: ctx.accounts.bonding_curve.created_at,
+    });
+    
+    if let Some(collision) = symbol_collision {
+        emit_cpi!(collision);
+    }
+    
+    if let Some(profile_updated) = profile_updated {
+        emit_cpi!(profile_updated);
+    }
+    
+    // Emit TradeEvent for initial buy (so sync-trades catches it!)
+    if let Some(trade) = initial_buy {
+        emit_cpi!(trade);
+    }
+    
+    Ok(())
+}
+
+/// Accounts `create_metaplex_metadata` writes and verifies against
+struct MetaplexMetadataAccounts<'a, 'info> {
+    config: &'a Account<'info, Config>,
+    bonding_curve: &'a AccountInfo<'info>,
+    creator: &'a AccountInfo<'info>,
+    payer: &'a AccountInfo<'info>,
+    mint: &'a AccountInfo<'info>,
+    metadata: &'a AccountInfo<'info>,
+    collection_mint: Option<AccountInfo<'info>>,
+    collection_metadata: Option<AccountInfo<'info>>,
+    collection_master_edition: Option<AccountInfo<'info>>,
+    metadata_program: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+    rent: &'a AccountInfo<'info>,
+}
+
+/// Create a legacy token's Metaplex metadata with the creator verified
+/// and, once the protocol collection exists, verify it into the collection
+fn create_metaplex_metadata(
+    accounts: MetaplexMetadataAccounts,
+    curve_signer: &[&[&[u8]]],
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Result<()> {
+    let collection_mint = accounts.config.collection_mint;
+    let collection = (collection_mint != Pubkey::default()).then_some(Collection {
+        verified: false,
+        key: collection_mint,
+    });
+    
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            accounts.metadata_program.clone(),
+            CreateMetadataAccountsV3 {
+                metadata: accounts.metadata.clone(),
+                mint: accounts.mint.clone(),
+                mint_authority: accounts.bonding_curve.clone(),
+                payer: accounts.payer.clone(),
+                update_authority: accounts.bonding_curve.clone(),
+                system_program: accounts.system_program.clone(),
+                rent: accounts.rent.clone(),
+            },
+            curve_signer,
+        ),
+        DataV2 {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+            seller_fee_basis_points: 0,
+            creators: Some(vec![Creator {
+                address: accounts.creator.key(),
+                verified: false,  // Verified by the creator's signature below
+                share: 100,
+            }]),
+            collection,
+            uses: None,
+        },
+        true,  // is_mutable
+        true,  // update_authority_is_signer
+        None,  // collection_details
+    )?;
+    
+    sign_metadata(CpiContext::new(
+        accounts.metadata_program.clone(),
+        SignMetadata {
+            creator: accounts.creator.clone(),
+            metadata: accounts.metadata.clone(),
+        },
+    ))?;
+    
+    if collection_mint != Pubkey::default() {
+        let (Some(collection_mint), Some(collection_metadata), Some(collection_master_edition)) = (
+            accounts.collection_mint,
+            accounts.collection_metadata,
+            accounts.collection_master_edition,
+        ) else {
+            return err!(ClawdVaultError::CollectionAccountsMissing);
+        };
+        let config_seeds = &[b"config".as_ref(), &[accounts.config.bump]];
+        verify_sized_collection_item(
+            CpiContext::new_with_signer(
+                accounts.metadata_program.clone(),
+                VerifySizedCollectionItem {
+                    payer: accounts.payer.clone(),
+                    metadata: accounts.metadata.clone(),
+                    collection_authority: accounts.config.to_account_info(),
+                    collection_mint,
+                    collection_metadata,
+                    collection_master_edition,
+                },
+                &[&config_seeds[..]],
+            ),
+            None,
+        )?;
+    }
+    
+    Ok(())
+}
+
+/// Accounts `launch_curve` mints into and trades against
+struct LaunchCurveAccounts<'a, 'info> {
+    config: &'a mut Account<'info, Config>,
+    bonding_curve: &'a mut Account<'info, BondingCurve>,
+    creator: &'a AccountInfo<'info>,
+    mint: &'a AccountInfo<'info>,
+    sol_vault: &'a AccountInfo<'info>,
+    token_vault: &'a AccountInfo<'info>,
+    creator_token_account: &'a AccountInfo<'info>,
+    token_program: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+}
+
+/// Mint the fixed supply into the curve, revoke the mint authority and initialize the curve
+/// (shared by both token programs' create instructions, after their metadata is set up)
+/// Returns the initial buy's trade, if any
+fn launch_curve(
+    accounts: LaunchCurveAccounts,
+    bump: u8,
+    sol_vault_bump: u8,
+    initial_buy_lamports: u64,
+    graduation_deadline: i64,
+) -> Result<Option<TradeEvent>> {
+    let created_at = Clock::get()?.unix_timestamp;
+    require!(
+        graduation_deadline == 0 || graduation_deadline > created_at,
+        ClawdVaultError::InvalidGraduationDeadline
+    );
+    
+    let mint_key = accounts.mint.key();
+    let creator_key = accounts.creator.key();
+    let seeds = &[
+        CURVE_SEED,
+        mint_key.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    // Mint total supply to token vault (needs bonding_curve as signer)
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            MintTo {
+                mint: accounts.mint.clone(),
+                to: accounts.token_vault.clone(),
+                authority: accounts.bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        TOTAL_SUPPLY,
+    )?;
+    
+    // Supply is fixed: nobody can mint again
+    token_interface::set_authority(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            SetAuthority {
+                current_authority: accounts.bonding_curve.to_account_info(),
+                account_or_mint: accounts.mint.clone(),
+            },
+            signer_seeds,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+    
+    // Initialize bonding curve state
+    let curve = &mut *accounts.bonding_curve;
+    curve.creator = creator_key;
+    curve.mint = mint_key;
+    curve.virtual_sol_reserves = INITIAL_VIRTUAL_SOL;
+    curve.virtual_token_reserves = INITIAL_VIRTUAL_TOKENS;
+    curve.real_sol_reserves = 0;
+    curve.real_token_reserves = TOTAL_SUPPLY;
+    curve.token_total_supply = TOTAL_SUPPLY;
+    curve.graduated = false;
+    curve.migrated = false;
+    curve.status = CurveStatus::Trading;
+    curve.created_at = created_at;
+    curve.bump = bump;
+    curve.sol_vault_bump = sol_vault_bump;
+    curve.graduation_deadline = graduation_deadline;
+    curve.token_program = accounts.token_program.key();
+    
+    // Update protocol stats
+    let config = &mut *accounts.config;
+    config.total_tokens_created = config.total_tokens_created.checked_add(1)
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    
+    if initial_buy_lamports == 0 {
+        return Ok(None);
+    }
+    
+    // Calculate tokens out using bonding curve math
+    let sol_after_fee = initial_buy_lamports
+        .checked_mul(BPS_DENOMINATOR - TOTAL_FEE_BPS)
+        .ok_or(ClawdVaultError::MathOverflow)?
+        .checked_div(BPS_DENOMINATOR)
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    
+    let new_virtual_sol = INITIAL_VIRTUAL_SOL
+        .checked_add(sol_after_fee)
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    
+    let invariant = (INITIAL_VIRTUAL_SOL as u128)
+        .checked_mul(INITIAL_VIRTUAL_TOKENS as u128)
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    
+    let new_virtual_tokens = invariant
+        .checked_div(new_virtual_sol as u128)
+        .ok_or(ClawdVaultError::MathOverflow)? as u64;
+    
+    let tokens_out = INITIAL_VIRTUAL_TOKENS
+        .checked_sub(new_virtual_tokens)
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    
+    // Transfer SOL from creator to sol_vault
+    system_program::transfer(
+        CpiContext::new(
+            accounts.system_program.clone(),
+            system_program::Transfer {
+                from: accounts.creator.clone(),
+                to: accounts.sol_vault.clone(),
+            },
+        ),
+        initial_buy_lamports,
+    )?;
+    
+    // Transfer tokens from vault to creator's token account
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            TransferChecked {
+                from: accounts.token_vault.clone(),
+                mint: accounts.mint.clone(),
+                to: accounts.creator_token_account.clone(),
+                authority: accounts.bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        tokens_out,
+        TOKEN_DECIMALS,
+    )?;
+    
+    // Update curve state
+    let curve = &mut *accounts.bonding_curve;
+    curve.virtual_sol_reserves = new_virtual_sol;
+    curve.virtual_token_reserves = new_virtual_tokens;
+    curve.real_sol_reserves = initial_buy_lamports;
+    curve.real_token_reserves = TOTAL_SUPPLY
+        .checked_sub(tokens_out)
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    
+    // Calculate fees (for logging)
+    let total_fee = initial_buy_lamports
+        .checked_mul(TOTAL_FEE_BPS)
+        .ok_or(ClawdVaultError::MathOverflow)?
+        .checked_div(BPS_DENOMINATOR)
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    
+    log!("🎯 Initial buy: {} lamports -> {} tokens (fee: {} lamports)", 
+        initial_buy_lamports, tokens_out, total_fee);
+    log!("Initial price: {} lamports/token", 
+        curve.virtual_sol_reserves / (curve.virtual_token_reserves / 1_000_000));
+    
+    let protocol_fee = total_fee / 2;
+    let creator_fee = total_fee - protocol_fee; // Remainder to avoid rounding loss
+    
+    Ok(Some(TradeEvent {
+        mint: mint_key,
+        trader: creator_key,
+        is_buy: true,
+        sol_amount: initial_buy_lamports,
+        token_amount: tokens_out,
+        protocol_fee,
+        creator_fee,
+        virtual_sol_reserves: new_virtual_sol,
+        virtual_token_reserves: new_virtual_tokens,
+        timestamp: created_at,
+    }))
+}
+
+/// Keys `finalize_migration` and `record_migration` check against an AMM pool account
+struct PoolKeys {
+    mint_0: Pubkey,
+    mint_1: Pubkey,
+    vault_0: Pubkey,
+    vault_1: Pubkey,
+    lp_mint: Pubkey,
+}
+
+impl PoolKeys {
+    /// The pool's vault for `mint`, if the pool trades it
+    fn vault_for(&self, mint: &Pubkey) -> Option<Pubkey> {
+        if *mint == self.mint_0 {
+            Some(self.vault_0)
+        } else if *mint == self.mint_1 {
+            Some(self.vault_1)
+        } else {
+            None
+        }
+    }
+}
+
+/// Decode a pool account in the layout of `dex`
+/// Only DEXes whose pools the program can verify are supported
+fn decode_pool(dex: MigrationDex, pool: &AccountInfo) -> Result<PoolKeys> {
+    match dex {
+        MigrationDex::RaydiumCpmm => {
+            // PoolState: discriminator, amm_config, pool_creator, token_0_vault, token_1_vault,
+            // lp_mint, token_0_mint, token_1_mint, ...
+            let data = pool.try_borrow_data()?;
+            require!(
+                RAYDIUM_CPMM_PROGRAMS.contains(pool.owner)
+                    && data.len() >= 8 + 7 * 32
+                    && data[..8] == RAYDIUM_CPMM_POOL_DISCRIMINATOR,
+                ClawdVaultError::InvalidMigrationPool
+            );
+            let key = |index: usize| {
+                let offset = 8 + index * 32;
+                Pubkey::try_from(&data[offset..offset + 32])
+                    .map_err(|_| error!(ClawdVaultError::InvalidMigrationPool))
+            };
+            Ok(PoolKeys {
+                vault_0: key(2)?,
+                vault_1: key(3)?,
+                lp_mint: key(4)?,
+                mint_0: key(5)?,
+                mint_1: key(6)?,
+            })
+        }
+        MigrationDex::ClawdVault => err!(ClawdVaultError::UnsupportedMigrationDex),
+    }
+}
+
+/// Accounts `release_curve` moves assets between
+struct ReleaseCurveAccounts<'a, 'info> {
+    config: &'a Config,
+    bonding_curve: &'a mut Account<'info, BondingCurve>,
+    token_mint: &'a InterfaceAccount<'info, Mint>,
+    sol_vault: &'a AccountInfo<'info>,
+    token_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    migration_escrow: &'a AccountInfo<'info>,
+    escrow_token_account: &'a AccountInfo<'info>,
+    fee_recipient: &'a AccountInfo<'info>,
+    creator: &'a AccountInfo<'info>,
+    excess_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Move a graduated curve's assets into its migration escrow (shared by single and batch release)
+/// Only the tokens matching the final curve price are escrowed; the protocol fee and
+/// creator bonus come out of the raised SOL first
+fn release_curve(accounts: ReleaseCurveAccounts, dex: MigrationDex) -> Result<MigrationReleasedEvent> {
+    // finalize_migration has to be able to verify the pool, or the escrow can only time out
+    require!(dex == MigrationDex::RaydiumCpmm, ClawdVaultError::UnsupportedMigrationDex);
+    
+    let (migration_fee, graduation_bonus, _) = pay_migration_fees(
+        accounts.config,
+        accounts.bonding_curve,
+        accounts.sol_vault,
+        accounts.fee_recipient,
+        accounts.creator,
+        None,
+    )?;
+    let curve = &*accounts.bonding_curve;
+    let mint_key = curve.mint;
+    let bump = curve.bump;
+    let sol_amount = curve.real_sol_reserves
+        .checked_sub(migration_fee)
+        .and_then(|sol| sol.checked_sub(graduation_bonus))
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    let token_amount = curve.listing_token_amount(sol_amount)?;
+    let excess_tokens = curve.real_token_reserves
+        .checked_sub(token_amount)
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    
+    log!("🚀 Releasing assets for {:?} migration...", dex);
+    log!("SOL to escrow: {} lamports", sol_amount);
+    log!("Tokens to escrow: {}", token_amount);
+    
+    // Build signer seeds for bonding curve PDA
+    let curve_seeds = &[
+        CURVE_SEED,
+        mint_key.as_ref(),
+        &[bump],
+    ];
+    let curve_signer = &[&curve_seeds[..]];
+    
+    // Transfer SOL from vault to migration escrow
+    if sol_amount > 0 {
+        **accounts.sol_vault.try_borrow_mut_lamports()? -= sol_amount;
+        **accounts.migration_escrow.try_borrow_mut_lamports()? += sol_amount;
+        
+        log!("✅ Transferred {} SOL to migration escrow", sol_amount);
+    }
+    
+    // Transfer tokens from vault to migration escrow's token account
+    if token_amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.token_vault.to_account_info(),
+                    mint: accounts.token_mint.to_account_info(),
+                    to: accounts.escrow_token_account.clone(),
+                    authority: accounts.bonding_curve.to_account_info(),
+                },
+                curve_signer,
+            ),
+            token_amount,
+            accounts.token_mint.decimals,
+        )?;
+        
+        log!("✅ Transferred {} tokens to migration escrow", token_amount);
+    }
+    let escrowed_tokens = token_amount
+        .checked_sub(transfer_fee(&accounts.token_mint.to_account_info(), token_amount)?)
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    
+    dispose_excess_tokens(
+        accounts.config,
+        accounts.token_program,
+        accounts.token_mint,
+        accounts.token_vault,
+        accounts.bonding_curve.to_account_info(),
+        accounts.excess_token_account,
+        curve_signer,
+        excess_tokens,
+    )?;
+    
+    let timestamp = Clock::get()?.unix_timestamp;
+    
+    // Mark as migrating until the pool is finalized (or the escrow refunded)
+    let curve = &mut *accounts.bonding_curve;
+    curve.transition(CurveStatus::Migrating)?;
+    curve.migration_dex = Some(dex);
+    curve.migration_started_at = timestamp;
+    curve.migration_sol_amount = sol_amount;
+    curve.migration_token_amount = escrowed_tokens;
+    curve.migration_wallet = Pubkey::default();
+    curve.real_sol_reserves = 0;
+    curve.real_token_reserves = 0;
+    
+    log!("🎓 Assets escrowed for migration!");
+    
+    Ok(MigrationReleasedEvent {
+        mint: mint_key,
+        sol_amount,
+        token_amount,
+        escrow: accounts.migration_escrow.key(),
+        dex,
+        excess_tokens,
+        excess_token_destination: accounts.config.excess_token_destination,
+        migration_fee,
+        graduation_bonus,
+        timestamp,
+    })
+}
+
+/// A `release_for_migration_batch` group that passed `check_batch_group`
+struct CheckedBatchGroup<'info> {
+    bonding_curve: Account<'info, BondingCurve>,
+    token_mint: InterfaceAccount<'info, Mint>,
+    token_vault: InterfaceAccount<'info, TokenAccount>,
+    excess_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    sol_vault: &'info AccountInfo<'info>,
+    escrow: &'info AccountInfo<'info>,
+    escrow_bump: u8,
+    escrow_token_account: &'info AccountInfo<'info>,
+    creator: &'info AccountInfo<'info>,
+}
+
+/// Validate one `release_for_migration_batch` group without moving anything
+/// Covers everything that can fail for a single curve, so the release that follows can't
+fn check_batch_group<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, ReleaseForMigrationBatch<'info>>,
+    group: &'info [AccountInfo<'info>],
+    dex: MigrationDex,
+) -> Result<CheckedBatchGroup<'info>> {
+    let [curve_info, mint_info, sol_vault, token_vault_info, escrow, escrow_token_info, creator, excess_info] = group else {
+        return err!(ClawdVaultError::InvalidBatch);
+    };
+    let config = &ctx.accounts.config;
+    
+    let bonding_curve = Account::<BondingCurve>::try_from(curve_info)?;
+    let token_mint = InterfaceAccount::<Mint>::try_from(mint_info)?;
+    let token_vault = InterfaceAccount::<TokenAccount>::try_from(token_vault_info)?;
+    let escrow_token_account = InterfaceAccount::<TokenAccount>::try_from(escrow_token_info)?;
+    let excess_token_account = if excess_info.key() == crate::ID {
+        None
+    } else {
+        Some(InterfaceAccount::<TokenAccount>::try_from(excess_info)?)
+    };
+    
+    let mint = token_mint.key();
+    let (escrow_key, escrow_bump) = Pubkey::find_program_address(&[MIGRATION_ESCROW_SEED, mint.as_ref()], &crate::ID);
+    require!(
+        bonding_curve.mint == mint
+            && curve_info.key() == Pubkey::create_program_address(&[CURVE_SEED, mint.as_ref(), &[bonding_curve.bump]], &crate::ID)
+                .map_err(|_| ClawdVaultError::InvalidAccountData)?
+            && sol_vault.key() == Pubkey::create_program_address(&[VAULT_SEED, mint.as_ref(), &[bonding_curve.sol_vault_bump]], &crate::ID)
+                .map_err(|_| ClawdVaultError::InvalidAccountData)?
+            && bonding_curve.token_program == ctx.accounts.token_program.key()
+            && token_vault.key() == get_associated_token_address_with_program_id(&curve_info.key(), &mint, &bonding_curve.token_program)
+            && escrow.key() == escrow_key
+            && escrow_token_account.key() == get_associated_token_address_with_program_id(&escrow_key, &mint, &bonding_curve.token_program)
+            && creator.key() == bonding_curve.creator,
+        ClawdVaultError::InvalidAccountData
+    );
+    bonding_curve.require_status(CurveStatus::Graduated)?;
+    
+    // Checks release_curve would otherwise hit after assets start moving
+    require!(dex == MigrationDex::RaydiumCpmm, ClawdVaultError::UnsupportedMigrationDex);
+    if !bonding_curve.migration_fees_paid {
+        migration_fees(config, bonding_curve.real_sol_reserves)?;
+    }
+    if config.excess_token_destination != Pubkey::default() {
+        require!(
+            excess_token_account.as_ref().is_some_and(|account| {
+                account.owner == config.excess_token_destination && account.mint == mint
+            }),
+            ClawdVaultError::InvalidExcessTokenAccount
+        );
+    }
+    
+    Ok(CheckedBatchGroup {
+        bonding_curve,
+        token_mint,
+        token_vault,
+        excess_token_account,
+        sol_vault,
+        escrow,
+        escrow_bump,
+        escrow_token_account: escrow_token_info,
+        creator,
+    })
+}
+
+/// Release a group checked by `check_batch_group`; errors here abort the batch
+fn release_batch_group<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, ReleaseForMigrationBatch<'info>>,
+    checked: CheckedBatchGroup<'info>,
+    dex: MigrationDex,
+) -> Result<MigrationReleasedEvent> {
+    let CheckedBatchGroup {
+        mut bonding_curve,
+        token_mint,
+        token_vault,
+        excess_token_account,
+        sol_vault,
+        escrow,
+        escrow_bump,
+        escrow_token_account,
+        creator,
+    } = checked;
+    let mint = token_mint.key();
+    
+    // Create the SOL escrow on first use (release_for_migration does this via init_if_needed)
+    if escrow.lamports() == 0 {
+        let rent = Rent::get()?.minimum_balance(0);
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: ctx.accounts.operator.to_account_info(),
+                    to: escrow.clone(),
+                },
+                &[&[MIGRATION_ESCROW_SEED, mint.as_ref(), &[escrow_bump]]],
+            ),
+            rent,
+            0,
+            &crate::ID,
+        )?;
+    }
+    
+    let event = release_curve(
+        ReleaseCurveAccounts {
+            config: &ctx.accounts.config,
+            bonding_curve: &mut bonding_curve,
+            token_mint: &token_mint,
+            sol_vault,
+            token_vault: &token_vault,
+            migration_escrow: escrow,
+            escrow_token_account,
+            fee_recipient: &ctx.accounts.fee_recipient.to_account_info(),
+            creator,
+            excess_token_account: excess_token_account.as_ref(),
+            token_program: &ctx.accounts.token_program,
+        },
+        dex,
+    )?;
+    bonding_curve.exit(&crate::ID)?;
+    
+    Ok(event)
+}
+
+/// Protocol migration fee and creator graduation bonus for a curve with `sol_reserves`
+/// Returns (migration_fee, graduation_bonus)
+fn migration_fees(config: &Config, sol_reserves: u64) -> Result<(u64, u64)> {
+    let migration_fee = mul_div(sol_reserves, config.migration_fee_bps as u64, BPS_DENOMINATOR)?
+        .checked_add(config.migration_fee_lamports)
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    let graduation_bonus = config.graduation_bonus;
+    
+    // Leave SOL for the pool
+    require!(
+        migration_fee.saturating_add(graduation_bonus) < sol_reserves,
+        ClawdVaultError::InsufficientLiquidity
+    );
+    
+    Ok((migration_fee, graduation_bonus))
+}
+
+/// Pay the protocol migration fee and creator graduation bonus out of the curve's SOL vault
+/// A crank caller gets up to CRANK_BOUNTY_LAMPORTS of the fee. Curves whose fees were
+/// already paid by an earlier (refunded) release pay nothing.
+/// Returns (migration_fee, graduation_bonus, crank_bounty)
+fn pay_migration_fees<'info>(
+    config: &Config,
+    curve: &mut BondingCurve,
+    sol_vault: &AccountInfo<'info>,
+    fee_recipient: &AccountInfo<'info>,
+    creator: &AccountInfo<'info>,
+    cranker: Option<&AccountInfo<'info>>,
+) -> Result<(u64, u64, u64)> {
+    if curve.migration_fees_paid {
+        log!("Migration fees already paid");
+        return Ok((0, 0, 0));
+    }
+    let (migration_fee, graduation_bonus) = migration_fees(config, curve.real_sol_reserves)?;
+    curve.migration_fees_paid = true;
+    
+    let crank_bounty = match cranker {
+        Some(cranker) => {
+            let bounty = migration_fee.min(CRANK_BOUNTY_LAMPORTS);
+            **cranker.try_borrow_mut_lamports()? += bounty;
+            bounty
+        }
+        None => 0,
+    };
+    
+    let total = migration_fee
+        .checked_add(graduation_bonus)
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    let protocol_fee = migration_fee
+        .checked_sub(crank_bounty)
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    **sol_vault.try_borrow_mut_lamports()? -= total;
+    **fee_recipient.try_borrow_mut_lamports()? += protocol_fee;
+    **creator.try_borrow_mut_lamports()? += graduation_bonus;
+    
+    log!("Migration fee: {} lamports ({} crank bounty), graduation bonus: {} lamports", migration_fee, crank_bounty, graduation_bonus);
+    
+    Ok((migration_fee, graduation_bonus, crank_bounty))
+}
+
+/// Burn excess curve tokens, or send them to `config.excess_token_destination` if one is set
+#[allow(clippy::too_many_arguments)]
+fn dispose_excess_tokens<'info>(
+    config: &Config,
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    bonding_curve: AccountInfo<'info>,
+    excess_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    curve_signer: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    if config.excess_token_destination == Pubkey::default() {
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Burn {
+                    mint: mint.to_account_info(),
+                    from: token_vault.to_account_info(),
+                    authority: bonding_curve,
+                },
+                curve_signer,
+            ),
+            amount,
+        )?;
+        log!("🔥 Burned {} excess tokens", amount);
+    } else {
+        let destination = excess_token_account.ok_or(ClawdVaultError::InvalidExcessTokenAccount)?;
+        require!(
+            destination.owner == config.excess_token_destination && destination.mint == mint.key(),
+            ClawdVaultError::InvalidExcessTokenAccount
+        );
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: token_vault.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: destination.to_account_info(),
+                    authority: bonding_curve,
+                },
+                curve_signer,
+            ),
+            amount,
+            mint.decimals,
+        )?;
+        log!("📦 Sent {} excess tokens to {}", amount, config.excess_token_destination);
+    }
+    
+    Ok(())
+}
+
+/// Tokens held outside a refunding curve: the mint supply minus the curve's vault and
+/// transfer fees withheld in the mint (which belong to the creator)
+fn circulating_supply(supply: u64, vault_amount: u64, withheld: u64) -> Result<u64> {
+    let circulating = supply
+        .checked_sub(vault_amount)
+        .and_then(|amount| amount.checked_sub(withheld))
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    Ok(circulating)
+}
+
+/// Pro-rata share of `sol_reserves` for redeeming `token_amount` of `circulating` tokens
+/// Rounded down, so the last redeemer (`token_amount == circulating`) gets whatever is left
+fn redeem_amount(token_amount: u64, sol_reserves: u64, circulating: u64) -> Result<u64> {
+    require!(token_amount <= circulating, ClawdVaultError::MathOverflow);
+    mul_div(token_amount, sol_reserves, circulating)
+}
+
+/// a * b / c, rounded down
+fn mul_div(a: u64, b: u64, c: u64) -> Result<u64> {
+    let result = (a as u128)
+        .checked_mul(b as u128)
+        .ok_or(ClawdVaultError::MathOverflow)?
+        .checked_div(c as u128)
+        .ok_or(ClawdVaultError::MathOverflow)?;
+    u64::try_from(result).map_err(|_| ClawdVaultError::MathOverflow.into())
+}
+
+/// a * b / c, rounded up
+fn mul_div_ceil(a: u64, b: u64, c: u64) -> Result<u64> {
+    require!(c > 0, ClawdVaultError::MathOverflow);
+    let result = (a as u128)
+        .checked_mul(b as u128)
+        .ok_or(ClawdVaultError::MathOverflow)?
+        .div_ceil(c as u128);
+    u64::try_from(result).map_err(|_| ClawdVaultError::MathOverflow.into())
+}
+
+/// Integer square root (floor)
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// Constant-product output for `amount_in` added to the `reserve_in` side, rounded down
+fn swap_out(reserve_in: u64, reserve_out: u64, amount_in: u64) -> Result<u64> {
+    mul_div(
+        reserve_out,
+        amount_in,
+        reserve_in.checked_add(amount_in).ok_or(ClawdVaultError::MathOverflow)?,
+    )
+}
+
+/// Protocol and creator fee for a pool swap of `sol_amount` lamports
+fn pool_fees(sol_amount: u64) -> Result<(u64, u64)> {
+    let protocol_fee = mul_div(sol_amount, POOL_PROTOCOL_FEE_BPS, BPS_DENOMINATOR)?;
+    let total_fee = mul_div(sol_amount, POOL_TOTAL_FEE_BPS, BPS_DENOMINATOR)?;
+    Ok((protocol_fee, total_fee - protocol_fee))
+}
+
+// ============================================================================
+// ACCOUNT STRUCTURES
+// ============================================================================
+
+/// Global protocol configuration
+#[account]
+pub struct Config {
+    pub authority: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub migration_operator: Pubkey,  // Can trigger migrations (hot wallet)
+    pub total_tokens_created: u64,
+    pub total_volume_sol: u64,
+    pub bump: u8,
+    pub migration_wallets: Vec<Pubkey>,  // May claim migration escrows
+    pub amm_programs: Vec<Pubkey>,       // May own finalized migration pools
+    pub excess_token_destination: Pubkey,  // Owner of excess tokens at migration (default = burn)
+    pub migration_fee_lamports: u64,  // Flat protocol fee at migration
+    pub migration_fee_bps: u16,       // Protocol cut of the raised SOL at migration
+    pub graduation_bonus: u64,        // Paid to the creator at migration (lamports)
+    pub metadata_policy: MetadataPolicy,  // Applied to token metadata after graduation
+    pub max_transfer_fee_bps: u16,    // Cap on Token-2022 transfer fees chosen at launch (0 = disabled)
+    pub collection_mint: Pubkey,      // Protocol collection NFT (default = not created yet)
+    pub symbol_policy: SymbolPolicy,  // Launches colliding with a verified or reserved ticker
+    pub name_charset: u8,             // CHARSET_* classes allowed in token names (0 = any)
+    pub symbol_charset: u8,           // CHARSET_* classes allowed in token symbols (0 = any)
+    pub uri_prefixes: Vec<String>,    // Allowed metadata URI prefixes (empty = any)
+    pub moderator: Pubkey,            // May set curve flags alongside the authority (default = none)
+    pub pending_migration_destinations: Vec<PendingMigrationDestination>,  // Queued allow-list approvals
+}
+
+impl Config {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1 +  // Added 32 for migration_operator
+        (4 + 32 * MAX_MIGRATION_DESTINATIONS) + // migration_wallets
+        (4 + 32 * MAX_MIGRATION_DESTINATIONS) + // amm_programs
+        32 + // excess_token_destination
+        8 + // migration_fee_lamports
+        2 + // migration_fee_bps
+        8 + // graduation_bonus
+        1 + // metadata_policy
+        2 + // max_transfer_fee_bps
+        32 + // collection_mint
+        1 + // symbol_policy
+        1 + // name_charset
+        1 + // symbol_charset
+        (4 + (4 + MAX_URI_PREFIX_LEN) * MAX_URI_PREFIXES) + // uri_prefixes
+        32 + // moderator
+        (4 + PendingMigrationDestination::LEN * MAX_MIGRATION_DESTINATIONS);  // pending_migration_destinations
+    
+    /// Original layout, before migration_operator
+    pub const V0_LEN: usize = 89;
+    
+    /// Layout before the migration allow-lists
+    pub const V1_LEN: usize = 121;
+    
+    /// Fill fields added after the legacy layouts (zeroed by `resize_config`)
+    pub fn upgrade_legacy(&mut self) {
+        self.amm_programs = DEFAULT_AMM_PROGRAMS.to_vec();
+    }
+    
+    /// The allow-list for `kind`
+    pub fn migration_destinations(&self, kind: MigrationDestinationKind) -> &Vec<Pubkey> {
+        match kind {
+            MigrationDestinationKind::Wallet => &self.migration_wallets,
+            MigrationDestinationKind::AmmProgram => &self.amm_programs,
+        }
+    }
+    
+    pub fn migration_destinations_mut(&mut self, kind: MigrationDestinationKind) -> &mut Vec<Pubkey> {
+        match kind {
+            MigrationDestinationKind::Wallet => &mut self.migration_wallets,
+            MigrationDestinationKind::AmmProgram => &mut self.amm_programs,
+        }
+    }
+}
+
+/// Bonding curve state for each token
+#[account]
+pub struct BondingCurve {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub graduated: bool,  // Legacy mirror of `status`, kept for v1 readers
+    pub migrated: bool,   // Legacy mirror of `status` (assets left the curve), kept for v1 readers
+    pub created_at: i64,
+    pub bump: u8,
+    pub sol_vault_bump: u8,
+    pub status: CurveStatus,
+    pub migration_dex: Option<MigrationDex>,
+    pub migration_pool: Pubkey,
+    pub migration_lp_mint: Pubkey,
+    pub migration_lp_handling: Option<LpHandling>,
+    pub migration_started_at: i64,
+    pub migration_sol_amount: u64,    // Held in the migration escrow while Migrating
+    pub migration_token_amount: u64,  // Held in the migration escrow while Migrating
+    pub migration_wallet: Pubkey,     // Allow-listed wallet that claimed the escrow
+    pub graduation_deadline: i64,     // Refundable after this if not graduated (0 = none)
+    pub metadata_policy: Option<MetadataPolicy>,  // Set once applied by `lock_metadata`
+    pub token_program: Pubkey,        // SPL Token or Token-2022
+    pub flags: u8,                    // FLAG_* bits
+    pub pending_creator: Pubkey,      // Proposed by the creator, takes over on `accept_creator` (default = none)
+    pub migration_fees_paid: bool,    // Migration fee and graduation bonus taken (kept across refunded releases)
+}
+
+impl BondingCurve {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // creator
+        32 + // mint
+        8 + // virtual_sol_reserves
+        8 + // virtual_token_reserves
+        8 + // real_sol_reserves
+        8 + // real_token_reserves
+        8 + // token_total_supply
+        1 + // graduated
+        1 + // migrated
+        8 + // created_at
+        1 + // bump
+        1 + // sol_vault_bump
+        1 + // status
+        2 + // migration_dex
+        32 + // migration_pool
+        32 + // migration_lp_mint
+        2 + // migration_lp_handling
+        8 + // migration_started_at
+        8 + // migration_sol_amount
+        8 + // migration_token_amount
+        32 + // migration_wallet
+        8 + // graduation_deadline
+        2 + // metadata_policy
+        32 + // token_program
+        1 + // flags
+        32 + // pending_creator
+        1;  // migration_fees_paid
+    
+    /// Size of curves created before `status` and the migration target were added (see `resize_curve`)
+    pub const LEGACY_LEN: usize = 8 + // discriminator
+        32 + // creator
+        32 + // mint
+        5 * 8 + // virtual/real reserves, token_total_supply
+        1 + // graduated
+        1 + // migrated
+        8 + // created_at
+        1 + // bump
+        1;  // sol_vault_bump
+    
+    /// Fill fields added after the legacy layout (zeroed by `resize_curve`)
+    pub fn upgrade_legacy(&mut self) {
+        self.token_program = Token::id();
+        self.status = if self.migrated {
+            // Every legacy migration went through a Raydium CPMM pool
+            self.migration_dex = Some(MigrationDex::RaydiumCpmm);
+            CurveStatus::Migrated
+        } else if self.graduated {
+            CurveStatus::Graduated
+        } else {
+            CurveStatus::Trading
+        };
+    }
+    
+    /// Fails once the curve's graduation deadline has passed
+    pub fn require_before_deadline(&self) -> Result<()> {
+        require!(
+            self.graduation_deadline == 0 || Clock::get()?.unix_timestamp < self.graduation_deadline,
+            ClawdVaultError::DeadlinePassed
+        );
+        Ok(())
+    }
+    
+    /// Tokens that list `sol_amount` at the final curve price (capped at the real token reserves)
+    pub fn listing_token_amount(&self, sol_amount: u64) -> Result<u64> {
+        let tokens = mul_div(sol_amount, self.virtual_token_reserves, self.virtual_sol_reserves)?;
+        Ok(tokens.min(self.real_token_reserves))
+    }
+    
+    /// Fails unless the curve is in `expected`, with an error describing the current state
+    pub fn require_status(&self, expected: CurveStatus) -> Result<()> {
+        if self.status == expected {
+            return Ok(());
+        }
+        let err = match self.status {
+            CurveStatus::Graduated if expected == CurveStatus::Migrating => ClawdVaultError::NotMigrating,
+            CurveStatus::Trading => ClawdVaultError::NotGraduated,
+            CurveStatus::Paused => ClawdVaultError::CurvePaused,
+            CurveStatus::Graduated => ClawdVaultError::AlreadyGraduated,
+            CurveStatus::Migrating | CurveStatus::Migrated => ClawdVaultError::AlreadyMigrated,
+            CurveStatus::Refunding => ClawdVaultError::CurveRefunding,
+            CurveStatus::Closed => ClawdVaultError::CurveClosed,
+        };
+        Err(err.into())
+    }
+    
+    /// Move to `next`. The only place `status` (and its legacy mirrors) may change.
+    pub fn transition(&mut self, next: CurveStatus) -> Result<()> {
+        require!(
+            self.status.can_transition_to(next),
+            ClawdVaultError::InvalidStatusTransition
+        );
+        self.status = next;
+        self.graduated = next.has_graduated();
+        self.migrated = matches!(next, CurveStatus::Migrating | CurveStatus::Migrated);
+        Ok(())
+    }
+}
+
+/// Bonding curve lifecycle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CurveStatus {
+    #[default]
+    Trading,
+    Paused,
+    Graduated,
+    Migrating,
+    Migrated,
+    Refunding,
+    Closed,
+}
+
+/// DEX a graduated curve's liquidity was moved to
+/// Only DEXes whose pools the program can decode and verify are listed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MigrationDex {
+    RaydiumCpmm,  // Through the migration escrow (`release_for_migration`)
+    ClawdVault,   // Built-in pool (`crank_migration`)
+}
+
+/// What happens to a token's Metaplex metadata after graduation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MetadataPolicy {
+    Immutable,         // Frozen forever
+    CreatorAuthority,  // Update authority handed to the creator
+}
+
+/// How launches whose symbol collides with a verified or reserved ticker are handled
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SymbolPolicy {
+    #[default]
+    Off,     // Registry is informational only
+    Flag,    // Launch succeeds with FLAG_IMPERSONATION set
+    Reject,  // Launch fails
+}
+
+/// Standing of a ticker in the symbol registry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SymbolStatus {
+    #[default]
+    Claimed,   // First launch with this ticker
+    Verified,  // Confirmed by the authority as the genuine token
+    Reserved,  // Held back by the authority
+}
+
+/// What happened to the migration pool's LP tokens
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LpHandling {
+    Burned,
+    Locked,
+}
+
+/// Profile fields passed to `set_token_profile` or a create instruction (empty = not set)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct TokenProfileArgs {
+    pub website: String,
+    pub twitter: String,
+    pub telegram: String,
+    pub agent_id: String,
+    pub description: String,
+}
+
+/// Per-curve outcome of `release_for_migration_batch` (returned as return data)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ReleaseResult {
+    pub mint: Pubkey,
+    pub error_code: u32,  // 0 = released, otherwise the ClawdVaultError / program error code
+}
+
+/// Which `Config` migration allow-list an entry belongs to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MigrationDestinationKind {
+    Wallet,
+    AmmProgram,
+}
+
+/// Migration destination approval waiting out `MIGRATION_DESTINATION_DELAY`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PendingMigrationDestination {
+    pub kind: MigrationDestinationKind,
+    pub destination: Pubkey,
+    pub activates_at: i64,  // `apply_migration_destination` accepted from then on
+}
+
+impl PendingMigrationDestination {
+    pub const LEN: usize = 1 + 32 + 8;
+}
+
+impl CurveStatus {
+    /// Past graduation: the curve no longer trades and its liquidity is or will be in a pool
+    pub fn has_graduated(self) -> bool {
+        matches!(self, CurveStatus::Graduated | CurveStatus::Migrating | CurveStatus::Migrated)
+    }
+    
+    pub fn can_transition_to(self, next: CurveStatus) -> bool {
+        use CurveStatus::*;
+        matches!(
+            (self, next),
+            (Trading, Paused)
+                | (Paused, Trading)
+                | (Trading, Graduated)
+                | (Trading, Refunding)
+                | (Paused, Refunding)
+                | (Graduated, Migrating)
+                | (Graduated, Migrated)
+                | (Migrating, Migrated)
+                | (Migrating, Graduated)
+                | (Refunding, Closed)
+        )
+    }
+}
+
+/// ClawdVault constant-product pool a graduated curve migrates into
+#[account]
+pub struct Pool {
+    pub mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub sol_reserves: u64,
+    pub token_reserves: u64,
+    pub lp_supply: u64,
+    pub created_at: i64,
+    pub bump: u8,
+    pub sol_vault_bump: u8,
+}
+
+impl Pool {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // lp_mint
+        8 + // sol_reserves
+        8 + // token_reserves
+        8 + // lp_supply
+        8 + // created_at
+        1 + // bump
+        1;  // sol_vault_bump
+    
+    /// (SOL, tokens) a deposit minting `lp_amount` LP must add
+    /// Rounded up so existing LPs are never diluted
+    pub fn deposit_amounts(&self, lp_amount: u64) -> Result<(u64, u64)> {
+        Ok((
+            mul_div_ceil(lp_amount, self.sol_reserves, self.lp_supply)?,
+            mul_div_ceil(lp_amount, self.token_reserves, self.lp_supply)?,
+        ))
+    }
+    
+    /// (SOL, tokens) a withdrawal burning `lp_amount` LP takes out
+    /// Rounded down so remaining LPs are never diluted
+    pub fn withdraw_amounts(&self, lp_amount: u64) -> Result<(u64, u64)> {
+        require!(lp_amount <= self.lp_supply, ClawdVaultError::MathOverflow);
+        Ok((
+            mul_div(lp_amount, self.sol_reserves, self.lp_supply)?,
+            mul_div(lp_amount, self.token_reserves, self.lp_supply)?,
+        ))
+    }
+}
+
+/// Symbol registry entry, one per normalized ticker
+#[account]
+pub struct SymbolRecord {
+    pub symbol: String,  // Normalized (uppercase)
+    pub mint: Pubkey,    // First token launched with the ticker (or the verified one)
+    pub payer: Pubkey,   // Paid the record's rent, refunded when a claim is released
+    pub status: SymbolStatus,
+    pub bump: u8,
+}
+
+impl SymbolRecord {
+    pub const LEN: usize = 8 + // discriminator
+        (4 + 10) + // symbol
+        32 + // mint
+        32 + // payer
+        1 + // status
+        1;  // bump
+}
+
+/// Creator-maintained token profile, so indexers can rebuild token pages from chain data
+#[account]
+pub struct TokenProfile {
+    pub mint: Pubkey,
+    pub website: String,
+    pub twitter: String,      // X handle
+    pub telegram: String,
+    pub agent_id: String,     // Agent id or model tag of the launching agent
+    pub description: String,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl TokenProfile {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        (4 + MAX_PROFILE_WEBSITE_LEN) + // website
+        (4 + MAX_PROFILE_HANDLE_LEN) + // twitter
+        (4 + MAX_PROFILE_HANDLE_LEN) + // telegram
+        (4 + MAX_PROFILE_AGENT_LEN) + // agent_id
+        (4 + MAX_PROFILE_DESCRIPTION_LEN) + // description
+        8 + // updated_at
+        1;  // bump
+}
+
+// ============================================================================
+// CONTEXT STRUCTURES  
+// ============================================================================
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Fee recipient wallet
+    pub fee_recipient: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Config::LEN,
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferAuthority<'info> {
+    /// Current authority (must sign)
+    #[account(
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Protocol config to update
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Resize config account (one-time migration) - uses raw account to handle format change
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResizeConfigRaw<'info> {
+    /// Authority pays for reallocation
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Protocol config (raw - will be manually verified and resized)
+    /// CHECK: Manually verified in instruction, PDA seeds checked here
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetMigrationDestination<'info> {
+    /// Authority (only authority can queue, apply or revoke allow-list entries)
+    #[account(
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Protocol config to update
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetMetadataPolicy<'info> {
+    /// Authority (only authority can set the policy)
+    #[account(
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Protocol config to update
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetSymbolPolicy<'info> {
+    /// Authority (only authority can set the policy)
+    #[account(
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Protocol config to update
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct SetSymbolStatus<'info> {
+    /// Authority (only authority can manage tickers)
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SymbolRecord::LEN,
+        seeds = [SYMBOL_SEED, normalize_symbol(&symbol).as_bytes()],
+        bump,
+    )]
+    pub symbol_record: Account<'info, SymbolRecord>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetMetadataCharsets<'info> {
+    /// Authority (only authority can set metadata rules)
+    #[account(
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Protocol config to update
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetUriPrefix<'info> {
+    /// Authority (only authority can set metadata rules)
+    #[account(
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Protocol config to update
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetMaxTransferFee<'info> {
+    /// Authority (only authority can set the cap)
+    #[account(
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Protocol config to update
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetExcessTokenDestination<'info> {
+    /// Authority (only authority can set the destination)
+    #[account(
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Protocol config to update
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetMigrationFees<'info> {
+    /// Authority (only authority can set fees)
+    #[account(
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Protocol config to update
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Resize a legacy bonding curve - uses raw account like ResizeConfigRaw
+#[derive(Accounts)]
+pub struct ResizeCurveRaw<'info> {
+    /// Anyone can pay for the reallocation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Only used to derive the curve PDA
+    pub mint: UncheckedAccount<'info>,
+    
+    /// Bonding curve (raw - discriminator and size verified in instruction)
+    /// CHECK: PDA seeds checked here
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub bonding_curve: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetCurvePaused<'info> {
+    /// Authority (only authority can pause)
+    #[account(
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetModerator<'info> {
+    /// Authority (only authority can set the moderator)
+    #[account(
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Protocol config to update
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetCurveFlags<'info> {
+    /// Moderator or authority
+    #[account(
+        constraint = moderator.key() == config.moderator
+            || moderator.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub moderator: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetMigrationOperator<'info> {
+    /// Authority (only authority can set operator)
+    #[account(
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Protocol config to update
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String, uri: String)]
+pub struct CreateToken<'info> {
+    /// Launch identity: recorded as the curve creator and receives creator fees
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    /// Funds every account the launch creates (may be the creator, or a sponsor)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// New mint: a keypair signing the transaction (`create_token`)
+    /// or the creator/nonce PDA (`create_token_pda`)
+    /// CHECK: Created and initialized by the handler
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+    
+    /// CHECK: Metadata account created via CPI to Metaplex
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = metadata_program.key(),
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = BondingCurve::LEN,
+        seeds = [CURVE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    /// SOL vault PDA - holds curve's SOL reserves
+    #[account(
+        init,
+        payer = payer,
+        space = 0,
+        seeds = [VAULT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: PDA for holding SOL
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    /// Token vault - holds curve's token reserves
+    /// CHECK: Curve's associated token account, created by the handler once the mint exists
+    #[account(mut)]
+    pub token_vault: UncheckedAccount<'info>,
+    
+    /// Creator's token account for initial buy
+    /// CHECK: Creator's associated token account, created by the handler if needed
+    #[account(mut)]
+    pub creator_token_account: UncheckedAccount<'info>,
+    
+    /// Ticker registry entry, claimed on first use (required unless the symbol policy is off)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SymbolRecord::LEN,
+        seeds = [SYMBOL_SEED, normalize_symbol(&symbol).as_bytes()],
+        bump,
+    )]
+    pub symbol_record: Option<Account<'info, SymbolRecord>>,
+    
+    /// Token profile, written at launch when profile fields are passed
+    #[account(
+        init,
+        payer = payer,
+        space = TokenProfile::LEN,
+        seeds = [PROFILE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub token_profile: Option<Box<Account<'info, TokenProfile>>>,
+    
+    /// Protocol collection NFT (required once `create_collection` has run)
+    /// CHECK: Must match the config; verified by Metaplex
+    #[account(address = config.collection_mint @ ClawdVaultError::InvalidCollection)]
+    pub collection_mint: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Collection metadata account, verified by seeds and the Metaplex program
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), config.collection_mint.as_ref()],
+        bump,
+        seeds::program = metadata_program.key(),
+    )]
+    pub collection_metadata: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Collection master edition account, verified by seeds and the Metaplex program
+    #[account(
+        seeds = [b"metadata", metadata_program.key().as_ref(), config.collection_mint.as_ref(), b"edition"],
+        bump,
+        seeds::program = metadata_program.key(),
+    )]
+    pub collection_master_edition: Option<UncheckedAccount<'info>>,
+    
+    /// Metaplex metadata requires the legacy token program
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateCollection<'info> {
+    /// Authority (only authority can create the collection)
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [COLLECTION_SEED],
+        bump,
+        mint::decimals = 0,
+        mint::authority = config,
+        mint::freeze_authority = config,
+        mint::token_program = token_program,
+    )]
+    pub collection_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// Holds the single collection NFT
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = collection_mint,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub collection_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Metadata account created via CPI to Metaplex
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), collection_mint.key().as_ref()],
+        bump,
+        seeds::program = metadata_program.key(),
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition account created via CPI to Metaplex
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), collection_mint.key().as_ref(), b"edition"],
+        bump,
+        seeds::program = metadata_program.key(),
+    )]
+    pub collection_master_edition: UncheckedAccount<'info>,
+    
+    /// Metaplex metadata requires the legacy token program
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateTokenGroup<'info> {
+    /// Authority (only authority can create the group)
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [TOKEN_GROUP_SEED],
+        bump,
+        mint::decimals = 0,
+        mint::authority = config,
+        mint::token_program = token_program,
+        extensions::group_pointer::authority = config,
+        extensions::group_pointer::group_address = token_group,
+    )]
+    pub token_group: Box<InterfaceAccount<'info, Mint>>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String)]
+pub struct CreateToken2022<'info> {
+    /// Launch identity: recorded as the curve creator and receives creator fees
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    /// Funds every account the launch creates (may be the creator, or a sponsor)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// New mint: a keypair signing the transaction (`create_token_2022`) or the creator/nonce
+    /// PDA (`create_token_2022_pda`); the account is created with its extensions in the instruction
+    /// CHECK: Created and initialized by the handler
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = BondingCurve::LEN,
+        seeds = [CURVE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    /// SOL vault PDA - holds curve's SOL reserves
+    #[account(
+        init,
+        payer = payer,
+        space = 0,
+        seeds = [VAULT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: PDA for holding SOL
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    /// CHECK: Curve's token vault (associated token account), created once the mint exists
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(&bonding_curve.key(), &mint.key(), &Token2022::id()),
+    )]
+    pub token_vault: UncheckedAccount<'info>,
+    
+    /// CHECK: Creator's associated token account for the initial buy, created if needed
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(&creator.key(), &mint.key(), &Token2022::id()),
+    )]
+    pub creator_token_account: UncheckedAccount<'info>,
+    
+    /// Ticker registry entry, claimed on first use (required unless the symbol policy is off)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SymbolRecord::LEN,
+        seeds = [SYMBOL_SEED, normalize_symbol(&symbol).as_bytes()],
+        bump,
+    )]
+    pub symbol_record: Option<Account<'info, SymbolRecord>>,
+    
+    /// Token profile, written at launch when profile fields are passed
+    #[account(
+        init,
+        payer = payer,
+        space = TokenProfile::LEN,
+        seeds = [PROFILE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub token_profile: Option<Box<Account<'info, TokenProfile>>>,
+    
+    /// CHECK: Protocol token group PDA; the launch joins it once `create_token_group` has run
+    #[account(
+        mut,
+        seeds = [TOKEN_GROUP_SEED],
+        bump,
+    )]
+    pub token_group: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String)]
+pub struct UpdateTokenMetadata<'info> {
+    /// Token creator (only the creator can update metadata)
+    #[account(
+        mut,
+        constraint = creator.key() == bonding_curve.creator @ ClawdVaultError::Unauthorized,
+    )]
+    pub creator: Signer<'info>,
+    
+    /// Metadata rules (charsets, URI allow-list) and symbol policy
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, mint.key().as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+    
+    /// Holds the metadata of Token-2022 tokens
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// Registry entry of the new symbol, claimed on first use (required for a new
+    /// symbol unless the symbol policy is off)
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = SymbolRecord::LEN,
+        seeds = [SYMBOL_SEED, normalize_symbol(&symbol).as_bytes()],
+        bump,
+    )]
+    pub symbol_record: Option<Box<Account<'info, SymbolRecord>>>,
+    
+    /// CHECK: Registry PDA of the current symbol, checked in the instruction and closed if this
+    /// token claimed it (required when the symbol changes)
+    #[account(mut)]
+    pub old_symbol_record: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Rent payer stored in the old symbol record, checked in the instruction and
+    /// refunded when the record is closed
+    #[account(mut)]
+    pub old_symbol_payer: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Metaplex metadata account, verified by seeds and the Metaplex program (unused for Token-2022)
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = metadata_program.key(),
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    
+    pub metadata_program: Program<'info, Metadata>,
+    
+    #[account(address = bonding_curve.token_program @ ClawdVaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetTokenProfile<'info> {
+    /// Token creator (only the creator can edit the profile; pays its rent)
+    #[account(
+        mut,
+        constraint = creator.key() == bonding_curve.creator @ ClawdVaultError::Unauthorized,
+    )]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = TokenProfile::LEN,
+        seeds = [PROFILE_SEED, bonding_curve.mint.as_ref()],
+        bump,
+    )]
+    pub token_profile: Box<Account<'info, TokenProfile>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeCreator<'info> {
+    /// Current creator
+    #[account(
+        constraint = creator.key() == bonding_curve.creator @ ClawdVaultError::Unauthorized,
+    )]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    /// CHECK: Token mint, holds the Token-2022 metadata (unused for legacy tokens)
+    #[account(mut, address = bonding_curve.mint)]
+    pub mint: UncheckedAccount<'info>,
+    
+    /// CHECK: Metaplex metadata account, verified by seeds and the Metaplex program (unused for Token-2022)
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), bonding_curve.mint.as_ref()],
+        bump,
+        seeds::program = metadata_program.key(),
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    
+    pub metadata_program: Program<'info, Metadata>,
+    #[account(address = bonding_curve.token_program @ ClawdVaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptCreator<'info> {
+    /// Proposed creator
+    #[account(
+        constraint = new_creator.key() == bonding_curve.pending_creator @ ClawdVaultError::Unauthorized,
+    )]
+    pub new_creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    /// CHECK: Token mint, holds the Token-2022 metadata (unused for legacy tokens)
+    #[account(mut, address = bonding_curve.mint)]
+    pub mint: UncheckedAccount<'info>,
+    
+    /// CHECK: Metaplex metadata account, verified by seeds and the Metaplex program (unused for Token-2022)
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), bonding_curve.mint.as_ref()],
+        bump,
+        seeds::program = metadata_program.key(),
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    
+    pub metadata_program: Program<'info, Metadata>,
+    #[account(address = bonding_curve.token_program @ ClawdVaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimTransferFees<'info> {
+    /// Token creator (only the creator can claim)
+    #[account(
+        mut,
+        constraint = creator.key() == bonding_curve.creator @ ClawdVaultError::Unauthorized,
+    )]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [CURVE_SEED, mint.key().as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+    
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: PDA set as the mint's withdraw-withheld authority at launch
+    #[account(
+        seeds = [TRANSFER_FEE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub fee_authority: UncheckedAccount<'info>,
+    
+    /// Creator's token account (created if needed)
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+    )]
+    pub creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(address = bonding_curve.token_program @ ClawdVaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Buy<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// SOL vault
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, mint.key().as_ref()],
+        bump = bonding_curve.sol_vault_bump,
+    )]
+    /// CHECK: PDA for SOL
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    /// Token vault
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Buyer's token account (created if needed)
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Protocol fee recipient
+    #[account(
+        mut,
+        address = config.fee_recipient,
+    )]
+    /// CHECK: Validated against config
+    pub fee_recipient: UncheckedAccount<'info>,
+    
+    /// Token creator (receives creator fee)
+    #[account(
+        mut,
+        address = bonding_curve.creator,
+    )]
+    /// CHECK: Validated against curve
+    pub creator: UncheckedAccount<'info>,
+    
+    #[account(address = bonding_curve.token_program @ ClawdVaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Sell<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// SOL vault
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, mint.key().as_ref()],
+        bump = bonding_curve.sol_vault_bump,
+    )]
+    /// CHECK: PDA for SOL
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    /// Token vault
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Seller's token account
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Protocol fee recipient
+    #[account(
+        mut,
+        address = config.fee_recipient,
+    )]
+    /// CHECK: Validated against config
+    pub fee_recipient: UncheckedAccount<'info>,
+    
+    /// Token creator
+    #[account(
+        mut,
+        address = bonding_curve.creator,
+    )]
+    /// CHECK: Validated against curve
+    pub creator: UncheckedAccount<'info>,
+    
+    #[account(address = bonding_curve.token_program @ ClawdVaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReleaseForMigration<'info> {
+    /// Operator or authority (either can trigger migration)
+    #[account(
+        mut,
+        constraint = (
+            operator.key() == config.migration_operator || 
+            operator.key() == config.authority
+        ) @ ClawdVaultError::Unauthorized,
+    )]
+    pub operator: Signer<'info>,
+    
+    /// Protocol config
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Bonding curve being migrated
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    /// SOL vault holding the curve's SOL (owned by program, not system)
+    /// CHECK: PDA verified by seeds, lamports transferred manually
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.sol_vault_bump,
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    /// Token vault holding remaining tokens (ATA owned by bonding_curve)
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// The token mint (mut: excess tokens may be burned)
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Migration escrow PDA - holds released SOL until the pool is finalized
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 0,
+        seeds = [MIGRATION_ESCROW_SEED, token_mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: PDA for holding SOL
+    pub migration_escrow: UncheckedAccount<'info>,
+    
+    /// Migration escrow's token account
+    #[account(
+        init_if_needed,
+        payer = operator,
+        associated_token::mint = token_mint,
+        associated_token::authority = migration_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Protocol fee recipient (receives the migration fee)
+    #[account(
+        mut,
+        address = config.fee_recipient,
+    )]
+    /// CHECK: Validated against config
+    pub fee_recipient: UncheckedAccount<'info>,
+    
+    /// Token creator (receives the graduation bonus)
+    #[account(
+        mut,
+        address = bonding_curve.creator,
+    )]
+    /// CHECK: Validated against curve
+    pub creator: UncheckedAccount<'info>,
+    
+    /// Receives excess tokens when `config.excess_token_destination` is set
+    #[account(mut)]
+    pub excess_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(address = bonding_curve.token_program @ ClawdVaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReleaseForMigrationBatch<'info> {
+    /// Operator or authority (either can trigger migration)
+    #[account(
+        mut,
+        constraint = (
+            operator.key() == config.migration_operator || 
+            operator.key() == config.authority
+        ) @ ClawdVaultError::Unauthorized,
+    )]
+    pub operator: Signer<'info>,
+    
+    /// Protocol config
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    
+    /// Protocol fee recipient (receives the migration fees)
+    #[account(
+        mut,
+        address = config.fee_recipient,
+    )]
+    /// CHECK: Validated against config
+    pub fee_recipient: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimMigrationEscrow<'info> {
+    /// Operator or authority (either can run migrations)
+    #[account(
+        constraint = (
+            operator.key() == config.migration_operator || 
+            operator.key() == config.authority
+        ) @ ClawdVaultError::Unauthorized,
+    )]
+    pub operator: Signer<'info>,
+    
+    /// Protocol config
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    
+    /// Bonding curve being migrated
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+    
+    /// Token mint (for checked transfers)
+    #[account(address = bonding_curve.mint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: PDA verified by seeds, lamports transferred manually
+    #[account(
+        mut,
+        seeds = [MIGRATION_ESCROW_SEED, bonding_curve.mint.as_ref()],
+        bump,
+    )]
+    pub migration_escrow: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = bonding_curve.mint,
+        associated_token::authority = migration_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Wallet creating the pool; must be on `config.migration_wallets`
+    /// CHECK: Checked against the allow-list; finalize_migration must follow in the same transaction
+    #[account(mut)]
+    pub migration_wallet: UncheckedAccount<'info>,
+    
+    /// Migration wallet's token account for the token
+    #[account(
+        mut,
+        token::mint = bonding_curve.mint,
+        token::authority = migration_wallet,
+    )]
+    pub migration_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Instructions sysvar, used to find the matching finalize_migration
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    #[account(address = bonding_curve.token_program @ ClawdVaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-impl Config {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1;  // Added 32 for migration_operator
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeMigration<'info> {
+    /// Operator or authority (either can run migrations)
+    #[account(
+        constraint = (
+            operator.key() == config.migration_operator || 
+            operator.key() == config.authority
+        ) @ ClawdVaultError::Unauthorized,
+    )]
+    pub operator: Signer<'info>,
+    
+    /// Protocol config
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    
+    /// Bonding curve being migrated
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+    
+    /// CHECK: Owner checked against `config.amm_programs`, decoded for the curve's DEX
+    pub pool: UncheckedAccount<'info>,
+    
+    /// Pool's wrapped SOL vault
+    #[account(token::mint = NATIVE_MINT)]
+    pub pool_sol_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Pool's token vault
+    #[account(token::mint = bonding_curve.mint)]
+    pub pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Wallet that claimed the escrow earlier in this transaction and received the LP tokens
+    #[account(address = bonding_curve.migration_wallet @ ClawdVaultError::EscrowNotClaimed)]
+    pub migration_wallet: Signer<'info>,
+    
+    /// Pool LP mint (checked against the pool)
+    #[account(mut, mint::token_program = lp_token_program)]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// Migration wallet's LP tokens, burned or locked in full
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = migration_wallet,
+        token::token_program = lp_token_program,
+    )]
+    pub lp_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// LP lock PDA's token account (required for `LpHandling::Locked`)
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = lp_lock,
+        token::token_program = lp_token_program,
+    )]
+    pub lp_lock_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// CHECK: PDA verified by seeds; never signs, so locked LP can't leave
+    #[account(
+        seeds = [LP_LOCK_SEED, bonding_curve.mint.as_ref()],
+        bump,
+    )]
+    pub lp_lock: UncheckedAccount<'info>,
+    
+    pub lp_token_program: Interface<'info, TokenInterface>,
 }
 
-/// Bonding curve state for each token
-#[account]
-pub struct BondingCurve {
-    pub creator: Pubkey,
-    pub mint: Pubkey,
-    pub virtual_sol_reserves: u64,
-    pub virtual_token_reserves: u64,
-    pub real_sol_reserves: u64,
-    pub real_token_reserves: u64,
-    pub token_total_supply: u64,
-    pub graduated: bool,
-    pub migrated_to_raydium: bool,
-    pub created_at: i64,
-    pub bump: u8,
-    pub sol_vault_bump: u8,
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefundMigration<'info> {
+    /// Anyone can refund a timed-out migration
+    pub signer: Signer<'info>,
+    
+    /// Bonding curve being refunded
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+    
+    /// Token mint (for checked transfers)
+    #[account(address = bonding_curve.mint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: PDA verified by seeds, lamports transferred manually
+    #[account(
+        mut,
+        seeds = [MIGRATION_ESCROW_SEED, bonding_curve.mint.as_ref()],
+        bump,
+    )]
+    pub migration_escrow: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = bonding_curve.mint,
+        associated_token::authority = migration_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: PDA verified by seeds, lamports transferred manually
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.sol_vault_bump,
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = bonding_curve.mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(address = bonding_curve.token_program @ ClawdVaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-impl BondingCurve {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // creator
-        32 + // mint
-        8 + // virtual_sol_reserves
-        8 + // virtual_token_reserves
-        8 + // real_sol_reserves
-        8 + // real_token_reserves
-        8 + // token_total_supply
-        1 + // graduated
-        1 + // migrated_to_raydium
-        8 + // created_at
-        1 + // bump
-        1;  // sol_vault_bump
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RecordMigration<'info> {
+    /// Operator or authority (either can record migrations)
+    #[account(
+        constraint = (
+            operator.key() == config.migration_operator || 
+            operator.key() == config.authority
+        ) @ ClawdVaultError::Unauthorized,
+    )]
+    pub operator: Signer<'info>,
+    
+    /// Protocol config
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Bonding curve whose assets were released
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    /// CHECK: Owner and layout verified against the curve's DEX in the instruction
+    pub pool: UncheckedAccount<'info>,
 }
 
-// ============================================================================
-// CONTEXT STRUCTURES  
-// ============================================================================
-
+#[event_cpi]
 #[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    /// CHECK: Fee recipient wallet
-    pub fee_recipient: UncheckedAccount<'info>,
+pub struct LockMetadata<'info> {
+    /// Anyone can lock a graduated token's metadata
+    pub signer: Signer<'info>,
     
     #[account(
-        init,
-        payer = authority,
-        space = Config::LEN,
         seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, mint.key().as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+    
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: Metaplex metadata account, verified by seeds and the Metaplex program (unused for Token-2022)
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), mint.key().as_ref()],
         bump,
+        seeds::program = metadata_program.key(),
     )]
-    pub config: Account<'info, Config>,
+    pub metadata: UncheckedAccount<'info>,
     
-    pub system_program: Program<'info, System>,
+    pub metadata_program: Program<'info, Metadata>,
+    #[account(address = bonding_curve.token_program @ ClawdVaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct TransferAuthority<'info> {
-    /// Current authority (must sign)
+pub struct StartRefund<'info> {
+    /// Anyone can start refunds once the deadline has passed
+    pub signer: Signer<'info>,
+    
     #[account(
-        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+        mut,
+        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
     )]
-    pub authority: Signer<'info>,
+    pub bonding_curve: Account<'info, BondingCurve>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Redeem<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
     
-    /// Protocol config to update
     #[account(
         mut,
-        seeds = [b"config"],
-        bump = config.bump,
+        seeds = [CURVE_SEED, mint.key().as_ref()],
+        bump = bonding_curve.bump,
     )]
-    pub config: Account<'info, Config>,
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+    
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: PDA for SOL
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, mint.key().as_ref()],
+        bump = bonding_curve.sol_vault_bump,
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    /// Curve's token vault (unsold tokens don't count as circulating)
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user,
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(address = bonding_curve.token_program @ ClawdVaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-/// Resize config account (one-time migration) - uses raw account to handle format change
+#[event_cpi]
 #[derive(Accounts)]
-pub struct ResizeConfigRaw<'info> {
-    /// Authority pays for reallocation
+pub struct CloseRefund<'info> {
+    /// Anyone can close a refund
+    pub signer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    
+    #[account(
+        mut,
+        seeds = [CURVE_SEED, mint.key().as_ref()],
+        bump = bonding_curve.bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+    
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: PDA for SOL
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, mint.key().as_ref()],
+        bump = bonding_curve.sol_vault_bump,
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    /// Curve's token vault (unsold tokens don't count as circulating)
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    /// Protocol config (raw - will be manually verified and resized)
-    /// CHECK: Manually verified in instruction, PDA seeds checked here
+    /// Protocol fee recipient (receives unredeemed SOL)
     #[account(
         mut,
-        seeds = [b"config"],
-        bump,
+        address = config.fee_recipient,
     )]
-    pub config: UncheckedAccount<'info>,
+    /// CHECK: Validated against config
+    pub fee_recipient: UncheckedAccount<'info>,
     
-    pub system_program: Program<'info, System>,
+    #[account(address = bonding_curve.token_program @ ClawdVaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct SetMigrationOperator<'info> {
-    /// Authority (only authority can set operator)
+pub struct CrankMigration<'info> {
+    /// Anyone can crank a graduated curve; fronts the pool rent (reimbursed), earns the crank bounty
+    /// Cranking a curve that is already migrating or migrated does nothing
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    
+    /// Protocol config
     #[account(
-        constraint = authority.key() == config.authority @ ClawdVaultError::Unauthorized,
+        seeds = [b"config"],
+        bump = config.bump,
     )]
-    pub authority: Signer<'info>,
+    pub config: Box<Account<'info, Config>>,
     
-    /// Protocol config to update
+    /// Bonding curve being migrated
     #[account(
         mut,
-        seeds = [b"config"],
-        bump = config.bump,
+        seeds = [CURVE_SEED, mint.key().as_ref()],
+        bump = bonding_curve.bump,
     )]
-    pub config: Account<'info, Config>,
-}
-
-#[derive(Accounts)]
-#[instruction(name: String, symbol: String, uri: String)]
-pub struct CreateToken<'info> {
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+    
     #[account(mut)]
-    pub creator: Signer<'info>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
     
+    /// CHECK: PDA verified by seeds, lamports transferred manually
     #[account(
         mut,
-        seeds = [b"config"],
-        bump = config.bump,
+        seeds = [VAULT_SEED, mint.key().as_ref()],
+        bump = bonding_curve.sol_vault_bump,
     )]
-    pub config: Account<'info, Config>,
+    pub sol_vault: UncheckedAccount<'info>,
     
     #[account(
-        init,
-        payer = creator,
-        mint::decimals = 6,
-        mint::authority = bonding_curve,
-        // No freeze authority - removes the scary wallet warning
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
     )]
-    pub mint: Account<'info, Mint>,
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    /// CHECK: Metadata account created via CPI to Metaplex
+    /// CHECK: Pool PDA, created in the instruction (a skipped crank creates nothing)
     #[account(
         mut,
-        seeds = [b"metadata", metadata_program.key().as_ref(), mint.key().as_ref()],
+        seeds = [POOL_SEED, mint.key().as_ref()],
         bump,
-        seeds::program = metadata_program.key(),
     )]
-    pub metadata: UncheckedAccount<'info>,
+    pub pool: UncheckedAccount<'info>,
     
+    /// Pool SOL vault PDA - holds the pool's SOL reserves
+    /// CHECK: Created in the instruction
     #[account(
-        init,
-        payer = creator,
-        space = BondingCurve::LEN,
-        seeds = [CURVE_SEED, mint.key().as_ref()],
+        mut,
+        seeds = [POOL_VAULT_SEED, mint.key().as_ref()],
         bump,
     )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+    pub pool_sol_vault: UncheckedAccount<'info>,
     
-    /// SOL vault PDA - holds curve's SOL reserves
+    /// Pool token vault - holds the pool's token reserves
+    /// CHECK: The pool's associated token account, created in the instruction
     #[account(
-        init,
-        payer = creator,
-        space = 0,
-        seeds = [VAULT_SEED, mint.key().as_ref()],
+        mut,
+        address = get_associated_token_address_with_program_id(&pool.key(), &mint.key(), &token_program.key()),
+    )]
+    pub pool_token_vault: UncheckedAccount<'info>,
+    
+    /// CHECK: LP mint PDA, created in the instruction
+    #[account(
+        mut,
+        seeds = [LP_MINT_SEED, pool.key().as_ref()],
         bump,
     )]
-    /// CHECK: PDA for holding SOL
-    pub sol_vault: UncheckedAccount<'info>,
+    pub lp_mint: UncheckedAccount<'info>,
     
-    /// Token vault - holds curve's token reserves
+    /// CHECK: Burn address, owner of the initial LP tokens
+    #[account(address = LP_BURN_ADDRESS)]
+    pub lp_burn_address: UncheckedAccount<'info>,
+    
+    /// CHECK: The burn address's LP token account, created in the instruction
     #[account(
-        init,
-        payer = creator,
-        associated_token::mint = mint,
-        associated_token::authority = bonding_curve,
+        mut,
+        address = get_associated_token_address_with_program_id(&LP_BURN_ADDRESS, &lp_mint.key(), &token_program.key()),
     )]
-    pub token_vault: Account<'info, TokenAccount>,
+    pub lp_burn_account: UncheckedAccount<'info>,
     
-    /// Creator's token account for initial buy (optional, created if needed)
+    /// Protocol fee recipient (receives the migration fee)
     #[account(
-        init_if_needed,
-        payer = creator,
-        associated_token::mint = mint,
-        associated_token::authority = creator,
+        mut,
+        address = config.fee_recipient,
+    )]
+    /// CHECK: Validated against config
+    pub fee_recipient: UncheckedAccount<'info>,
+    
+    /// Token creator (receives the graduation bonus)
+    #[account(
+        mut,
+        address = bonding_curve.creator,
     )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Validated against curve
+    pub creator: UncheckedAccount<'info>,
     
-    pub token_program: Program<'info, Token>,
+    /// Receives excess tokens when `config.excess_token_destination` is set
+    #[account(mut)]
+    pub excess_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(address = bonding_curve.token_program @ ClawdVaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub metadata_program: Program<'info, Metadata>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct Buy<'info> {
+pub struct Swap<'info> {
     #[account(mut)]
-    pub buyer: Signer<'info>,
+    pub user: Signer<'info>,
     
     #[account(
-        mut,
-        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    
+    /// Graduated curve - source of the creator fee destination
+    #[account(
+        seeds = [CURVE_SEED, mint.key().as_ref()],
         bump = bonding_curve.bump,
     )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
     
     #[account(
-        seeds = [b"config"],
-        bump = config.bump,
+        mut,
+        seeds = [POOL_SEED, mint.key().as_ref()],
+        bump = pool.bump,
     )]
-    pub config: Account<'info, Config>,
+    pub pool: Box<Account<'info, Pool>>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
     
-    /// SOL vault
+    /// CHECK: PDA for SOL
     #[account(
         mut,
-        seeds = [VAULT_SEED, mint.key().as_ref()],
-        bump = bonding_curve.sol_vault_bump,
+        seeds = [POOL_VAULT_SEED, mint.key().as_ref()],
+        bump = pool.sol_vault_bump,
     )]
-    /// CHECK: PDA for SOL
-    pub sol_vault: UncheckedAccount<'info>,
+    pub pool_sol_vault: UncheckedAccount<'info>,
     
-    /// Token vault
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = bonding_curve,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
     )]
-    pub token_vault: Account<'info, TokenAccount>,
+    pub pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    /// Buyer's token account (created if needed)
+    /// User's token account (created if needed)
     #[account(
         init_if_needed,
-        payer = buyer,
+        payer = user,
         associated_token::mint = mint,
-        associated_token::authority = buyer,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     
     /// Protocol fee recipient
     #[account(
@@ -1006,148 +6537,304 @@ pub struct Buy<'info> {
     /// CHECK: Validated against curve
     pub creator: UncheckedAccount<'info>,
     
-    pub token_program: Program<'info, Token>,
+    #[account(address = bonding_curve.token_program @ ClawdVaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct Sell<'info> {
+pub struct Deposit<'info> {
     #[account(mut)]
-    pub seller: Signer<'info>,
+    pub user: Signer<'info>,
     
     #[account(
         mut,
-        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
-        bump = bonding_curve.bump,
-    )]
-    pub bonding_curve: Account<'info, BondingCurve>,
-    
-    #[account(
-        seeds = [b"config"],
-        bump = config.bump,
+        seeds = [POOL_SEED, mint.key().as_ref()],
+        bump = pool.bump,
     )]
-    pub config: Account<'info, Config>,
+    pub pool: Box<Account<'info, Pool>>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
     
-    /// SOL vault
     #[account(
         mut,
-        seeds = [VAULT_SEED, mint.key().as_ref()],
-        bump = bonding_curve.sol_vault_bump,
+        address = pool.lp_mint,
     )]
-    /// CHECK: PDA for SOL
-    pub sol_vault: UncheckedAccount<'info>,
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
     
-    /// Token vault
+    /// CHECK: PDA for SOL
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = bonding_curve,
+        seeds = [POOL_VAULT_SEED, mint.key().as_ref()],
+        bump = pool.sol_vault_bump,
     )]
-    pub token_vault: Account<'info, TokenAccount>,
+    pub pool_sol_vault: UncheckedAccount<'info>,
     
-    /// Seller's token account
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = seller,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
     )]
-    pub seller_token_account: Account<'info, TokenAccount>,
+    pub pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    /// Protocol fee recipient
     #[account(
         mut,
-        address = config.fee_recipient,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    /// CHECK: Validated against config
-    pub fee_recipient: UncheckedAccount<'info>,
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    /// Token creator
+    /// User's LP token account (created if needed)
     #[account(
-        mut,
-        address = bonding_curve.creator,
+        init_if_needed,
+        payer = user,
+        associated_token::mint = lp_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    /// CHECK: Validated against curve
-    pub creator: UncheckedAccount<'info>,
+    pub user_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct ReleaseForMigration<'info> {
-    /// Operator or authority (either can trigger migration)
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
     #[account(
         mut,
-        constraint = (
-            operator.key() == config.migration_operator || 
-            operator.key() == config.authority
-        ) @ ClawdVaultError::Unauthorized,
+        seeds = [POOL_SEED, mint.key().as_ref()],
+        bump = pool.bump,
     )]
-    pub operator: Signer<'info>,
+    pub pool: Box<Account<'info, Pool>>,
     
-    /// Protocol config
-    #[account(
-        seeds = [b"config"],
-        bump = config.bump,
-    )]
-    pub config: Account<'info, Config>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
     
-    /// Bonding curve being migrated
     #[account(
         mut,
-        seeds = [CURVE_SEED, bonding_curve.mint.as_ref()],
-        bump = bonding_curve.bump,
-        constraint = bonding_curve.graduated @ ClawdVaultError::NotGraduated,
-        constraint = !bonding_curve.migrated_to_raydium @ ClawdVaultError::AlreadyMigrated,
+        address = pool.lp_mint,
     )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
     
-    /// SOL vault holding the curve's SOL (owned by program, not system)
-    /// CHECK: PDA verified by seeds, lamports transferred manually
+    /// CHECK: PDA for SOL
     #[account(
         mut,
-        seeds = [VAULT_SEED, bonding_curve.mint.as_ref()],
-        bump = bonding_curve.sol_vault_bump,
+        seeds = [POOL_VAULT_SEED, mint.key().as_ref()],
+        bump = pool.sol_vault_bump,
     )]
-    pub sol_vault: UncheckedAccount<'info>,
+    pub pool_sol_vault: UncheckedAccount<'info>,
     
-    /// Token vault holding remaining tokens (ATA owned by bonding_curve)
     #[account(
         mut,
-        associated_token::mint = token_mint,
-        associated_token::authority = bonding_curve,
+        associated_token::mint = mint,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
     )]
-    pub token_vault: Account<'info, TokenAccount>,
+    pub pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    /// The token mint
-    pub token_mint: Account<'info, Mint>,
-    
-    /// Migration wallet that will receive assets for Raydium pool creation
-    /// CHECK: Any wallet can be the migration target, validated by authority
-    #[account(mut)]
-    pub migration_wallet: UncheckedAccount<'info>,
+    /// User's token account (created if needed)
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    /// Migration wallet's token account for the token
     #[account(
         mut,
-        token::mint = token_mint,
-        token::authority = migration_wallet,
+        associated_token::mint = lp_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    pub migration_token_account: Account<'info, TokenAccount>,
+    pub user_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-// ============================================================================
-// EVENTS
-// ============================================================================
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+#[event]
+pub struct TradeEvent {
+    pub mint: Pubkey,
+    pub trader: Pubkey,
+    pub is_buy: bool,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenCreatedEvent {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub graduation_deadline: i64,
+    pub transfer_fee_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GraduationEvent {
+    pub mint: Pubkey,
+    pub sol_raised: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationReleasedEvent {
+    pub mint: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub escrow: Pubkey,
+    pub dex: MigrationDex,
+    pub excess_tokens: u64,
+    pub excess_token_destination: Pubkey,  // Default = burned
+    pub migration_fee: u64,
+    pub graduation_bonus: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationReleaseFailed {
+    pub mint: Pubkey,
+    pub error_code: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationEscrowClaimed {
+    pub mint: Pubkey,
+    pub migration_wallet: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,  // Sent from the escrow, before any Token-2022 transfer fee
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationRefundedEvent {
+    pub mint: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationRecordedEvent {
+    pub mint: Pubkey,
+    pub dex: MigrationDex,
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_handling: LpHandling,
+    pub timestamp: i64,
+}
 
 #[event]
-pub struct TradeEvent {
+pub struct RefundStartedEvent {
+    pub mint: Pubkey,
+    pub real_sol_reserves: u64,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedeemEvent {
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RefundClosedEvent {
+    pub mint: Pubkey,
+    pub unredeemed_tokens: u64,
+    pub sol_amount: u64,  // Unredeemed reserves swept to the fee recipient
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SymbolCollisionEvent {
+    pub mint: Pubkey,
+    pub symbol: String,
+    pub registered_mint: Pubkey,
+    pub registered_status: SymbolStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenProfileUpdated {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorProposed {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub pending_creator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorChanged {
+    pub mint: Pubkey,
+    pub old_creator: Pubkey,
+    pub new_creator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferFeesClaimedEvent {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolCreatedEvent {
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub lp_burned: u64,
+    pub excess_tokens: u64,
+    pub migration_fee: u64,
+    pub graduation_bonus: u64,
+    pub cranker: Pubkey,
+    pub crank_bounty: u64,
+    pub rent_reimbursed: u64,  // Pool account rent returned to the cranker from the reserves
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolSwapEvent {
     pub mint: Pubkey,
     pub trader: Pubkey,
     pub is_buy: bool,
@@ -1155,13 +6842,106 @@ pub struct TradeEvent {
     pub token_amount: u64,
     pub protocol_fee: u64,
     pub creator_fee: u64,
-    pub virtual_sol_reserves: u64,
-    pub virtual_token_reserves: u64,
+    pub sol_reserves: u64,
+    pub token_reserves: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct TokenCreatedEvent {
+pub struct PoolLiquidityEvent {
+    pub mint: Pubkey,
+    pub provider: Pubkey,
+    pub is_deposit: bool,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub lp_amount: u64,
+    pub timestamp: i64,
+}
+
+// Admin audit events - one per privileged change, with old/new value and signer
+
+#[event]
+pub struct AuthorityChanged {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OperatorChanged {
+    pub old_operator: Pubkey,
+    pub new_operator: Pubkey,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeConfigChanged {
+    pub old_fee_recipient: Pubkey,
+    pub new_fee_recipient: Pubkey,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ModeratorChanged {
+    pub old_moderator: Pubkey,
+    pub new_moderator: Pubkey,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CurveFlagsChanged {
+    pub mint: Pubkey,
+    pub old_flags: u8,
+    pub new_flags: u8,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseChanged {
+    pub mint: Pubkey,
+    pub old_paused: bool,
+    pub new_paused: bool,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationDestinationChanged {
+    pub kind: MigrationDestinationKind,
+    pub destination: Pubkey,
+    pub allowed: bool,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationDestinationQueued {
+    pub kind: MigrationDestinationKind,
+    pub destination: Pubkey,
+    pub activates_at: i64,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationFeesChanged {
+    pub old_fee_lamports: u64,
+    pub new_fee_lamports: u64,
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+    pub old_graduation_bonus: u64,
+    pub new_graduation_bonus: u64,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenMetadataUpdatedEvent {
     pub mint: Pubkey,
     pub creator: Pubkey,
     pub name: String,
@@ -1171,18 +6951,93 @@ pub struct TokenCreatedEvent {
 }
 
 #[event]
-pub struct GraduationEvent {
+pub struct MetadataPolicyChanged {
+    pub old_policy: MetadataPolicy,
+    pub new_policy: MetadataPolicy,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SymbolPolicyChanged {
+    pub old_policy: SymbolPolicy,
+    pub new_policy: SymbolPolicy,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SymbolStatusChanged {
+    pub symbol: String,
     pub mint: Pubkey,
-    pub sol_raised: u64,
+    pub old_status: SymbolStatus,
+    pub new_status: SymbolStatus,
+    pub signer: Pubkey,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct MigrationReleasedEvent {
+pub struct MetadataCharsetsChanged {
+    pub old_name_charset: u8,
+    pub new_name_charset: u8,
+    pub old_symbol_charset: u8,
+    pub new_symbol_charset: u8,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UriPrefixChanged {
+    pub prefix: String,
+    pub old_allowed: bool,
+    pub new_allowed: bool,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MaxTransferFeeChanged {
+    pub old_max_bps: u16,
+    pub new_max_bps: u16,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollectionCreated {
+    pub collection_mint: Pubkey,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenGroupCreated {
+    pub group_mint: Pubkey,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MetadataLockedEvent {
     pub mint: Pubkey,
-    pub sol_amount: u64,
-    pub token_amount: u64,
-    pub migration_wallet: Pubkey,
+    pub policy: MetadataPolicy,
+    pub update_authority: Pubkey,  // Default when immutable
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExcessTokenDestinationChanged {
+    pub old_destination: Pubkey,
+    pub new_destination: Pubkey,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigMigrated {
+    pub old_size: u64,
+    pub new_size: u64,
+    pub signer: Pubkey,
     pub timestamp: i64,
 }
 
@@ -1198,10 +7053,10 @@ pub enum ClawdVaultError {
     #[msg("Slippage tolerance exceeded")]
     SlippageExceeded,
     
-    #[msg("Token has already graduated to Raydium")]
+    #[msg("Token has already graduated")]
     AlreadyGraduated,
     
-    #[msg("Token has already been migrated to Raydium pool")]
+    #[msg("Token has already been migrated to a DEX pool")]
     AlreadyMigrated,
     
     #[msg("Token has not graduated yet")]
@@ -1227,4 +7082,406 @@ pub enum ClawdVaultError {
     
     #[msg("Invalid account data")]
     InvalidAccountData,
+    
+    #[msg("Trading is paused for this token")]
+    CurvePaused,
+    
+    #[msg("Token is in refund mode")]
+    CurveRefunding,
+    
+    #[msg("Bonding curve is closed")]
+    CurveClosed,
+    
+    #[msg("Invalid bonding curve status transition")]
+    InvalidStatusTransition,
+    
+    #[msg("Escrow can only be claimed together with finalize_migration")]
+    MigrationNotFinalized,
+    
+    #[msg("Pool is not owned by an allowed AMM or doesn't hold the migrated vaults")]
+    InvalidMigrationPool,
+    
+    #[msg("Migration timeout has not passed yet")]
+    MigrationTimeoutNotReached,
+    
+    #[msg("Migration wallet is not on the allow-list")]
+    MigrationDestinationNotAllowed,
+    
+    #[msg("Migration allow-list is full")]
+    TooManyMigrationDestinations,
+    
+    #[msg("Excess token account missing or not owned by the configured destination")]
+    InvalidExcessTokenAccount,
+    
+    #[msg("Invalid fee configuration")]
+    InvalidFeeConfig,
+    
+    #[msg("Batch accounts must be complete groups, up to the batch limit")]
+    InvalidBatch,
+    
+    #[msg("Graduation deadline must be in the future (or 0 for none)")]
+    InvalidGraduationDeadline,
+    
+    #[msg("Graduation deadline has passed")]
+    DeadlinePassed,
+    
+    #[msg("Graduation deadline has not passed yet")]
+    DeadlineNotReached,
+    
+    #[msg("Token metadata has already been locked")]
+    MetadataAlreadyLocked,
+    
+    #[msg("Token program does not match the curve's mint")]
+    InvalidTokenProgram,
+    
+    #[msg("Transfer fee exceeds the protocol cap")]
+    TransferFeeTooHigh,
+    
+    #[msg("Token has no transfer fee")]
+    NoTransferFee,
+    
+    #[msg("Collection accounts are required once the protocol collection exists")]
+    CollectionAccountsMissing,
+    
+    #[msg("Collection does not match the config")]
+    InvalidCollection,
+    
+    #[msg("Symbol is reserved or belongs to a verified token")]
+    SymbolReserved,
+    
+    #[msg("Symbol record is required by the symbol policy")]
+    SymbolRecordRequired,
+    
+    #[msg("Token name contains characters the metadata policy does not allow")]
+    InvalidNameCharacters,
+    
+    #[msg("Token symbol contains characters the metadata policy does not allow")]
+    InvalidSymbolCharacters,
+    
+    #[msg("Metadata URI does not start with an allowed prefix")]
+    UriNotAllowed,
+    
+    #[msg("Unknown character class in charset")]
+    InvalidCharset,
+    
+    #[msg("URI prefix is longer than 64 bytes")]
+    UriPrefixTooLong,
+    
+    #[msg("URI prefix allow-list is full")]
+    TooManyUriPrefixes,
+    
+    #[msg("Unknown curve flag")]
+    InvalidCurveFlags,
+    
+    #[msg("Buys are halted for this token; selling is still allowed")]
+    BuysHalted,
+    
+    #[msg("Profile link or handle too long")]
+    ProfileFieldTooLong,
+    
+    #[msg("Profile description too long (max 200 bytes)")]
+    DescriptionTooLong,
+    
+    #[msg("Curve is not migrating")]
+    NotMigrating,
+    
+    #[msg("Escrow must be claimed by the migration wallet earlier in the same transaction")]
+    EscrowNotClaimed,
+    
+    #[msg("Pools on this DEX can't be verified on-chain")]
+    UnsupportedMigrationDex,
+    
+    #[msg("LP tokens are not fully burned or locked")]
+    LpNotSecured,
+    
+    #[msg("Symbol must not be empty")]
+    EmptySymbol,
+    
+    #[msg("Token profile account must be passed exactly when profile fields are")]
+    InvalidProfileAccount,
+    
+    #[msg("Migration destination is not queued for approval")]
+    MigrationDestinationNotQueued,
+    
+    #[msg("Migration destination delay has not passed yet")]
+    TimelockNotElapsed,
+    
+    #[msg("Tokens are still circulating and the refund grace period has not passed")]
+    RefundGracePeriodActive,
+    
+    #[msg("Metadata URI prefix is empty")]
+    UriPrefixEmpty,
+    
+    #[msg("Rent payer of the released symbol record is missing or wrong")]
+    InvalidSymbolPayer,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(sol_reserves: u64, token_reserves: u64, lp_supply: u64) -> Pool {
+        Pool {
+            mint: Pubkey::default(),
+            lp_mint: Pubkey::default(),
+            sol_reserves,
+            token_reserves,
+            lp_supply,
+            created_at: 0,
+            bump: 0,
+            sol_vault_bump: 0,
+        }
+    }
+
+    fn curve(virtual_sol_reserves: u64, virtual_token_reserves: u64, real_token_reserves: u64) -> BondingCurve {
+        BondingCurve {
+            creator: Pubkey::default(),
+            mint: Pubkey::default(),
+            virtual_sol_reserves,
+            virtual_token_reserves,
+            real_sol_reserves: 0,
+            real_token_reserves,
+            token_total_supply: TOTAL_SUPPLY,
+            graduated: true,
+            migrated: false,
+            created_at: 0,
+            bump: 0,
+            sol_vault_bump: 0,
+            status: CurveStatus::Graduated,
+            migration_dex: None,
+            migration_pool: Pubkey::default(),
+            migration_lp_mint: Pubkey::default(),
+            migration_lp_handling: None,
+            migration_started_at: 0,
+            migration_sol_amount: 0,
+            migration_token_amount: 0,
+            migration_wallet: Pubkey::default(),
+            graduation_deadline: 0,
+            metadata_policy: None,
+            token_program: Pubkey::default(),
+            flags: 0,
+            pending_creator: Pubkey::default(),
+            migration_fees_paid: false,
+        }
+    }
+
+    #[test]
+    fn status_checks_report_the_current_state() {
+        let mut curve = curve(1, 1, 1);
+        assert!(curve.require_status(CurveStatus::Graduated).is_ok());
+        assert_eq!(
+            curve.require_status(CurveStatus::Migrating).unwrap_err(),
+            ClawdVaultError::NotMigrating.into()
+        );
+        curve.transition(CurveStatus::Migrated).unwrap();
+        assert!(curve.graduated && curve.migrated);
+        assert_eq!(
+            curve.require_status(CurveStatus::Graduated).unwrap_err(),
+            ClawdVaultError::AlreadyMigrated.into()
+        );
+        assert!(curve.transition(CurveStatus::Refunding).is_err());
+    }
+
+    #[test]
+    fn metadata_rights_follow_the_creator_unless_immutable() {
+        let mut c = curve(0, 0, 0);
+        assert!(metadata_follows_creator(&c) && !creator_holds_update_authority(&c));
+        c.metadata_policy = Some(MetadataPolicy::CreatorAuthority);
+        assert!(metadata_follows_creator(&c) && creator_holds_update_authority(&c));
+        c.metadata_policy = Some(MetadataPolicy::Immutable);
+        assert!(!metadata_follows_creator(&c) && !creator_holds_update_authority(&c));
+        
+        c.token_program = Token2022::id();
+        c.metadata_policy = Some(MetadataPolicy::CreatorAuthority);
+        assert!(!metadata_follows_creator(&c) && creator_holds_update_authority(&c));
+    }
+    
+    #[test]
+    fn only_trading_or_paused_curves_can_start_refunding() {
+        use CurveStatus::*;
+        for status in [Trading, Paused, Graduated, Migrating, Migrated, Refunding, Closed] {
+            assert_eq!(status.can_transition_to(Refunding), matches!(status, Trading | Paused), "{status:?}");
+            assert_eq!(status.has_graduated(), matches!(status, Graduated | Migrating | Migrated), "{status:?}");
+        }
+    }
+
+    #[test]
+    fn mul_div_rounds_down_and_up() {
+        assert_eq!(mul_div(10, 10, 3).unwrap(), 33);
+        assert_eq!(mul_div_ceil(10, 10, 3).unwrap(), 34);
+        assert_eq!(mul_div_ceil(10, 9, 3).unwrap(), 30);
+        // The u128 intermediate can't overflow, only the u64 result
+        assert_eq!(mul_div(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+        assert!(mul_div(u64::MAX, 2, 1).is_err());
+        assert!(mul_div_ceil(u64::MAX, 2, 1).is_err());
+    }
+
+    #[test]
+    fn mul_div_rejects_zero_denominator() {
+        assert!(mul_div(1, 1, 0).is_err());
+        assert!(mul_div_ceil(1, 1, 0).is_err());
+    }
+
+    #[test]
+    fn isqrt_is_floor() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(u64::MAX as u128 * u64::MAX as u128), u64::MAX as u128);
+        let root = isqrt(u128::MAX);
+        assert!(root.checked_mul(root).is_some() && (root + 1).checked_mul(root + 1).is_none());
+    }
+
+    #[test]
+    fn pool_fees_split_the_total_fee() {
+        let (protocol_fee, creator_fee) = pool_fees(1_000_000).unwrap();
+        assert_eq!(protocol_fee, 1_500);
+        assert_eq!(creator_fee, 1_000);
+        // Rounding never takes more than the total fee
+        for amount in [0, 1, 399, 400, 401, 6_667, u64::MAX] {
+            let (protocol_fee, creator_fee) = pool_fees(amount).unwrap();
+            assert_eq!(
+                protocol_fee + creator_fee,
+                mul_div(amount, POOL_TOTAL_FEE_BPS, BPS_DENOMINATOR).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn swap_out_keeps_the_product() {
+        let (sol_reserves, token_reserves) = (100_000_000_000u64, 200_000_000_000_000u64);
+        for sol_in in [1, 1_000, 1_000_000_000, 50_000_000_000] {
+            let tokens_out = swap_out(sol_reserves, token_reserves, sol_in).unwrap();
+            // Rounded down: k never shrinks
+            let k_before = sol_reserves as u128 * token_reserves as u128;
+            let k_after = (sol_reserves + sol_in) as u128 * (token_reserves - tokens_out) as u128;
+            assert!(k_after >= k_before);
+        }
+        // Dust in rounds to nothing out
+        assert_eq!(swap_out(1_000_000, 1, 1).unwrap(), 0);
+        // The pool can never be drained
+        assert!(swap_out(1, token_reserves, u64::MAX - 1).unwrap() < token_reserves);
+        assert!(swap_out(u64::MAX, 1, 1).is_err());
+    }
+
+    #[test]
+    fn deposit_rounds_up_and_withdraw_rounds_down() {
+        let pool = pool(1_000, 3_001, 100);
+        assert_eq!(pool.deposit_amounts(1).unwrap(), (10, 31));
+        assert_eq!(pool.withdraw_amounts(1).unwrap(), (10, 30));
+        // A full withdrawal returns the reserves exactly
+        assert_eq!(pool.withdraw_amounts(100).unwrap(), (1_000, 3_001));
+    }
+
+    #[test]
+    fn withdraw_is_bounded_by_lp_supply() {
+        assert!(pool(1_000, 1_000, 100).withdraw_amounts(101).is_err());
+        assert!(pool(0, 0, 0).withdraw_amounts(1).is_err());
+        assert!(pool(0, 0, 0).deposit_amounts(1).is_err());
+    }
+
+    #[test]
+    fn listing_token_amount_matches_the_final_price() {
+        // 150 SOL virtual against 200M tokens: 1 SOL lists ~1.33M tokens, rounded down
+        let curve = curve(150_000_000_000, 200_000_000_000_000, 300_000_000_000_000);
+        assert_eq!(curve.listing_token_amount(1_000_000_000).unwrap(), 1_333_333_333_333);
+        assert_eq!(curve.listing_token_amount(0).unwrap(), 0);
+        // Listing never prices tokens below the curve
+        let sol = 120_000_000_000;
+        let tokens = curve.listing_token_amount(sol).unwrap() as u128;
+        assert!(tokens * curve.virtual_sol_reserves as u128 <= sol as u128 * curve.virtual_token_reserves as u128);
+    }
+
+    #[test]
+    fn listing_token_amount_is_capped_by_real_reserves() {
+        let curve = curve(150_000_000_000, 200_000_000_000_000, 1_000);
+        assert_eq!(curve.listing_token_amount(120_000_000_000).unwrap(), 1_000);
+        assert!(curve.listing_token_amount(u64::MAX).is_err());
+        let mut empty = curve;
+        empty.virtual_sol_reserves = 0;
+        assert!(empty.listing_token_amount(1).is_err());
+    }
+
+    #[test]
+    fn circulating_supply_leaves_out_vault_and_withheld_fees() {
+        assert_eq!(circulating_supply(1_000, 600, 10).unwrap(), 390);
+        assert_eq!(circulating_supply(1_000, 1_000, 0).unwrap(), 0);
+        assert!(circulating_supply(1_000, 995, 10).is_err());
+    }
+
+    #[test]
+    fn redeem_rounds_down_and_the_last_redeemer_takes_the_rest() {
+        let (mut sol_reserves, mut circulating) = (1_000_000_001u64, 3u64);
+        let first = redeem_amount(1, sol_reserves, circulating).unwrap();
+        assert_eq!(first, 333_333_333);
+        sol_reserves -= first;
+        circulating -= 1;
+        let second = redeem_amount(1, sol_reserves, circulating).unwrap();
+        assert_eq!(second, 333_333_334);
+        sol_reserves -= second;
+        circulating -= 1;
+        // Rounding dust goes to the last holder, nothing is stranded
+        assert_eq!(redeem_amount(1, sol_reserves, circulating).unwrap(), sol_reserves);
+    }
+
+    #[test]
+    fn redeem_excludes_withheld_fees_from_the_denominator() {
+        // 100 tokens out, 10 of them withheld fees in the mint: 90 holders share the SOL
+        let circulating = circulating_supply(1_000, 900, 10).unwrap();
+        assert_eq!(redeem_amount(90, 9_000, circulating).unwrap(), 9_000);
+        assert_eq!(redeem_amount(45, 9_000, circulating).unwrap(), 4_500);
+    }
+
+    #[test]
+    fn redeem_rejects_zero_supply_and_more_than_circulating() {
+        assert!(redeem_amount(1, 1_000, 0).is_err());
+        assert!(redeem_amount(2, 1_000, 1).is_err());
+        assert_eq!(redeem_amount(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn charset_allows_only_the_selected_classes() {
+        assert!(charset_allows(0, "anything \u{200b} goes"));
+        assert!(charset_allows(CHARSET_UPPER | CHARSET_DIGIT, "CLAWD2"));
+        assert!(!charset_allows(CHARSET_UPPER | CHARSET_DIGIT, "Clawd"));
+        assert!(!charset_allows(CHARSET_UPPER, "CLAWD DAO"));
+        assert!(charset_allows(CHARSET_UPPER | CHARSET_SPACE, "CLAWD DAO"));
+        assert!(charset_allows(CHARSET_ALL, "Claw'd Vault #1!"));
+        assert!(charset_allows(CHARSET_UPPER, ""));
+    }
+
+    #[test]
+    fn charset_rejects_look_alikes_and_invisible_characters() {
+        // Zero-width space, Cyrillic "А", full-width "Ｃ" and a tab
+        for value in ["CLA\u{200b}WD", "\u{410}BC", "\u{ff23}LAWD", "CLAWD\t"] {
+            assert!(!charset_allows(CHARSET_ALL, value), "{value:?}");
+        }
+    }
+
+    fn symbol_record(mint: Pubkey, status: SymbolStatus) -> SymbolRecord {
+        SymbolRecord { symbol: "CLAWD".to_string(), mint, payer: Pubkey::default(), status, bump: 0 }
+    }
+
+    #[test]
+    fn symbol_owner_never_collides_with_its_own_record() {
+        let owner = Pubkey::new_unique();
+        for status in [SymbolStatus::Claimed, SymbolStatus::Verified, SymbolStatus::Reserved] {
+            let record = symbol_record(owner, status);
+            assert!(!symbol_collides(SymbolPolicy::Reject, &record, owner).unwrap());
+            assert!(!symbol_collides(SymbolPolicy::Flag, &record, owner).unwrap());
+        }
+    }
+
+    #[test]
+    fn symbol_collides_with_someone_elses_verified_or_reserved_record() {
+        let (owner, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let verified = symbol_record(owner, SymbolStatus::Verified);
+        assert!(symbol_collides(SymbolPolicy::Reject, &verified, other).is_err());
+        assert!(symbol_collides(SymbolPolicy::Flag, &verified, other).unwrap());
+        assert!(!symbol_collides(SymbolPolicy::Off, &verified, other).unwrap());
+        // A plain first-come claim only tracks the ticker
+        let claimed = symbol_record(owner, SymbolStatus::Claimed);
+        assert!(!symbol_collides(SymbolPolicy::Reject, &claimed, other).unwrap());
+    }
 }
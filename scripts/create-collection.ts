@@ -0,0 +1,146 @@
+/**
+ * Create Collection
+ * 
+ * Creates the protocol collection NFT. Once it exists, every `create_token` launch
+ * is verified into it, so wallets can tell genuine ClawdVault tokens from impostors.
+ * Must be run by the protocol authority (once).
+ * 
+ * Usage: 
+ *   npx tsx scripts/create-collection.ts <metadata_uri> [name] [symbol]
+ *   MAINNET=1 npx tsx scripts/create-collection.ts <metadata_uri> [name] [symbol]
+ */
+
+import { 
+  Connection, 
+  Keypair, 
+  PublicKey, 
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  clusterApiUrl, 
+  sendAndConfirmTransaction,
+} from '@solana/web3.js';
+import * as fs from 'fs';
+import * as crypto from 'crypto';
+
+const PROGRAM_ID = new PublicKey('GUyF2TVe32Cid4iGVt2F6wPYDhLSVmTUZBj2974outYM');
+const TOKEN_PROGRAM_ID = new PublicKey('TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA');
+const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey('ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL');
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+const RENT_SYSVAR_ID = new PublicKey('SysvarRent111111111111111111111111111111111');
+
+// Get connection based on env
+const isMainnet = process.env.MAINNET === '1';
+const rpcUrl = isMainnet 
+  ? (process.env.SOLANA_RPC_URL || 'https://api.mainnet-beta.solana.com')
+  : clusterApiUrl('devnet');
+const connection = new Connection(rpcUrl, 'confirmed');
+
+console.log(`Network: ${isMainnet ? 'MAINNET' : 'devnet'}`);
+console.log(`RPC: ${rpcUrl}`);
+
+// Load authority wallet
+const walletPath = process.env.WALLET_PATH || process.env.HOME + '/.config/solana/claw-wallet.json';
+const authority = Keypair.fromSecretKey(
+  Uint8Array.from(JSON.parse(fs.readFileSync(walletPath, 'utf-8')))
+);
+
+console.log('Authority wallet:', authority.publicKey.toBase58());
+
+// Compute discriminator
+function getDiscriminator(name: string): Buffer {
+  return crypto.createHash('sha256').update(`global:${name}`).digest().slice(0, 8);
+}
+
+// Borsh string: u32 length prefix + bytes
+function encodeString(value: string): Buffer {
+  const bytes = Buffer.from(value);
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(bytes.length);
+  return Buffer.concat([len, bytes]);
+}
+
+async function main() {
+  const [uri, name = 'ClawdVault', symbol = 'CLAWD'] = process.argv.slice(2);
+  
+  if (!uri) {
+    console.log('\nUsage: npx tsx scripts/create-collection.ts <metadata_uri> [name] [symbol]');
+    return;
+  }
+
+  // Find PDAs
+  const [configPDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+  const [eventAuthorityPDA] = PublicKey.findProgramAddressSync([Buffer.from('__event_authority')], PROGRAM_ID);
+  const [collectionMint] = PublicKey.findProgramAddressSync([Buffer.from('collection')], PROGRAM_ID);
+  const [collectionTokenAccount] = PublicKey.findProgramAddressSync(
+    [configPDA.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), collectionMint.toBuffer()],
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+  const [collectionMetadata] = PublicKey.findProgramAddressSync(
+    [Buffer.from('metadata'), TOKEN_METADATA_PROGRAM_ID.toBuffer(), collectionMint.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+  const [collectionMasterEdition] = PublicKey.findProgramAddressSync(
+    [Buffer.from('metadata'), TOKEN_METADATA_PROGRAM_ID.toBuffer(), collectionMint.toBuffer(), Buffer.from('edition')],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+  console.log('Config PDA:', configPDA.toBase58());
+  console.log('Collection mint:', collectionMint.toBase58());
+
+  const configAccount = await connection.getAccountInfo(configPDA);
+  if (!configAccount) {
+    console.error('❌ Config not found! Protocol may not be initialized.');
+    return;
+  }
+
+  const currentAuthority = new PublicKey(configAccount.data.slice(8, 40));
+  if (!currentAuthority.equals(authority.publicKey)) {
+    console.error('\n❌ Your wallet is not the authority!');
+    console.error(`   Your wallet: ${authority.publicKey.toBase58()}`);
+    console.error(`   On-chain authority: ${currentAuthority.toBase58()}`);
+    return;
+  }
+
+  if (await connection.getAccountInfo(collectionMint)) {
+    console.log('\n⚠️ Collection already exists. Nothing to do.');
+    return;
+  }
+
+  const data = Buffer.concat([
+    getDiscriminator('create_collection'),
+    encodeString(name),
+    encodeString(symbol),
+    encodeString(uri),
+  ]);
+
+  const instruction = new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: authority.publicKey, isSigner: true, isWritable: true },
+      { pubkey: configPDA, isSigner: false, isWritable: true },
+      { pubkey: collectionMint, isSigner: false, isWritable: true },
+      { pubkey: collectionTokenAccount, isSigner: false, isWritable: true },
+      { pubkey: collectionMetadata, isSigner: false, isWritable: true },
+      { pubkey: collectionMasterEdition, isSigner: false, isWritable: true },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: RENT_SYSVAR_ID, isSigner: false, isWritable: false },
+      { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+      { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+    ],
+    data,
+  });
+
+  const tx = new Transaction().add(instruction);
+  tx.feePayer = authority.publicKey;
+  tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+
+  console.log('\n📤 Sending transaction...');
+  const signature = await sendAndConfirmTransaction(connection, tx, [authority]);
+  console.log('✅ Collection created!');
+  console.log('   Signature:', signature);
+}
+
+main().catch(console.error);
@@ -0,0 +1,103 @@
+/**
+ * Create Token Group
+ * 
+ * Creates the protocol Token-2022 group. Once it exists, every `create_token_2022`
+ * launch joins it, so wallets can tell genuine ClawdVault tokens from impostors.
+ * Must be run by the protocol authority (once).
+ * 
+ * Usage: 
+ *   npx tsx scripts/create-token-group.ts
+ *   MAINNET=1 npx tsx scripts/create-token-group.ts
+ */
+
+import { 
+  Connection, 
+  Keypair, 
+  PublicKey, 
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  clusterApiUrl, 
+  sendAndConfirmTransaction,
+} from '@solana/web3.js';
+import * as fs from 'fs';
+import * as crypto from 'crypto';
+
+const PROGRAM_ID = new PublicKey('GUyF2TVe32Cid4iGVt2F6wPYDhLSVmTUZBj2974outYM');
+const TOKEN_2022_PROGRAM_ID = new PublicKey('TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb');
+
+// Get connection based on env
+const isMainnet = process.env.MAINNET === '1';
+const rpcUrl = isMainnet 
+  ? (process.env.SOLANA_RPC_URL || 'https://api.mainnet-beta.solana.com')
+  : clusterApiUrl('devnet');
+const connection = new Connection(rpcUrl, 'confirmed');
+
+console.log(`Network: ${isMainnet ? 'MAINNET' : 'devnet'}`);
+console.log(`RPC: ${rpcUrl}`);
+
+// Load authority wallet
+const walletPath = process.env.WALLET_PATH || process.env.HOME + '/.config/solana/claw-wallet.json';
+const authority = Keypair.fromSecretKey(
+  Uint8Array.from(JSON.parse(fs.readFileSync(walletPath, 'utf-8')))
+);
+
+console.log('Authority wallet:', authority.publicKey.toBase58());
+
+// Compute discriminator
+function getDiscriminator(name: string): Buffer {
+  return crypto.createHash('sha256').update(`global:${name}`).digest().slice(0, 8);
+}
+
+async function main() {
+  // Find PDAs
+  const [configPDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+  const [eventAuthorityPDA] = PublicKey.findProgramAddressSync([Buffer.from('__event_authority')], PROGRAM_ID);
+  const [tokenGroup] = PublicKey.findProgramAddressSync([Buffer.from('token_group')], PROGRAM_ID);
+  console.log('Config PDA:', configPDA.toBase58());
+  console.log('Token group mint:', tokenGroup.toBase58());
+
+  const configAccount = await connection.getAccountInfo(configPDA);
+  if (!configAccount) {
+    console.error('❌ Config not found! Protocol may not be initialized.');
+    return;
+  }
+
+  const currentAuthority = new PublicKey(configAccount.data.slice(8, 40));
+  if (!currentAuthority.equals(authority.publicKey)) {
+    console.error('\n❌ Your wallet is not the authority!');
+    console.error(`   Your wallet: ${authority.publicKey.toBase58()}`);
+    console.error(`   On-chain authority: ${currentAuthority.toBase58()}`);
+    return;
+  }
+
+  if (await connection.getAccountInfo(tokenGroup)) {
+    console.log('\n⚠️ Token group already exists. Nothing to do.');
+    return;
+  }
+
+  const instruction = new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: authority.publicKey, isSigner: true, isWritable: true },
+      { pubkey: configPDA, isSigner: false, isWritable: false },
+      { pubkey: tokenGroup, isSigner: false, isWritable: true },
+      { pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+      { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+    ],
+    data: getDiscriminator('create_token_group'),
+  });
+
+  const tx = new Transaction().add(instruction);
+  tx.feePayer = authority.publicKey;
+  tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+
+  console.log('\n📤 Sending transaction...');
+  const signature = await sendAndConfirmTransaction(connection, tx, [authority]);
+  console.log('✅ Token group created!');
+  console.log('   Signature:', signature);
+}
+
+main().catch(console.error);
@@ -49,10 +49,15 @@ if [ "$BALANCE" -lt "$MIN_BALANCE" ]; then
     fi
 fi
 
-# Build
+# Build (devnet keeps msg! logging, mainnet strips it to save compute units)
+BUILD_FLAGS=""
+if [ "$NETWORK" = "devnet" ]; then
+    BUILD_FLAGS="-- --features verbose-logs"
+fi
+
 echo "🔨 Building program..."
 cd "$(dirname "$0")/.."
-anchor build
+anchor build $BUILD_FLAGS
 
 # Get program keypair
 PROGRAM_KEYPAIR="target/deploy/clawdvault-keypair.json"
@@ -72,7 +77,7 @@ sed -i.bak "s/clawdvault = \".*\"/clawdvault = \"$PROGRAM_ID\"/" Anchor.toml
 
 # Rebuild with correct ID
 echo "🔨 Rebuilding with correct program ID..."
-anchor build
+anchor build $BUILD_FLAGS
 
 # Deploy
 echo "🚀 Deploying to $NETWORK..."
@@ -0,0 +1,139 @@
+/**
+ * Set Curve Flags
+ *
+ * Replaces the moderation flags on a token's bonding curve, or sets the
+ * moderator wallet. Flags can be set by the moderator or the protocol
+ * authority; the moderator can only be set by the authority.
+ *
+ * Flags (comma separated, or "none"): impersonation, delisted, nsfw, verified, buys-halted
+ *
+ * Usage:
+ *   npx tsx scripts/set-curve-flags.ts <mint> <flags>
+ *   npx tsx scripts/set-curve-flags.ts moderator <moderator_pubkey>
+ *   MAINNET=1 npx tsx scripts/set-curve-flags.ts ...
+ *
+ * Example (delist and let holders exit):
+ *   npx tsx scripts/set-curve-flags.ts <mint> delisted,buys-halted
+ */
+
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  Transaction,
+  TransactionInstruction,
+  clusterApiUrl,
+  sendAndConfirmTransaction,
+} from '@solana/web3.js';
+import * as fs from 'fs';
+import * as crypto from 'crypto';
+
+const PROGRAM_ID = new PublicKey('GUyF2TVe32Cid4iGVt2F6wPYDhLSVmTUZBj2974outYM');
+
+// Must match the FLAG_* bits in the program
+const FLAG_BITS: Record<string, number> = {
+  impersonation: 1 << 0,
+  delisted: 1 << 1,
+  nsfw: 1 << 2,
+  verified: 1 << 3,
+  'buys-halted': 1 << 4,
+};
+
+// Get connection based on env
+const isMainnet = process.env.MAINNET === '1';
+const rpcUrl = isMainnet
+  ? (process.env.SOLANA_RPC_URL || 'https://api.mainnet-beta.solana.com')
+  : clusterApiUrl('devnet');
+const connection = new Connection(rpcUrl, 'confirmed');
+
+console.log(`Network: ${isMainnet ? 'MAINNET' : 'devnet'}`);
+console.log(`RPC: ${rpcUrl}`);
+
+// Load moderator (or authority) wallet
+const walletPath = process.env.WALLET_PATH || process.env.HOME + '/.config/solana/claw-wallet.json';
+const signer = Keypair.fromSecretKey(
+  Uint8Array.from(JSON.parse(fs.readFileSync(walletPath, 'utf-8')))
+);
+
+console.log('Signer wallet:', signer.publicKey.toBase58());
+
+// Compute discriminator
+function getDiscriminator(name: string): Buffer {
+  return crypto.createHash('sha256').update(`global:${name}`).digest().slice(0, 8);
+}
+
+function parseFlags(arg: string): number {
+  if (arg === 'none') return 0;
+  return arg.split(',').reduce((mask, name) => {
+    const bit = FLAG_BITS[name.trim()];
+    if (bit === undefined) throw new Error(`Unknown flag: ${name}`);
+    return mask | bit;
+  }, 0);
+}
+
+async function main() {
+  const [first, second] = process.argv.slice(2);
+
+  if (!first || !second) {
+    console.log('\nUsage:');
+    console.log('  npx tsx scripts/set-curve-flags.ts <mint> <flags>');
+    console.log('  npx tsx scripts/set-curve-flags.ts moderator <moderator_pubkey>');
+    console.log('\nFlags: impersonation, delisted, nsfw, verified, buys-halted (comma separated) or "none"');
+    return;
+  }
+
+  const [configPDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+  const [eventAuthorityPDA] = PublicKey.findProgramAddressSync([Buffer.from('__event_authority')], PROGRAM_ID);
+  console.log('Config PDA:', configPDA.toBase58());
+
+  let instruction: TransactionInstruction;
+
+  if (first === 'moderator') {
+    const newModerator = new PublicKey(second);
+    console.log('\n📝 Setting moderator:', newModerator.toBase58());
+
+    instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: signer.publicKey, isSigner: true, isWritable: false },
+        { pubkey: configPDA, isSigner: false, isWritable: true },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.concat([getDiscriminator('set_moderator'), newModerator.toBuffer()]),
+    });
+  } else {
+    const mint = new PublicKey(first);
+    const flags = parseFlags(second);
+    const [curvePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from('bonding_curve'), mint.toBuffer()],
+      PROGRAM_ID
+    );
+
+    console.log(`\n📝 Setting flags on ${mint.toBase58()}: ${second} (${flags})`);
+    console.log('   Curve:', curvePDA.toBase58());
+
+    instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: signer.publicKey, isSigner: true, isWritable: false },
+        { pubkey: configPDA, isSigner: false, isWritable: false },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.concat([getDiscriminator('set_curve_flags'), Buffer.from([flags])]),
+    });
+  }
+
+  const tx = new Transaction().add(instruction);
+  tx.feePayer = signer.publicKey;
+  tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+
+  console.log('\n📤 Sending transaction...');
+  const signature = await sendAndConfirmTransaction(connection, tx, [signer]);
+  console.log('✅ Done!');
+  console.log('   Signature:', signature);
+}
+
+main().catch(console.error);
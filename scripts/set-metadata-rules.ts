@@ -0,0 +1,140 @@
+/**
+ * Set Metadata Rules
+ *
+ * Sets the character classes allowed in token names and symbols, and manages
+ * the metadata URI prefix allow-list. Must be run by the protocol authority.
+ *
+ * Character classes (comma separated, or "any"): upper, lower, digit, space, punct
+ *
+ * Usage:
+ *   npx tsx scripts/set-metadata-rules.ts charsets <name_classes> <symbol_classes>
+ *   npx tsx scripts/set-metadata-rules.ts allow-uri <prefix>
+ *   npx tsx scripts/set-metadata-rules.ts remove-uri <prefix>
+ *   MAINNET=1 npx tsx scripts/set-metadata-rules.ts ...
+ *
+ * Example:
+ *   npx tsx scripts/set-metadata-rules.ts charsets upper,lower,digit,space,punct upper,digit
+ *   npx tsx scripts/set-metadata-rules.ts allow-uri https://arweave.net/
+ */
+
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  Transaction,
+  TransactionInstruction,
+  clusterApiUrl,
+  sendAndConfirmTransaction,
+} from '@solana/web3.js';
+import * as fs from 'fs';
+import * as crypto from 'crypto';
+
+const PROGRAM_ID = new PublicKey('GUyF2TVe32Cid4iGVt2F6wPYDhLSVmTUZBj2974outYM');
+
+// Must match the CHARSET_* bits in the program
+const CHARSET_BITS: Record<string, number> = {
+  upper: 1 << 0,
+  lower: 1 << 1,
+  digit: 1 << 2,
+  space: 1 << 3,
+  punct: 1 << 4,
+};
+
+// Get connection based on env
+const isMainnet = process.env.MAINNET === '1';
+const rpcUrl = isMainnet
+  ? (process.env.SOLANA_RPC_URL || 'https://api.mainnet-beta.solana.com')
+  : clusterApiUrl('devnet');
+const connection = new Connection(rpcUrl, 'confirmed');
+
+console.log(`Network: ${isMainnet ? 'MAINNET' : 'devnet'}`);
+console.log(`RPC: ${rpcUrl}`);
+
+// Load authority wallet
+const walletPath = process.env.WALLET_PATH || process.env.HOME + '/.config/solana/claw-wallet.json';
+const authority = Keypair.fromSecretKey(
+  Uint8Array.from(JSON.parse(fs.readFileSync(walletPath, 'utf-8')))
+);
+
+console.log('Authority wallet:', authority.publicKey.toBase58());
+
+// Compute discriminator
+function getDiscriminator(name: string): Buffer {
+  return crypto.createHash('sha256').update(`global:${name}`).digest().slice(0, 8);
+}
+
+function parseCharset(arg: string): number {
+  if (arg === 'any') return 0;
+  return arg.split(',').reduce((mask, name) => {
+    const bit = CHARSET_BITS[name.trim()];
+    if (bit === undefined) throw new Error(`Unknown character class: ${name}`);
+    return mask | bit;
+  }, 0);
+}
+
+function usage() {
+  console.log('\nUsage:');
+  console.log('  npx tsx scripts/set-metadata-rules.ts charsets <name_classes> <symbol_classes>');
+  console.log('  npx tsx scripts/set-metadata-rules.ts allow-uri <prefix>');
+  console.log('  npx tsx scripts/set-metadata-rules.ts remove-uri <prefix>');
+  console.log('\nCharacter classes: upper, lower, digit, space, punct (comma separated) or "any"');
+}
+
+async function main() {
+  const [command, ...args] = process.argv.slice(2);
+
+  const [configPDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+  const [eventAuthorityPDA] = PublicKey.findProgramAddressSync([Buffer.from('__event_authority')], PROGRAM_ID);
+  console.log('Config PDA:', configPDA.toBase58());
+
+  let data: Buffer;
+
+  if (command === 'charsets' && args.length === 2) {
+    const nameCharset = parseCharset(args[0]);
+    const symbolCharset = parseCharset(args[1]);
+    console.log(`\n📝 Setting charsets: name ${args[0]} (${nameCharset}), symbol ${args[1]} (${symbolCharset})`);
+
+    data = Buffer.concat([
+      getDiscriminator('set_metadata_charsets'),
+      Buffer.from([nameCharset, symbolCharset]),
+    ]);
+  } else if ((command === 'allow-uri' || command === 'remove-uri') && args.length === 1) {
+    const allowed = command === 'allow-uri';
+    const prefixBytes = Buffer.from(args[0]);
+    const prefixLen = Buffer.alloc(4);
+    prefixLen.writeUInt32LE(prefixBytes.length);
+    console.log(`\n📝 ${allowed ? 'Allowing' : 'Removing'} URI prefix: ${args[0]}`);
+
+    data = Buffer.concat([
+      getDiscriminator('set_uri_prefix'),
+      prefixLen,
+      prefixBytes,
+      Buffer.from([allowed ? 1 : 0]),
+    ]);
+  } else {
+    usage();
+    return;
+  }
+
+  const instruction = new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+      { pubkey: configPDA, isSigner: false, isWritable: true },
+      { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+      { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+    ],
+    data,
+  });
+
+  const tx = new Transaction().add(instruction);
+  tx.feePayer = authority.publicKey;
+  tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+
+  console.log('\n📤 Sending transaction...');
+  const signature = await sendAndConfirmTransaction(connection, tx, [authority]);
+  console.log('✅ Done!');
+  console.log('   Signature:', signature);
+}
+
+main().catch(console.error);
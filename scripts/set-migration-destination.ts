@@ -0,0 +1,120 @@
+/**
+ * Set Migration Destination
+ * 
+ * Queues, applies or revokes an entry on the config's migration allow-lists:
+ *   wallet - may claim migration escrows (the MIGRATION_WALLET used for pool creation)
+ *   amm    - AMM program that may own finalized migration pools
+ * Approvals are queued on-chain and can only be applied 48 hours later;
+ * revocations take effect immediately. Must be run by the protocol authority.
+ * 
+ * Usage: 
+ *   npx tsx scripts/set-migration-destination.ts <wallet|amm> <pubkey> [apply|revoke]
+ *   MAINNET=1 npx tsx scripts/set-migration-destination.ts <wallet|amm> <pubkey> [apply|revoke]
+ */
+
+import { 
+  Connection, 
+  Keypair, 
+  PublicKey, 
+  Transaction,
+  TransactionInstruction,
+  clusterApiUrl, 
+  sendAndConfirmTransaction,
+} from '@solana/web3.js';
+import * as fs from 'fs';
+import * as crypto from 'crypto';
+
+const PROGRAM_ID = new PublicKey('GUyF2TVe32Cid4iGVt2F6wPYDhLSVmTUZBj2974outYM');
+
+// Matches MigrationDestinationKind enum in program
+const KINDS: Record<string, number> = { wallet: 0, amm: 1 };
+
+// Get connection based on env
+const isMainnet = process.env.MAINNET === '1';
+const rpcUrl = isMainnet 
+  ? (process.env.SOLANA_RPC_URL || 'https://api.mainnet-beta.solana.com')
+  : clusterApiUrl('devnet');
+const connection = new Connection(rpcUrl, 'confirmed');
+
+console.log(`Network: ${isMainnet ? 'MAINNET' : 'devnet'}`);
+console.log(`RPC: ${rpcUrl}`);
+
+// Load authority wallet
+const walletPath = process.env.WALLET_PATH || process.env.HOME + '/.config/solana/claw-wallet.json';
+const authority = Keypair.fromSecretKey(
+  Uint8Array.from(JSON.parse(fs.readFileSync(walletPath, 'utf-8')))
+);
+
+console.log('Authority wallet:', authority.publicKey.toBase58());
+
+// Compute discriminator
+function getDiscriminator(name: string): Buffer {
+  return crypto.createHash('sha256').update(`global:${name}`).digest().slice(0, 8);
+}
+
+async function main() {
+  const [kindArg, destinationArg, actionArg] = process.argv.slice(2);
+  
+  if (!destinationArg || !(kindArg in KINDS) || (actionArg && !['apply', 'revoke'].includes(actionArg))) {
+    console.log('\nUsage: npx tsx scripts/set-migration-destination.ts <wallet|amm> <pubkey> [apply|revoke]');
+    return;
+  }
+
+  const destination = new PublicKey(destinationArg);
+  const action = actionArg || 'queue';
+  console.log(`${{ queue: 'Queueing', apply: 'Applying', revoke: 'Revoking' }[action]} ${kindArg}:`, destination.toBase58());
+
+  // Find config PDA
+  const [configPDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+  const [eventAuthorityPDA] = PublicKey.findProgramAddressSync([Buffer.from('__event_authority')], PROGRAM_ID);
+  console.log('Config PDA:', configPDA.toBase58());
+
+  const configAccount = await connection.getAccountInfo(configPDA);
+  if (!configAccount) {
+    console.error('❌ Config not found! Protocol may not be initialized.');
+    return;
+  }
+
+  const currentAuthority = new PublicKey(configAccount.data.slice(8, 40));
+  if (!currentAuthority.equals(authority.publicKey)) {
+    console.error('\n❌ Your wallet is not the authority!');
+    console.error(`   Your wallet: ${authority.publicKey.toBase58()}`);
+    console.error(`   On-chain authority: ${currentAuthority.toBase58()}`);
+    return;
+  }
+
+  const data = action === 'apply'
+    ? Buffer.concat([
+        getDiscriminator('apply_migration_destination'),
+        Buffer.from([KINDS[kindArg]]),
+        destination.toBuffer(),
+      ])
+    : Buffer.concat([
+        getDiscriminator('set_migration_destination'),
+        Buffer.from([KINDS[kindArg]]),
+        destination.toBuffer(),
+        Buffer.from([action === 'revoke' ? 0 : 1]),
+      ]);
+
+  const instruction = new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+      { pubkey: configPDA, isSigner: false, isWritable: true },
+      { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+      { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+    ],
+    data,
+  });
+
+  const tx = new Transaction().add(instruction);
+  tx.feePayer = authority.publicKey;
+  tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+
+  console.log('\n📤 Sending transaction...');
+  const signature = await sendAndConfirmTransaction(connection, tx, [authority]);
+  console.log(`✅ Migration destination ${{ queue: 'queued (apply it in 48 hours)', apply: 'approved', revoke: 'revoked' }[action]}!`);
+  console.log('   Signature:', signature);
+}
+
+main().catch(console.error);
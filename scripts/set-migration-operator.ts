@@ -62,6 +62,7 @@ async function main() {
 
   // Find config PDA
   const [configPDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+  const [eventAuthorityPDA] = PublicKey.findProgramAddressSync([Buffer.from('__event_authority')], PROGRAM_ID);
   console.log('Config PDA:', configPDA.toBase58());
 
   // Check current config
@@ -111,6 +112,8 @@ async function main() {
     keys: [
       { pubkey: authority.publicKey, isSigner: true, isWritable: false },
       { pubkey: configPDA, isSigner: false, isWritable: true },
+      { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+      { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
     ],
     data,
   });
@@ -0,0 +1,146 @@
+/**
+ * Manage the Ticker Registry
+ *
+ * Sets the registry policy for symbol collisions, or reserves / verifies a ticker.
+ * Must be run by the protocol authority.
+ *
+ * Usage:
+ *   npx tsx scripts/set-symbol-status.ts policy <off|flag|reject>
+ *   npx tsx scripts/set-symbol-status.ts <SYMBOL> <claimed|verified|reserved> [mint_pubkey]
+ *   MAINNET=1 npx tsx scripts/set-symbol-status.ts ...
+ */
+
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  clusterApiUrl,
+  sendAndConfirmTransaction,
+} from '@solana/web3.js';
+import * as fs from 'fs';
+import * as crypto from 'crypto';
+
+const PROGRAM_ID = new PublicKey('GUyF2TVe32Cid4iGVt2F6wPYDhLSVmTUZBj2974outYM');
+
+const POLICIES = ['off', 'flag', 'reject'];
+const STATUSES = ['claimed', 'verified', 'reserved'];
+
+// Get connection based on env
+const isMainnet = process.env.MAINNET === '1';
+const rpcUrl = isMainnet
+  ? (process.env.SOLANA_RPC_URL || 'https://api.mainnet-beta.solana.com')
+  : clusterApiUrl('devnet');
+const connection = new Connection(rpcUrl, 'confirmed');
+
+console.log(`Network: ${isMainnet ? 'MAINNET' : 'devnet'}`);
+console.log(`RPC: ${rpcUrl}`);
+
+// Load authority wallet
+const walletPath = process.env.WALLET_PATH || process.env.HOME + '/.config/solana/claw-wallet.json';
+const authority = Keypair.fromSecretKey(
+  Uint8Array.from(JSON.parse(fs.readFileSync(walletPath, 'utf-8')))
+);
+
+console.log('Authority wallet:', authority.publicKey.toBase58());
+
+// Compute discriminator
+function getDiscriminator(name: string): Buffer {
+  return crypto.createHash('sha256').update(`global:${name}`).digest().slice(0, 8);
+}
+
+function usage() {
+  console.log('\nUsage:');
+  console.log('  npx tsx scripts/set-symbol-status.ts policy <off|flag|reject>');
+  console.log('  npx tsx scripts/set-symbol-status.ts <SYMBOL> <claimed|verified|reserved> [mint_pubkey]');
+}
+
+async function main() {
+  const [first, second, third] = process.argv.slice(2);
+
+  if (!first || !second) {
+    usage();
+    return;
+  }
+
+  const [configPDA] = PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+  const [eventAuthorityPDA] = PublicKey.findProgramAddressSync([Buffer.from('__event_authority')], PROGRAM_ID);
+  console.log('Config PDA:', configPDA.toBase58());
+
+  let instruction: TransactionInstruction;
+
+  if (first === 'policy') {
+    const policy = POLICIES.indexOf(second.toLowerCase());
+    if (policy < 0) {
+      usage();
+      return;
+    }
+
+    console.log(`\n📝 Setting symbol policy to ${POLICIES[policy]}...`);
+
+    instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+        { pubkey: configPDA, isSigner: false, isWritable: true },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.concat([getDiscriminator('set_symbol_policy'), Buffer.from([policy])]),
+    });
+  } else {
+    const status = STATUSES.indexOf(second.toLowerCase());
+    if (status < 0) {
+      usage();
+      return;
+    }
+
+    // Registry records are keyed by the ASCII-uppercased symbol, as on-chain
+    const symbol = first.replace(/[a-z]/g, (c) => c.toUpperCase());
+    const mint = third ? new PublicKey(third) : PublicKey.default;
+    const [symbolRecordPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from('symbol'), Buffer.from(symbol)],
+      PROGRAM_ID
+    );
+
+    console.log(`\n📝 Setting ${symbol} to ${STATUSES[status]}...`);
+    console.log('   Record:', symbolRecordPDA.toBase58());
+    console.log('   Mint:  ', mint.toBase58());
+
+    const symbolBytes = Buffer.from(symbol);
+    const symbolLen = Buffer.alloc(4);
+    symbolLen.writeUInt32LE(symbolBytes.length);
+
+    instruction = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: authority.publicKey, isSigner: true, isWritable: true },
+        { pubkey: configPDA, isSigner: false, isWritable: false },
+        { pubkey: symbolRecordPDA, isSigner: false, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: eventAuthorityPDA, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.concat([
+        getDiscriminator('set_symbol_status'),
+        symbolLen,
+        symbolBytes,
+        Buffer.from([status]),
+        mint.toBuffer(),
+      ]),
+    });
+  }
+
+  const tx = new Transaction().add(instruction);
+  tx.feePayer = authority.publicKey;
+  tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+
+  console.log('\n📤 Sending transaction...');
+  const signature = await sendAndConfirmTransaction(connection, tx, [authority]);
+  console.log('✅ Done!');
+  console.log('   Signature:', signature);
+}
+
+main().catch(console.error);
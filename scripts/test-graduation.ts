@@ -123,7 +123,6 @@ async function main() {
   const releaseTx = await client.buildReleaseForMigrationTx(
     authority.publicKey,
     mint,
-    migrationWallet
   );
   
   const releaseSig = await sendAndConfirmTransaction(connection, releaseTx, [authority]);
@@ -139,8 +138,8 @@ async function main() {
     
     if (finalMigrated) {
       console.log('\n🎉 Migration test successful!');
-      console.log('   SOL and tokens released to migration wallet');
-      console.log('   Ready for Raydium pool creation');
+      console.log('   SOL and tokens released to migration escrow');
+      console.log('   Ready for Raydium pool creation (claim + create + finalize in one tx)');
     }
   }
 }
@@ -35,6 +35,9 @@ describe("clawdvault", () => {
   let solVaultPDA: PublicKey;
   let tokenVault: PublicKey;
   
+  // Legacy SPL tokens carry Metaplex metadata (the program is cloned from devnet, see Anchor.toml)
+  const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+  
   // Constants
   const INITIAL_VIRTUAL_SOL = new BN(30_000_000_000); // 30 SOL
   const TOTAL_SUPPLY = new BN("1000000000000000"); // 1B tokens
@@ -85,7 +88,7 @@ describe("clawdvault", () => {
   it("Initializes the protocol", async () => {
     await program.methods
       .initialize()
-      .accounts({
+      .accountsPartial({
         authority: authority.publicKey,
         feeRecipient: feeRecipient.publicKey,
         config: configPDA,
@@ -101,15 +104,27 @@ describe("clawdvault", () => {
   
   it("Creates a token with bonding curve", async () => {
     await program.methods
-      .createToken("Test Token", "TEST", "https://example.com/metadata.json")
-      .accounts({
+      .createToken("Test Token", "TEST", "https://example.com/metadata.json", new BN(0), new BN(0), null)
+      .accountsPartial({
         creator: creator.publicKey,
+        payer: creator.publicKey,
         config: configPDA,
         mint: mint.publicKey,
+        metadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.publicKey.toBuffer()],
+          TOKEN_METADATA_PROGRAM_ID
+        )[0],
         bondingCurve: bondingCurvePDA,
         solVault: solVaultPDA,
         tokenVault: tokenVault,
+        creatorTokenAccount: await getAssociatedTokenAddress(mint.publicKey, creator.publicKey),
+        symbolRecord: null,
+        tokenProfile: null,
+        collectionMint: null,
+        collectionMetadata: null,
+        collectionMasterEdition: null,
         tokenProgram: TOKEN_PROGRAM_ID,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -146,7 +161,7 @@ describe("clawdvault", () => {
     
     await program.methods
       .buy(solAmount, minTokensOut)
-      .accounts({
+      .accountsPartial({
         buyer: buyer.publicKey,
         bondingCurve: bondingCurvePDA,
         config: configPDA,
@@ -212,7 +227,7 @@ describe("clawdvault", () => {
     
     await program.methods
       .sell(tokenAmount, minSolOut)
-      .accounts({
+      .accountsPartial({
         seller: buyer.publicKey,
         bondingCurve: bondingCurvePDA,
         config: configPDA,
@@ -256,7 +271,7 @@ describe("clawdvault", () => {
     try {
       await program.methods
         .buy(solAmount, minTokensOut)
-        .accounts({
+        .accountsPartial({
           buyer: buyer.publicKey,
           bondingCurve: bondingCurvePDA,
           config: configPDA,
@@ -279,6 +294,62 @@ describe("clawdvault", () => {
     }
   });
   
+  it("Pauses and resumes trading on a curve", async () => {
+    const buyerTokenAccount = await getAssociatedTokenAddress(
+      mint.publicKey,
+      buyer.publicKey
+    );
+    
+    await program.methods
+      .setCurvePaused(true)
+      .accountsPartial({
+        authority: authority.publicKey,
+        config: configPDA,
+        bondingCurve: bondingCurvePDA,
+      })
+      .rpc();
+    
+    let curve = await program.account.bondingCurve.fetch(bondingCurvePDA);
+    expect(curve.status).to.deep.equal({ paused: {} });
+    
+    try {
+      await program.methods
+        .buy(new BN(LAMPORTS_PER_SOL / 10), new BN(0))
+        .accountsPartial({
+          buyer: buyer.publicKey,
+          bondingCurve: bondingCurvePDA,
+          config: configPDA,
+          mint: mint.publicKey,
+          solVault: solVaultPDA,
+          tokenVault: tokenVault,
+          buyerTokenAccount: buyerTokenAccount,
+          feeRecipient: feeRecipient.publicKey,
+          creator: creator.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer])
+        .rpc();
+      
+      expect.fail("Should have thrown CurvePaused error");
+    } catch (e: any) {
+      expect(e.message).to.include("CurvePaused");
+    }
+    
+    await program.methods
+      .setCurvePaused(false)
+      .accountsPartial({
+        authority: authority.publicKey,
+        config: configPDA,
+        bondingCurve: bondingCurvePDA,
+      })
+      .rpc();
+    
+    curve = await program.account.bondingCurve.fetch(bondingCurvePDA);
+    expect(curve.status).to.deep.equal({ trading: {} });
+  });
+  
   it("Calculates correct bonding curve prices", async () => {
     const curve = await program.account.bondingCurve.fetch(bondingCurvePDA);
     
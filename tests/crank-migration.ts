@@ -0,0 +1,106 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID, getMint } from "@solana/spl-token";
+import { expect } from "chai";
+import { cuLimit, expectWithinCuLimit } from "./helpers/compute-units";
+import {
+  GRADUATING_BUY,
+  LP_BURN_ADDRESS,
+  airdrop,
+  ata2022,
+  buy2022,
+  configPDA,
+  crankMigration,
+  createToken2022,
+  curveAccounts,
+  ensureConfig,
+} from "./helpers/launch";
+
+describe("crank_migration", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const buyer = Keypair.generate();
+  const cranker = Keypair.generate();
+  const mint = Keypair.generate();
+  const accounts = curveAccounts(program, mint.publicKey);
+
+  let feeRecipient: PublicKey;
+
+  const crank = () => crankMigration(program, cranker, mint.publicKey, creator.publicKey, feeRecipient).rpc();
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, buyer.publicKey, 130);
+    await airdrop(provider, cranker.publicKey, 1);
+    feeRecipient = await ensureConfig(program, provider);
+
+    const config = await program.account.config.fetch(configPDA(program));
+    expect(config.migrationFeeLamports.toNumber()).to.equal(0);
+    expect(config.migrationFeeBps).to.equal(0);
+
+    await createToken2022(program, creator, mint);
+    // One buy past the 120 SOL threshold graduates the curve
+    await buy2022(program, buyer, mint.publicKey, creator.publicKey, feeRecipient, GRADUATING_BUY).rpc();
+
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.status).to.deep.equal({ graduated: {} });
+  });
+
+  it("Migrates a graduated curve into its pool without costing the cranker rent", async () => {
+    const curveBefore = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    const crankerBefore = await provider.connection.getBalance(cranker.publicKey);
+
+    const signature = await crank();
+    await expectWithinCuLimit(
+      provider.connection,
+      signature,
+      cuLimit(program, "CU_LIMIT_CRANK_MIGRATION"),
+      "crank_migration"
+    );
+
+    // The migration fee defaults to 0, so the only cost left is the transaction fee
+    const crankerAfter = await provider.connection.getBalance(cranker.publicKey);
+    expect(crankerBefore - crankerAfter).to.be.at.most(10_000);
+
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.status).to.deep.equal({ migrated: {} });
+    expect(curve.migrationDex).to.deep.equal({ clawdVault: {} });
+    expect(curve.migrationPool.toBase58()).to.equal(accounts.pool.toBase58());
+    expect(curve.realSolReserves.toNumber()).to.equal(0);
+
+    // The pool holds the reserves minus the reimbursed rent
+    const pool = await program.account.pool.fetch(accounts.pool);
+    const rent = curveBefore.realSolReserves.sub(pool.solReserves).toNumber();
+    expect(rent).to.be.greaterThan(0);
+    expect(rent).to.be.lessThan(0.05 * LAMPORTS_PER_SOL);
+    const poolVaultBalance = await provider.connection.getBalance(accounts.poolSolVault);
+    expect(poolVaultBalance).to.be.at.least(pool.solReserves.toNumber());
+
+    // All initial LP sits with the burn address
+    const lpMint = await getMint(provider.connection, accounts.lpMint, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(lpMint.supply.toString()).to.equal(pool.lpSupply.toString());
+    const lpBurned = await provider.connection.getTokenAccountBalance(ata2022(accounts.lpMint, LP_BURN_ADDRESS));
+    expect(lpBurned.value.amount).to.equal(pool.lpSupply.toString());
+  });
+
+  it("Skips a curve that has already migrated", async () => {
+    const poolBefore = await program.account.pool.fetch(accounts.pool);
+    const crankerBefore = await provider.connection.getBalance(cranker.publicKey);
+
+    await crank();
+
+    // No bounty, no state change: the cranker only pays the transaction fee
+    const crankerAfter = await provider.connection.getBalance(cranker.publicKey);
+    expect(crankerBefore - crankerAfter).to.be.at.most(10_000);
+    const pool = await program.account.pool.fetch(accounts.pool);
+    expect(pool.solReserves.toString()).to.equal(poolBefore.solReserves.toString());
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.status).to.deep.equal({ migrated: {} });
+  });
+});
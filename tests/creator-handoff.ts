@@ -0,0 +1,324 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+  getTokenMetadata,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  GRADUATING_BUY,
+  airdrop,
+  ata2022,
+  buy2022,
+  configPDA,
+  crankMigration,
+  createToken2022,
+  curveAccounts,
+  ensureConfig,
+  uniqueSymbol,
+} from "./helpers/launch";
+
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+const metadataPDA = (mint: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+
+/** Creators list of a Metaplex metadata account */
+function metadataCreators(data: Buffer): { address: PublicKey; verified: boolean; share: number }[] {
+  // key, update authority, mint, then length-prefixed name, symbol and uri
+  let offset = 1 + 32 + 32;
+  for (let i = 0; i < 3; i++) {
+    offset += 4 + data.readUInt32LE(offset);
+  }
+  offset += 2; // seller_fee_basis_points
+  if (data[offset] === 0) {
+    return [];
+  }
+  const count = data.readUInt32LE(offset + 1);
+  offset += 5;
+  return Array.from({ length: count }, (_, i) => {
+    const start = offset + i * 34;
+    return {
+      address: new PublicKey(data.subarray(start, start + 32)),
+      verified: data[start + 32] === 1,
+      share: data[start + 33],
+    };
+  });
+}
+
+describe("creator handoff", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const newCreator = Keypair.generate();
+  const buyer = Keypair.generate();
+  const mint = Keypair.generate();
+  const accounts = curveAccounts(program, mint.publicKey);
+  const metadata = metadataPDA(mint.publicKey);
+  const ata = (owner: PublicKey) => getAssociatedTokenAddressSync(mint.publicKey, owner, true, TOKEN_PROGRAM_ID);
+
+  let feeRecipient: PublicKey;
+
+  const creators = async () =>
+    metadataCreators((await provider.connection.getAccountInfo(metadata))!.data);
+
+  const propose = (pendingCreator: PublicKey) =>
+    program.methods
+      .proposeCreator(pendingCreator)
+      .accountsPartial({
+        creator: creator.publicKey,
+        bondingCurve: accounts.bondingCurve,
+        mint: mint.publicKey,
+        metadata,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+
+  const accept = (signer: Keypair) =>
+    program.methods
+      .acceptCreator()
+      .accountsPartial({
+        newCreator: signer.publicKey,
+        bondingCurve: accounts.bondingCurve,
+        mint: mint.publicKey,
+        metadata,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, newCreator.publicKey, 1);
+    await airdrop(provider, buyer.publicKey, 2);
+    feeRecipient = await ensureConfig(program, provider);
+
+    await program.methods
+      .createToken("Handoff Token", uniqueSymbol("H"), "https://example.com/handoff.json", new BN(0), new BN(0), null)
+      .accountsPartial({
+        creator: creator.publicKey,
+        payer: creator.publicKey,
+        config: configPDA(program),
+        mint: mint.publicKey,
+        metadata,
+        bondingCurve: accounts.bondingCurve,
+        solVault: accounts.solVault,
+        tokenVault: ata(accounts.bondingCurve),
+        creatorTokenAccount: ata(creator.publicKey),
+        symbolRecord: null,
+        tokenProfile: null,
+        collectionMint: null,
+        collectionMetadata: null,
+        collectionMasterEdition: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([creator, mint])
+      .rpc();
+
+    const [launchCreator] = await creators();
+    expect(launchCreator.address.toBase58()).to.equal(creator.publicKey.toBase58());
+    expect(launchCreator.verified).to.be.true;
+  });
+
+  it("Unverifies the Metaplex creator on propose and re-verifies it on cancel", async () => {
+    await propose(newCreator.publicKey);
+
+    let curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.pendingCreator.toBase58()).to.equal(newCreator.publicKey.toBase58());
+    expect((await creators())[0].verified).to.be.false;
+
+    await propose(PublicKey.default);
+
+    curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.pendingCreator.toBase58()).to.equal(PublicKey.default.toBase58());
+    const [current] = await creators();
+    expect(current.address.toBase58()).to.equal(creator.publicKey.toBase58());
+    expect(current.verified).to.be.true;
+  });
+
+  it("Rejects accepting from a wallet that wasn't proposed", async () => {
+    await propose(newCreator.publicKey);
+    try {
+      await accept(buyer);
+      expect.fail("Should have thrown Unauthorized");
+    } catch (e: any) {
+      expect(e.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Hands the curve and the Metaplex creator to the accepting wallet", async () => {
+    await accept(newCreator);
+
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.creator.toBase58()).to.equal(newCreator.publicKey.toBase58());
+    expect(curve.pendingCreator.toBase58()).to.equal(PublicKey.default.toBase58());
+
+    const list = await creators();
+    expect(list).to.have.length(1);
+    expect(list[0].address.toBase58()).to.equal(newCreator.publicKey.toBase58());
+    expect(list[0].verified).to.be.true;
+    expect(list[0].share).to.equal(100);
+  });
+
+  it("Pays creator fees to the new creator", async () => {
+    const before = await provider.connection.getBalance(newCreator.publicKey);
+    const solAmount = new BN(LAMPORTS_PER_SOL);
+
+    await program.methods
+      .buy(solAmount, new BN(0))
+      .accountsPartial({
+        buyer: buyer.publicKey,
+        bondingCurve: accounts.bondingCurve,
+        config: configPDA(program),
+        mint: mint.publicKey,
+        solVault: accounts.solVault,
+        tokenVault: ata(accounts.bondingCurve),
+        buyerTokenAccount: ata(buyer.publicKey),
+        feeRecipient,
+        creator: newCreator.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([buyer])
+      .rpc();
+
+    const after = await provider.connection.getBalance(newCreator.publicKey);
+    expect(after - before).to.be.closeTo(solAmount.toNumber() * 50 / 10000, 1000);
+  });
+});
+
+describe("creator handoff after metadata is locked", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const newCreator = Keypair.generate();
+  const trader = Keypair.generate();
+  const mint = Keypair.generate();
+  const accounts = curveAccounts(program, mint.publicKey);
+  const metadata = metadataPDA(mint.publicKey);
+
+  let feeRecipient: PublicKey;
+
+  const setMetadataPolicy = (policy: object) =>
+    program.methods
+      .setMetadataPolicy(policy as any)
+      .accountsPartial({ authority: provider.wallet.publicKey, config: configPDA(program) })
+      .rpc();
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, newCreator.publicKey, 1);
+    await airdrop(provider, trader.publicKey, 130);
+    feeRecipient = await ensureConfig(program, provider);
+
+    await createToken2022(program, creator, mint);
+    await buy2022(program, trader, mint.publicKey, creator.publicKey, feeRecipient, GRADUATING_BUY).rpc();
+
+    const oldPolicy = (await program.account.config.fetch(configPDA(program))).metadataPolicy;
+    await setMetadataPolicy({ creatorAuthority: {} });
+    try {
+      await program.methods
+        .lockMetadata()
+        .accountsPartial({
+          signer: trader.publicKey,
+          config: configPDA(program),
+          bondingCurve: accounts.bondingCurve,
+          mint: mint.publicKey,
+          metadata,
+          metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([trader])
+        .rpc();
+    } finally {
+      await setMetadataPolicy(oldPolicy);
+    }
+
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.metadataPolicy).to.deep.equal({ creatorAuthority: {} });
+  });
+
+  it("Escrows the metadata update authority with the curve while a handoff is pending", async () => {
+    await program.methods
+      .proposeCreator(newCreator.publicKey)
+      .accountsPartial({
+        creator: creator.publicKey,
+        bondingCurve: accounts.bondingCurve,
+        mint: mint.publicKey,
+        metadata,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+
+    const tokenMetadata = await getTokenMetadata(provider.connection, mint.publicKey);
+    expect(tokenMetadata!.updateAuthority!.toBase58()).to.equal(accounts.bondingCurve.toBase58());
+  });
+
+  it("Hands the metadata update authority to the new creator on accept", async () => {
+    await program.methods
+      .acceptCreator()
+      .accountsPartial({
+        newCreator: newCreator.publicKey,
+        bondingCurve: accounts.bondingCurve,
+        mint: mint.publicKey,
+        metadata,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([newCreator])
+      .rpc();
+
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.creator.toBase58()).to.equal(newCreator.publicKey.toBase58());
+
+    const tokenMetadata = await getTokenMetadata(provider.connection, mint.publicKey);
+    expect(tokenMetadata!.updateAuthority!.toBase58()).to.equal(newCreator.publicKey.toBase58());
+  });
+
+  it("Pays pool creator fees to the new creator", async () => {
+    await crankMigration(program, trader, mint.publicKey, newCreator.publicKey, feeRecipient).rpc();
+
+    const before = await provider.connection.getBalance(newCreator.publicKey);
+    const solAmount = new BN(LAMPORTS_PER_SOL);
+
+    await program.methods
+      .swap(true, solAmount, new BN(0))
+      .accountsPartial({
+        user: trader.publicKey,
+        config: configPDA(program),
+        bondingCurve: accounts.bondingCurve,
+        pool: accounts.pool,
+        mint: mint.publicKey,
+        poolSolVault: accounts.poolSolVault,
+        poolTokenVault: accounts.poolTokenVault,
+        userTokenAccount: ata2022(mint.publicKey, trader.publicKey),
+        feeRecipient,
+        creator: newCreator.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([trader])
+      .rpc();
+
+    const after = await provider.connection.getBalance(newCreator.publicKey);
+    expect(after - before).to.be.closeTo(solAmount.toNumber() * 10 / 10000, 1000);
+  });
+});
@@ -0,0 +1,101 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { cpiEvents } from "./helpers/events";
+import { airdrop, buy2022, configPDA, createToken2022, ensureConfig } from "./helpers/launch";
+
+describe("self-CPI events", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const buyer = Keypair.generate();
+  const mint = Keypair.generate();
+
+  let feeRecipient: PublicKey;
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, buyer.publicKey, 2);
+    feeRecipient = await ensureConfig(program, provider);
+    await createToken2022(program, creator, mint);
+  });
+
+  it("Emits trades as self-CPI instructions rather than program logs", async () => {
+    const solAmount = new BN(LAMPORTS_PER_SOL / 2);
+    const signature = await buy2022(program, buyer, mint.publicKey, creator.publicKey, feeRecipient, solAmount).rpc();
+
+    const events = await cpiEvents(program, provider.connection, signature);
+    const trades = events.filter((event) => event.name.toLowerCase() === "tradeevent");
+    expect(trades).to.have.length(1);
+    const trade = trades[0].data;
+    expect(trade.mint.toBase58()).to.equal(mint.publicKey.toBase58());
+    expect(trade.trader.toBase58()).to.equal(buyer.publicKey.toBase58());
+    expect(trade.isBuy).to.be.true;
+    expect(trade.tokenAmount.gtn(0)).to.be.true;
+
+    // Logs can be truncated, so nothing is emitted through them anymore
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    expect(tx!.meta!.logMessages!.some((line) => line.startsWith("Program data:"))).to.be.false;
+  });
+});
+
+describe("admin audit events", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const auditEvent = async (signature: string, name: string) => {
+    const events = await cpiEvents(program, provider.connection, signature);
+    const matching = events.filter((event) => event.name.toLowerCase() === name.toLowerCase());
+    expect(matching, name).to.have.length(1);
+    return matching[0].data;
+  };
+
+  before(async () => {
+    await ensureConfig(program, provider);
+  });
+
+  it("Records old and new values and the signer of config changes", async () => {
+    const config = await program.account.config.fetch(configPDA(program));
+    const moderator = Keypair.generate().publicKey;
+
+    const signature = await program.methods
+      .setModerator(moderator)
+      .accountsPartial({ authority: provider.wallet.publicKey, config: configPDA(program) })
+      .rpc();
+    const event = await auditEvent(signature, "ModeratorChanged");
+    expect(event.oldModerator.toBase58()).to.equal(config.moderator.toBase58());
+    expect(event.newModerator.toBase58()).to.equal(moderator.toBase58());
+    expect(event.signer.toBase58()).to.equal(provider.wallet.publicKey.toBase58());
+
+    const restore = await program.methods
+      .setModerator(config.moderator)
+      .accountsPartial({ authority: provider.wallet.publicKey, config: configPDA(program) })
+      .rpc();
+    const restored = await auditEvent(restore, "ModeratorChanged");
+    expect(restored.oldModerator.toBase58()).to.equal(moderator.toBase58());
+    expect(restored.newModerator.toBase58()).to.equal(config.moderator.toBase58());
+  });
+
+  it("Leaves an audit record for a change that keeps the value", async () => {
+    const config = await program.account.config.fetch(configPDA(program));
+
+    const signature = await program.methods
+      .setMaxTransferFee(config.maxTransferFeeBps)
+      .accountsPartial({ authority: provider.wallet.publicKey, config: configPDA(program) })
+      .rpc();
+    const event = await auditEvent(signature, "MaxTransferFeeChanged");
+    expect(event.oldMaxBps).to.equal(config.maxTransferFeeBps);
+    expect(event.newMaxBps).to.equal(config.maxTransferFeeBps);
+  });
+});
@@ -0,0 +1,120 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID, createAssociatedTokenAccountIdempotent, getMint } from "@solana/spl-token";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  GRADUATING_BUY,
+  airdrop,
+  buy2022,
+  configPDA,
+  crankMigration,
+  createToken2022,
+  curveAccounts,
+  ensureConfig,
+} from "./helpers/launch";
+
+const TOTAL_SUPPLY = new BN("1000000000000000");
+
+describe("excess tokens at migration", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  const creator = Keypair.generate();
+  const buyer = Keypair.generate();
+  const treasury = Keypair.generate();
+
+  let feeRecipient: PublicKey;
+  let oldDestination: PublicKey;
+
+  const setExcessTokenDestination = (destination: PublicKey) =>
+    program.methods
+      .setExcessTokenDestination(destination)
+      .accountsPartial({ authority: provider.wallet.publicKey, config: configPDA(program) })
+      .rpc();
+
+  /** Launch and graduate a curve, returning its mint and the curve as it stood before migration */
+  const graduate = async () => {
+    const mint = Keypair.generate();
+    await createToken2022(program, creator, mint);
+    await buy2022(program, buyer, mint.publicKey, creator.publicKey, feeRecipient, GRADUATING_BUY).rpc();
+    const curve = await program.account.bondingCurve.fetch(curveAccounts(program, mint.publicKey).bondingCurve);
+    return { mint: mint.publicKey, curve };
+  };
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, buyer.publicKey, 400);
+    feeRecipient = await ensureConfig(program, provider);
+    oldDestination = (await program.account.config.fetch(configPDA(program))).excessTokenDestination;
+  });
+
+  after(async () => {
+    await setExcessTokenDestination(oldDestination);
+  });
+
+  it("Lists at the final curve price and burns the rest by default", async () => {
+    await setExcessTokenDestination(PublicKey.default);
+    const { mint, curve } = await graduate();
+
+    await crankMigration(program, buyer, mint, creator.publicKey, feeRecipient).rpc();
+
+    // Pool tokens are the pool's SOL at the curve's last price, rounded down
+    const pool = await program.account.pool.fetch(curveAccounts(program, mint).pool);
+    const listed = pool.solReserves.mul(curve.virtualTokenReserves).div(curve.virtualSolReserves);
+    expect(pool.tokenReserves.toString()).to.equal(listed.toString());
+
+    const excess = curve.realTokenReserves.sub(pool.tokenReserves);
+    expect(excess.gtn(0)).to.be.true;
+    const supply = (await getMint(provider.connection, mint, undefined, TOKEN_2022_PROGRAM_ID)).supply;
+    expect(supply.toString()).to.equal(TOTAL_SUPPLY.sub(excess).toString());
+  });
+
+  it("Sends excess tokens to the configured destination instead", async () => {
+    await setExcessTokenDestination(treasury.publicKey);
+    const { mint, curve } = await graduate();
+    const treasuryAccount = await createAssociatedTokenAccountIdempotent(
+      provider.connection,
+      payer,
+      mint,
+      treasury.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    await crankMigration(program, buyer, mint, creator.publicKey, feeRecipient, treasuryAccount).rpc();
+
+    const pool = await program.account.pool.fetch(curveAccounts(program, mint).pool);
+    const excess = curve.realTokenReserves.sub(pool.tokenReserves);
+    const received = (await provider.connection.getTokenAccountBalance(treasuryAccount)).value.amount;
+    expect(received).to.equal(excess.toString());
+    const supply = (await getMint(provider.connection, mint, undefined, TOKEN_2022_PROGRAM_ID)).supply;
+    expect(supply.toString()).to.equal(TOTAL_SUPPLY.toString());
+  });
+
+  it("Rejects a missing or foreign excess token account", async () => {
+    await setExcessTokenDestination(treasury.publicKey);
+    const { mint } = await graduate();
+    const [treasuryAccount, foreignAccount] = await Promise.all(
+      [treasury.publicKey, buyer.publicKey].map((owner) =>
+        createAssociatedTokenAccountIdempotent(provider.connection, payer, mint, owner, undefined, TOKEN_2022_PROGRAM_ID)
+      )
+    );
+
+    for (const account of [null, foreignAccount]) {
+      try {
+        await crankMigration(program, buyer, mint, creator.publicKey, feeRecipient, account).rpc();
+        expect.fail("Should have thrown InvalidExcessTokenAccount");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidExcessTokenAccount");
+      }
+    }
+
+    await crankMigration(program, buyer, mint, creator.publicKey, feeRecipient, treasuryAccount).rpc();
+  });
+});
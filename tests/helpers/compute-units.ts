@@ -0,0 +1,37 @@
+import { Program } from "@coral-xyz/anchor";
+import { Connection, TransactionSignature } from "@solana/web3.js";
+import { Clawdvault } from "../../target/types/clawdvault";
+import { expect } from "chai";
+
+/**
+ * A `CU_LIMIT_*` constant from the program IDL
+ */
+export function cuLimit(program: Program<Clawdvault>, name: string): number {
+  const constant = program.idl.constants?.find((c) => c.name === name);
+  if (!constant) throw new Error(`Missing IDL constant ${name}`);
+  return Number(constant.value.replace(/_/g, ""));
+}
+
+/**
+ * Check the compute units a landed transaction consumed against a limit
+ * Set `CU_REPORT=1` to print the measurements when updating the README table
+ */
+export async function expectWithinCuLimit(
+  connection: Connection,
+  signature: TransactionSignature,
+  limit: number,
+  label: string
+): Promise<number> {
+  await connection.confirmTransaction(signature, "confirmed");
+  const tx = await connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const used = tx?.meta?.computeUnitsConsumed;
+  expect(used, `${label}: computeUnitsConsumed`).to.not.be.undefined;
+  if (process.env.CU_REPORT === "1") {
+    console.log(`    ${label}: ${used} CU (limit ${limit})`);
+  }
+  expect(used!).to.be.at.most(limit);
+  return used!;
+}
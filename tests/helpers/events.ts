@@ -0,0 +1,45 @@
+import { Program, utils } from "@coral-xyz/anchor";
+import { Connection, PublicKey, TransactionSignature } from "@solana/web3.js";
+import { Clawdvault } from "../../target/types/clawdvault";
+
+// Anchor's `emit_cpi!` instruction tag, sha256("anchor:event")[..8]
+const EVENT_IX_TAG = Buffer.from([0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d]);
+
+/**
+ * Events a landed transaction emitted through self-CPI, decoded with the IDL (compare names in any case)
+ * Only inner instructions to our program signed by its event authority count, like the trade sync
+ */
+export async function cpiEvents(
+  program: Program<Clawdvault>,
+  connection: Connection,
+  signature: TransactionSignature
+): Promise<{ name: string; data: any }[]> {
+  await connection.confirmTransaction(signature, "confirmed");
+  const tx = await connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const eventAuthority = PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], program.programId)[0];
+  const accountKeys = tx!.transaction.message.getAccountKeys({ accountKeysFromLookups: tx!.meta!.loadedAddresses });
+
+  const events: { name: string; data: any }[] = [];
+  for (const inner of tx!.meta!.innerInstructions ?? []) {
+    for (const ix of inner.instructions) {
+      if (
+        !accountKeys.get(ix.programIdIndex)?.equals(program.programId) ||
+        ix.accounts.length !== 1 ||
+        !accountKeys.get(ix.accounts[0])?.equals(eventAuthority)
+      ) {
+        continue;
+      }
+      const data = Buffer.from(utils.bytes.bs58.decode(ix.data));
+      if (data.subarray(0, 8).equals(EVENT_IX_TAG)) {
+        const event = program.coder.events.decode(data.subarray(8).toString("base64"));
+        if (event) {
+          events.push(event);
+        }
+      }
+    }
+  }
+  return events;
+}
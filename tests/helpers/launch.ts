@@ -0,0 +1,252 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../../target/types/clawdvault";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+} from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { BN } from "bn.js";
+
+export const LP_BURN_ADDRESS = new PublicKey("1nc1nerator11111111111111111111111111111111");
+
+/** Buy that takes a fresh curve past the 120 SOL graduation threshold */
+export const GRADUATING_BUY = new BN(125 * LAMPORTS_PER_SOL);
+
+/** PDAs of a curve and its pool, derived from the mint */
+export function curveAccounts(program: Program<Clawdvault>, mint: PublicKey) {
+  const pda = (...seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const bondingCurve = pda(Buffer.from("bonding_curve"), mint.toBuffer());
+  const pool = pda(Buffer.from("pool"), mint.toBuffer());
+  const migrationEscrow = pda(Buffer.from("migration_escrow"), mint.toBuffer());
+  const lpMint = pda(Buffer.from("lp_mint"), pool.toBuffer());
+  return {
+    mint,
+    bondingCurve,
+    solVault: pda(Buffer.from("sol_vault"), mint.toBuffer()),
+    tokenVault: ata2022(mint, bondingCurve),
+    pool,
+    poolSolVault: pda(Buffer.from("pool_sol_vault"), mint.toBuffer()),
+    poolTokenVault: ata2022(mint, pool),
+    lpMint,
+    migrationEscrow,
+    escrowTokenAccount: ata2022(mint, migrationEscrow),
+  };
+}
+
+/** Token-2022 associated token account (owner may be a PDA) */
+export function ata2022(mint: PublicKey, owner: PublicKey): PublicKey {
+  return getAssociatedTokenAddressSync(mint, owner, true, TOKEN_2022_PROGRAM_ID);
+}
+
+/** Symbol registry record of a ticker (registry tickers are upper-cased) */
+export function symbolRecordPDA(program: Program<Clawdvault>, symbol: string): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("symbol"), Buffer.from(symbol.toUpperCase())],
+    program.programId
+  )[0];
+}
+
+/** On-chain profile of a token */
+export function profilePDA(program: Program<Clawdvault>, mint: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from("profile"), mint.toBuffer()], program.programId)[0];
+}
+
+export function configPDA(program: Program<Clawdvault>): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId)[0];
+}
+
+export async function airdrop(provider: anchor.AnchorProvider, to: PublicKey, sol: number) {
+  const sig = await provider.connection.requestAirdrop(to, sol * LAMPORTS_PER_SOL);
+  await provider.connection.confirmTransaction(sig);
+}
+
+/**
+ * Initialize the protocol unless another suite already did on this validator
+ * Returns the config's fee recipient
+ */
+export async function ensureConfig(
+  program: Program<Clawdvault>,
+  provider: anchor.AnchorProvider
+): Promise<PublicKey> {
+  const config = configPDA(program);
+  if (!(await provider.connection.getAccountInfo(config))) {
+    await program.methods
+      .initialize()
+      .accountsPartial({
+        authority: provider.wallet.publicKey,
+        feeRecipient: Keypair.generate().publicKey,
+        config,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+  return (await program.account.config.fetch(config)).feeRecipient;
+}
+
+/** Unix time of the validator's latest slot */
+export async function validatorTime(provider: anchor.AnchorProvider): Promise<number> {
+  return (await provider.connection.getBlockTime(await provider.connection.getSlot()))!;
+}
+
+/**
+ * Queue a migration allow-list entry, wait out the timelock and apply it
+ * (the tests build with `short-timelock`, so the wait is a few seconds)
+ */
+export async function approveMigrationDestination(
+  program: Program<Clawdvault>,
+  provider: anchor.AnchorProvider,
+  kind: { wallet: {} } | { ammProgram: {} },
+  destination: PublicKey
+) {
+  const accounts = { authority: provider.wallet.publicKey, config: configPDA(program) };
+  await program.methods.setMigrationDestination(kind, destination, true).accountsPartial(accounts).rpc();
+
+  const config = await program.account.config.fetch(configPDA(program));
+  const pending = config.pendingMigrationDestinations.find((entry) => entry.destination.equals(destination));
+  // Already on the allow-list
+  if (!pending) {
+    return;
+  }
+  while ((await validatorTime(provider)) < pending.activatesAt.toNumber()) {
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+  }
+  await program.methods.applyMigrationDestination(kind, destination).accountsPartial(accounts).rpc();
+}
+
+/** Random ticker, so suites don't collide in the symbol registry */
+export function uniqueSymbol(prefix: string): string {
+  return prefix + Math.random().toString(36).slice(2, 7).toUpperCase();
+}
+
+/** Profile fields accepted by the create instructions and `set_token_profile` */
+export type ProfileArgs = {
+  website: string;
+  twitter: string;
+  telegram: string;
+  agentId: string;
+  description: string;
+};
+
+/** Launch a Token-2022 curve with no initial buy, and no profile unless one is passed */
+export async function createToken2022(
+  program: Program<Clawdvault>,
+  creator: Keypair,
+  mint: Keypair,
+  opts: {
+    name?: string;
+    symbol?: string;
+    uri?: string;
+    symbolRecord?: PublicKey;
+    deadline?: number;
+    transferFeeBps?: number;
+    profile?: ProfileArgs;
+    payer?: Keypair;
+  } = {}
+) {
+  const accounts = curveAccounts(program, mint.publicKey);
+  await program.methods
+    .createToken2022(
+      opts.name ?? "Test Token",
+      opts.symbol ?? uniqueSymbol("T"),
+      opts.uri ?? "https://example.com/metadata.json",
+      new BN(0),
+      new BN(opts.deadline ?? 0),
+      opts.transferFeeBps ?? 0,
+      opts.profile ?? null
+    )
+    .accountsPartial({
+      creator: creator.publicKey,
+      payer: (opts.payer ?? creator).publicKey,
+      config: configPDA(program),
+      mint: mint.publicKey,
+      bondingCurve: accounts.bondingCurve,
+      solVault: accounts.solVault,
+      tokenVault: accounts.tokenVault,
+      creatorTokenAccount: ata2022(mint.publicKey, creator.publicKey),
+      symbolRecord: opts.symbolRecord ?? null,
+      tokenProfile: opts.profile ? profilePDA(program, mint.publicKey) : null,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    })
+    .signers(opts.payer ? [creator, opts.payer, mint] : [creator, mint])
+    .rpc();
+  return accounts;
+}
+
+/** Buy from a Token-2022 curve */
+export function buy2022(
+  program: Program<Clawdvault>,
+  buyer: Keypair,
+  mint: PublicKey,
+  creator: PublicKey,
+  feeRecipient: PublicKey,
+  lamports: BN
+) {
+  const accounts = curveAccounts(program, mint);
+  return program.methods
+    .buy(lamports, new BN(0))
+    .accountsPartial({
+      buyer: buyer.publicKey,
+      bondingCurve: accounts.bondingCurve,
+      config: configPDA(program),
+      mint,
+      solVault: accounts.solVault,
+      tokenVault: accounts.tokenVault,
+      buyerTokenAccount: ata2022(mint, buyer.publicKey),
+      feeRecipient,
+      creator,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    })
+    .signers([buyer]);
+}
+
+/**
+ * Migrate a graduated Token-2022 curve into its ClawdVault pool
+ * `excessTokenAccount` is needed once the config sends excess tokens somewhere instead of burning them
+ */
+export function crankMigration(
+  program: Program<Clawdvault>,
+  cranker: Keypair,
+  mint: PublicKey,
+  creator: PublicKey,
+  feeRecipient: PublicKey,
+  excessTokenAccount: PublicKey | null = null
+) {
+  const accounts = curveAccounts(program, mint);
+  return program.methods
+    .crankMigration()
+    .accountsPartial({
+      caller: cranker.publicKey,
+      config: configPDA(program),
+      bondingCurve: accounts.bondingCurve,
+      mint,
+      solVault: accounts.solVault,
+      tokenVault: accounts.tokenVault,
+      pool: accounts.pool,
+      poolSolVault: accounts.poolSolVault,
+      poolTokenVault: accounts.poolTokenVault,
+      lpMint: accounts.lpMint,
+      lpBurnAddress: LP_BURN_ADDRESS,
+      lpBurnAccount: ata2022(accounts.lpMint, LP_BURN_ADDRESS),
+      feeRecipient,
+      creator,
+      excessTokenAccount,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    })
+    .signers([cranker]);
+}
+
+/** Error code of a program error, looked up in the IDL by name (any case) */
+export function errorCode(program: Program<Clawdvault>, name: string): number {
+  const error = program.idl.errors?.find((e) => e.name.toLowerCase() === name.toLowerCase());
+  if (!error) {
+    throw new Error(`Unknown program error ${name}`);
+  }
+  return error.code;
+}
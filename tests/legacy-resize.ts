@@ -0,0 +1,119 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import { configPDA, ensureConfig } from "./helpers/launch";
+
+// Curves in the original 124-byte layout, loaded into the validator from
+// tests/fixtures (see [[test.validator.account]] in Anchor.toml)
+const LEGACY_TRADING_MINT = new PublicKey("4dYQasDEDuWaxSHNyfhXSZ4kuQcEX5dvk6GRvm4LM7y4");
+const LEGACY_MIGRATED_MINT = new PublicKey("ENxipHtQYWrgCmdcmzpMfEHcAGCUSybMonGcmrYwnAcR");
+const LEGACY_CREATOR = new PublicKey("8TC2pXaZDkFkB8voVZeqsRJ95gts7f17nxKuqKBFvQAD");
+const LEGACY_LEN = 124;
+
+describe("resize_curve", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const curvePDA = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("bonding_curve"), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  const resize = (mint: PublicKey) =>
+    program.methods
+      .resizeCurve()
+      .accountsPartial({
+        payer: provider.wallet.publicKey,
+        mint,
+        bondingCurve: curvePDA(mint),
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+  const recordMigration = (mint: PublicKey, pool: PublicKey) =>
+    program.methods
+      .recordMigration({ burned: {} })
+      .accountsPartial({
+        operator: provider.wallet.publicKey,
+        config: configPDA(program),
+        bondingCurve: curvePDA(mint),
+        pool,
+      })
+      .rpc();
+
+  before(async () => {
+    await ensureConfig(program, provider);
+  });
+
+  it("Resizes a baseline-sized trading curve", async () => {
+    const curve = curvePDA(LEGACY_TRADING_MINT);
+    const before = await provider.connection.getAccountInfo(curve);
+    expect(before!.data.length).to.equal(LEGACY_LEN);
+
+    await resize(LEGACY_TRADING_MINT);
+
+    const after = await provider.connection.getAccountInfo(curve);
+    expect(after!.data.length).to.be.greaterThan(LEGACY_LEN);
+    // Legacy bytes are kept as-is
+    expect(after!.data.subarray(0, LEGACY_LEN).equals(before!.data)).to.be.true;
+    const rent = await provider.connection.getMinimumBalanceForRentExemption(after!.data.length);
+    expect(after!.lamports).to.be.at.least(rent);
+
+    const state = await program.account.bondingCurve.fetch(curve);
+    expect(state.creator.toBase58()).to.equal(LEGACY_CREATOR.toBase58());
+    expect(state.mint.toBase58()).to.equal(LEGACY_TRADING_MINT.toBase58());
+    expect(state.realSolReserves.toString()).to.equal("5000000000");
+    expect(state.status).to.deep.equal({ trading: {} });
+    expect(state.migrationDex).to.be.null;
+    expect(state.tokenProgram.toBase58()).to.equal(TOKEN_PROGRAM_ID.toBase58());
+    expect(state.flags).to.equal(0);
+    expect(state.pendingCreator.toBase58()).to.equal(PublicKey.default.toBase58());
+  });
+
+  it("Derives Migrated status for a legacy migrated curve", async () => {
+    await resize(LEGACY_MIGRATED_MINT);
+
+    const state = await program.account.bondingCurve.fetch(curvePDA(LEGACY_MIGRATED_MINT));
+    expect(state.graduated).to.be.true;
+    expect(state.migrated).to.be.true;
+    expect(state.status).to.deep.equal({ migrated: {} });
+    expect(state.migrationDex).to.deep.equal({ raydiumCpmm: {} });
+  });
+
+  it("Rejects recording a pool that isn't owned by the curve's DEX", async () => {
+    try {
+      // Owned by this program, not Raydium CPMM
+      await recordMigration(LEGACY_MIGRATED_MINT, curvePDA(LEGACY_TRADING_MINT));
+      expect.fail("Should have thrown InvalidMigrationPool");
+    } catch (e: any) {
+      expect(e.message).to.include("InvalidMigrationPool");
+    }
+
+    const state = await program.account.bondingCurve.fetch(curvePDA(LEGACY_MIGRATED_MINT));
+    expect(state.migrationPool.toBase58()).to.equal(PublicKey.default.toBase58());
+  });
+
+  it("Rejects recording a migration for a curve that hasn't migrated", async () => {
+    try {
+      await recordMigration(LEGACY_TRADING_MINT, curvePDA(LEGACY_MIGRATED_MINT));
+      expect.fail("Should have thrown NotGraduated");
+    } catch (e: any) {
+      expect(e.message).to.include("NotGraduated");
+    }
+  });
+
+  it("Rejects resizing a curve twice", async () => {
+    try {
+      await resize(LEGACY_TRADING_MINT);
+      expect.fail("Should have thrown InvalidAccountData");
+    } catch (e: any) {
+      expect(e.message).to.include("InvalidAccountData");
+    }
+  });
+});
@@ -0,0 +1,105 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID, getMint, getTokenMetadata } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  GRADUATING_BUY,
+  airdrop,
+  buy2022,
+  configPDA,
+  createToken2022,
+  curveAccounts,
+  ensureConfig,
+} from "./helpers/launch";
+
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+describe("mint authority and metadata lock", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const buyer = Keypair.generate();
+  const mint = Keypair.generate();
+  const accounts = curveAccounts(program, mint.publicKey);
+
+  let feeRecipient: PublicKey;
+  let oldPolicy: any;
+
+  const setMetadataPolicy = (policy: object) =>
+    program.methods
+      .setMetadataPolicy(policy as any)
+      .accountsPartial({ authority: provider.wallet.publicKey, config: configPDA(program) })
+      .rpc();
+
+  const lockMetadata = () =>
+    program.methods
+      .lockMetadata()
+      .accountsPartial({
+        signer: buyer.publicKey,
+        config: configPDA(program),
+        bondingCurve: accounts.bondingCurve,
+        mint: mint.publicKey,
+        metadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.publicKey.toBuffer()],
+          TOKEN_METADATA_PROGRAM_ID
+        )[0],
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([buyer])
+      .rpc();
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, buyer.publicKey, 130);
+    feeRecipient = await ensureConfig(program, provider);
+    oldPolicy = (await program.account.config.fetch(configPDA(program))).metadataPolicy;
+    await setMetadataPolicy({ immutable: {} });
+
+    await createToken2022(program, creator, mint);
+  });
+
+  after(async () => {
+    await setMetadataPolicy(oldPolicy);
+  });
+
+  it("Revokes the mint authority at launch and keeps metadata with the curve", async () => {
+    const mintAccount = await getMint(provider.connection, mint.publicKey, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(mintAccount.mintAuthority).to.be.null;
+    expect(mintAccount.supply.toString()).to.equal("1000000000000000");
+
+    const metadata = await getTokenMetadata(provider.connection, mint.publicKey);
+    expect(metadata!.updateAuthority!.toBase58()).to.equal(accounts.bondingCurve.toBase58());
+  });
+
+  it("Rejects locking metadata before graduation", async () => {
+    try {
+      await lockMetadata();
+      expect.fail("Should have thrown NotGraduated");
+    } catch (e: any) {
+      expect(e.message).to.include("NotGraduated");
+    }
+  });
+
+  it("Lets anyone make a graduated token's metadata immutable, once", async () => {
+    await buy2022(program, buyer, mint.publicKey, creator.publicKey, feeRecipient, GRADUATING_BUY).rpc();
+    await lockMetadata();
+
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.metadataPolicy).to.deep.equal({ immutable: {} });
+    const metadata = await getTokenMetadata(provider.connection, mint.publicKey);
+    expect(metadata!.updateAuthority).to.be.undefined;
+
+    try {
+      await lockMetadata();
+      expect.fail("Should have thrown MetadataAlreadyLocked");
+    } catch (e: any) {
+      expect(e.message).to.include("MetadataAlreadyLocked");
+    }
+  });
+});
@@ -0,0 +1,116 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import { airdrop, configPDA, createToken2022, ensureConfig, uniqueSymbol } from "./helpers/launch";
+
+const CHARSET_UPPER = 1 << 0;
+const CHARSET_LOWER = 1 << 1;
+const CHARSET_DIGIT = 1 << 2;
+const CHARSET_SPACE = 1 << 3;
+
+const ALLOWED_PREFIX = "https://arweave.net/";
+
+describe("metadata rules", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+
+  let oldCharsets: { name: number; symbol: number };
+  let prefixWasAllowed: boolean;
+
+  const setCharsets = (name: number, symbol: number) =>
+    program.methods
+      .setMetadataCharsets(name, symbol)
+      .accountsPartial({ authority: provider.wallet.publicKey, config: configPDA(program) })
+      .rpc();
+
+  const setUriPrefix = (prefix: string, allowed: boolean) =>
+    program.methods
+      .setUriPrefix(prefix, allowed)
+      .accountsPartial({ authority: provider.wallet.publicKey, config: configPDA(program) })
+      .rpc();
+
+  const expectRejected = async (launch: Promise<unknown>, error: string) => {
+    try {
+      await launch;
+      expect.fail(`Should have thrown ${error}`);
+    } catch (e: any) {
+      expect(e.message).to.include(error);
+    }
+  };
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 5);
+    await ensureConfig(program, provider);
+
+    const config = await program.account.config.fetch(configPDA(program));
+    oldCharsets = { name: config.nameCharset, symbol: config.symbolCharset };
+    prefixWasAllowed = config.uriPrefixes.includes(ALLOWED_PREFIX);
+  });
+
+  // Other suites launch with example.com URIs and mixed-case names
+  after(async () => {
+    await setCharsets(oldCharsets.name, oldCharsets.symbol);
+    await setUriPrefix(ALLOWED_PREFIX, prefixWasAllowed);
+  });
+
+  it("Rejects URIs outside the allowed prefixes once one is set", async () => {
+    await setUriPrefix(ALLOWED_PREFIX, true);
+
+    await expectRejected(
+      createToken2022(program, creator, Keypair.generate(), { uri: "https://example.com/metadata.json" }),
+      "UriNotAllowed"
+    );
+    // A prefix only matches at the start of the URI
+    await expectRejected(
+      createToken2022(program, creator, Keypair.generate(), { uri: `https://example.com/?${ALLOWED_PREFIX}` }),
+      "UriNotAllowed"
+    );
+
+    await createToken2022(program, creator, Keypair.generate(), { uri: `${ALLOWED_PREFIX}metadata.json` });
+  });
+
+  it("Rejects names and symbols outside the allowed character classes", async () => {
+    await setCharsets(CHARSET_UPPER | CHARSET_LOWER | CHARSET_DIGIT | CHARSET_SPACE, CHARSET_UPPER | CHARSET_DIGIT);
+    const uri = `${ALLOWED_PREFIX}metadata.json`;
+
+    await expectRejected(
+      createToken2022(program, creator, Keypair.generate(), { uri, symbol: uniqueSymbol("t").toLowerCase() }),
+      "InvalidSymbolCharacters"
+    );
+    // Zero-width space hidden in the name
+    await expectRejected(
+      createToken2022(program, creator, Keypair.generate(), { uri, name: "Claw\u200bd Token" }),
+      "InvalidNameCharacters"
+    );
+    // Cyrillic capital A, which looks like the Latin one
+    await expectRejected(
+      createToken2022(program, creator, Keypair.generate(), { uri, symbol: uniqueSymbol("\u0410") }),
+      "InvalidSymbolCharacters"
+    );
+
+    await createToken2022(program, creator, Keypair.generate(), { uri, name: "Clawd Token 2" });
+  });
+
+  it("Rejects URI prefix changes from a wallet that isn't the authority", async () => {
+    const stranger = Keypair.generate();
+    await expectRejected(
+      program.methods
+        .setUriPrefix("https://evil.example/", true)
+        .accountsPartial({ authority: stranger.publicKey, config: configPDA(program) })
+        .signers([stranger])
+        .rpc(),
+      "Unauthorized"
+    );
+  });
+
+  it("Rejects an empty or over-long URI prefix", async () => {
+    await expectRejected(setUriPrefix("", true), "UriPrefixEmpty");
+    await expectRejected(setUriPrefix("https://" + "a".repeat(64), true), "UriPrefixTooLong");
+  });
+});
@@ -0,0 +1,98 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID, getTokenMetadata } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  GRADUATING_BUY,
+  airdrop,
+  buy2022,
+  configPDA,
+  createToken2022,
+  curveAccounts,
+  ensureConfig,
+  uniqueSymbol,
+} from "./helpers/launch";
+
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+describe("metadata updates", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const buyer = Keypair.generate();
+  const mint = Keypair.generate();
+  const symbol = uniqueSymbol("U");
+
+  let feeRecipient: PublicKey;
+
+  // Keeps the symbol, so no registry records are involved
+  const update = (signer: Keypair, name: string, uri: string) =>
+    program.methods
+      .updateTokenMetadata(name, symbol, uri)
+      .accountsPartial({
+        creator: signer.publicKey,
+        config: configPDA(program),
+        bondingCurve: curveAccounts(program, mint.publicKey).bondingCurve,
+        mint: mint.publicKey,
+        symbolRecord: null,
+        oldSymbolRecord: null,
+        oldSymbolPayer: null,
+        metadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.publicKey.toBuffer()],
+          TOKEN_METADATA_PROGRAM_ID
+        )[0],
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, buyer.publicKey, 130);
+    feeRecipient = await ensureConfig(program, provider);
+    await createToken2022(program, creator, mint, { symbol });
+  });
+
+  it("Lets the creator fix the name and URI while the curve trades", async () => {
+    await update(creator, "Fixed Token", "https://example.com/fixed.json");
+
+    const metadata = await getTokenMetadata(provider.connection, mint.publicKey);
+    expect(metadata!.name).to.equal("Fixed Token");
+    expect(metadata!.symbol).to.equal(symbol);
+    expect(metadata!.uri).to.equal("https://example.com/fixed.json");
+  });
+
+  it("Rejects updates from anyone but the creator", async () => {
+    try {
+      await update(buyer, "Hijacked", "https://example.com/hijacked.json");
+      expect.fail("Should have thrown Unauthorized");
+    } catch (e: any) {
+      expect(e.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Applies the launch length limits", async () => {
+    try {
+      await update(creator, "x".repeat(33), "https://example.com/fixed.json");
+      expect.fail("Should have thrown NameTooLong");
+    } catch (e: any) {
+      expect(e.message).to.include("NameTooLong");
+    }
+  });
+
+  it("Rejects updates once the curve has graduated", async () => {
+    await buy2022(program, buyer, mint.publicKey, creator.publicKey, feeRecipient, GRADUATING_BUY).rpc();
+    try {
+      await update(creator, "Too Late", "https://example.com/late.json");
+      expect.fail("Should have thrown AlreadyGraduated");
+    } catch (e: any) {
+      expect(e.message).to.include("AlreadyGraduated");
+    }
+  });
+});
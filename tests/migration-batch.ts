@@ -0,0 +1,117 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID, createAssociatedTokenAccountIdempotent } from "@solana/spl-token";
+import { expect } from "chai";
+import { cuLimit, expectWithinCuLimit } from "./helpers/compute-units";
+import {
+  GRADUATING_BUY,
+  airdrop,
+  buy2022,
+  configPDA,
+  createToken2022,
+  curveAccounts,
+  ensureConfig,
+  errorCode,
+} from "./helpers/launch";
+
+const RAYDIUM_CPMM = { raydiumCpmm: {} };
+
+describe("migration batch", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const buyer = Keypair.generate();
+  // Released together by the batch: two graduated, one still trading
+  const batched = [Keypair.generate(), Keypair.generate()];
+  const trading = Keypair.generate();
+
+  let feeRecipient: PublicKey;
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 10);
+    await airdrop(provider, buyer.publicKey, 260);
+    feeRecipient = await ensureConfig(program, provider);
+
+    for (const mint of batched) {
+      await createToken2022(program, creator, mint);
+      await buy2022(program, buyer, mint.publicKey, creator.publicKey, feeRecipient, GRADUATING_BUY).rpc();
+    }
+    await createToken2022(program, creator, trading);
+
+    // The batch expects every escrow token account to exist
+    for (const mint of [...batched, trading]) {
+      await createAssociatedTokenAccountIdempotent(
+        provider.connection,
+        creator,
+        mint.publicKey,
+        curveAccounts(program, mint.publicKey).migrationEscrow,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+        undefined,
+        true
+      );
+    }
+  });
+
+  it("Releases a batch, reporting the curve that isn't graduated", async () => {
+    const mints = [...batched, trading].map((mint) => mint.publicKey);
+    const remainingAccounts = mints.flatMap((mint) => {
+      const accounts = curveAccounts(program, mint);
+      return [
+        accounts.bondingCurve,
+        mint,
+        accounts.solVault,
+        accounts.tokenVault,
+        accounts.migrationEscrow,
+        accounts.escrowTokenAccount,
+        creator.publicKey,
+      ]
+        .map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))
+        .concat({ pubkey: program.programId, isWritable: false, isSigner: false });
+    });
+
+    const signature = await program.methods
+      .releaseForMigrationBatch(RAYDIUM_CPMM)
+      .accountsPartial({
+        operator: provider.wallet.publicKey,
+        config: configPDA(program),
+        feeRecipient,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+    await expectWithinCuLimit(
+      provider.connection,
+      signature,
+      cuLimit(program, "CU_LIMIT_ADMIN") +
+        mints.length * cuLimit(program, "CU_LIMIT_RELEASE_FOR_MIGRATION_BATCH_PER_CURVE"),
+      "release_for_migration_batch"
+    );
+
+    // Vec<ReleaseResult>: u32 length, then (mint, error_code u32) per curve
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const data = Buffer.from(tx!.meta!.returnData!.data[0], "base64");
+    expect(data.readUInt32LE(0)).to.equal(mints.length);
+    const results = mints.map((_, i) => ({
+      mint: new PublicKey(data.subarray(4 + i * 36, 36 + i * 36)),
+      errorCode: data.readUInt32LE(36 + i * 36),
+    }));
+    expect(results.map((r) => r.mint.toBase58())).to.deep.equal(mints.map((m) => m.toBase58()));
+    expect(results.map((r) => r.errorCode)).to.deep.equal([0, 0, errorCode(program, "NotGraduated")]);
+
+    for (const mint of batched) {
+      const curve = await program.account.bondingCurve.fetch(curveAccounts(program, mint.publicKey).bondingCurve);
+      expect(curve.status).to.deep.equal({ migrating: {} });
+    }
+    const curve = await program.account.bondingCurve.fetch(curveAccounts(program, trading.publicKey).bondingCurve);
+    expect(curve.status).to.deep.equal({ trading: {} });
+  });
+});
@@ -0,0 +1,318 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import {
+  NATIVE_MINT,
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotent,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { cuLimit, expectWithinCuLimit } from "./helpers/compute-units";
+import {
+  GRADUATING_BUY,
+  airdrop,
+  approveMigrationDestination,
+  ata2022,
+  buy2022,
+  configPDA,
+  crankMigration,
+  createToken2022,
+  curveAccounts,
+  ensureConfig,
+  validatorTime,
+} from "./helpers/launch";
+
+const RAYDIUM_CPMM = { raydiumCpmm: {} };
+
+describe("migration", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const buyer = Keypair.generate();
+  const migrationWallet = Keypair.generate();
+  // Released, then claimed, finalized and refunded
+  const escrowed = Keypair.generate();
+
+  let feeRecipient: PublicKey;
+
+  const revokeMigrationWallet = () =>
+    program.methods
+      .setMigrationDestination({ wallet: {} }, migrationWallet.publicKey, false)
+      .accountsPartial({
+        authority: provider.wallet.publicKey,
+        config: configPDA(program),
+      })
+      .rpc();
+
+  const release = (mint: PublicKey, dex: any) => {
+    const accounts = curveAccounts(program, mint);
+    return program.methods
+      .releaseForMigration(dex)
+      .accountsPartial({
+        operator: provider.wallet.publicKey,
+        config: configPDA(program),
+        bondingCurve: accounts.bondingCurve,
+        solVault: accounts.solVault,
+        tokenVault: accounts.tokenVault,
+        tokenMint: mint,
+        migrationEscrow: accounts.migrationEscrow,
+        escrowTokenAccount: accounts.escrowTokenAccount,
+        feeRecipient,
+        creator: creator.publicKey,
+        excessTokenAccount: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+  };
+
+  const claim = (mint: PublicKey) => {
+    const accounts = curveAccounts(program, mint);
+    return program.methods
+      .claimMigrationEscrow()
+      .accountsPartial({
+        operator: provider.wallet.publicKey,
+        config: configPDA(program),
+        bondingCurve: accounts.bondingCurve,
+        mint,
+        migrationEscrow: accounts.migrationEscrow,
+        escrowTokenAccount: accounts.escrowTokenAccount,
+        migrationWallet: migrationWallet.publicKey,
+        migrationTokenAccount: ata2022(mint, migrationWallet.publicKey),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      });
+  };
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 10);
+    await airdrop(provider, buyer.publicKey, 130);
+    await airdrop(provider, migrationWallet.publicKey, 1);
+    feeRecipient = await ensureConfig(program, provider);
+    await approveMigrationDestination(program, provider, { wallet: {} }, migrationWallet.publicKey);
+
+    await createToken2022(program, creator, escrowed);
+    await buy2022(program, buyer, escrowed.publicKey, creator.publicKey, feeRecipient, GRADUATING_BUY).rpc();
+
+    await createAssociatedTokenAccountIdempotent(
+      provider.connection,
+      migrationWallet,
+      escrowed.publicKey,
+      migrationWallet.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await createAssociatedTokenAccountIdempotent(
+      provider.connection,
+      migrationWallet,
+      NATIVE_MINT,
+      migrationWallet.publicKey
+    );
+  });
+
+  after(async () => {
+    await revokeMigrationWallet();
+  });
+
+  it("Rejects releasing to an unsupported DEX", async () => {
+    try {
+      // The built-in pool is only reachable through crank_migration
+      await release(escrowed.publicKey, { clawdVault: {} });
+      expect.fail("Should have thrown UnsupportedMigrationDex");
+    } catch (e: any) {
+      expect(e.message).to.include("UnsupportedMigrationDex");
+    }
+  });
+
+  it("Releases a graduated curve into the migration escrow", async () => {
+    const accounts = curveAccounts(program, escrowed.publicKey);
+    const before = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+
+    const signature = await release(escrowed.publicKey, RAYDIUM_CPMM);
+    await expectWithinCuLimit(
+      provider.connection,
+      signature,
+      cuLimit(program, "CU_LIMIT_RELEASE_FOR_MIGRATION"),
+      "release_for_migration"
+    );
+
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.status).to.deep.equal({ migrating: {} });
+    expect(curve.migrationDex).to.deep.equal(RAYDIUM_CPMM);
+    expect(curve.realSolReserves.toNumber()).to.equal(0);
+    expect(curve.migrationSolAmount.toString()).to.equal(before.realSolReserves.toString());
+
+    const escrowLamports = await provider.connection.getBalance(accounts.migrationEscrow);
+    expect(escrowLamports).to.be.at.least(curve.migrationSolAmount.toNumber());
+    const escrowTokens = await provider.connection.getTokenAccountBalance(accounts.escrowTokenAccount);
+    expect(escrowTokens.value.amount).to.equal(curve.migrationTokenAmount.toString());
+  });
+
+  it("Skips a crank that races the operator's release", async () => {
+    const accounts = curveAccounts(program, escrowed.publicKey);
+    const before = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+
+    await crankMigration(program, buyer, escrowed.publicKey, creator.publicKey, feeRecipient).rpc();
+
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.status).to.deep.equal({ migrating: {} });
+    expect(curve.migrationSolAmount.toString()).to.equal(before.migrationSolAmount.toString());
+    expect(await provider.connection.getAccountInfo(accounts.pool)).to.be.null;
+  });
+
+  it("Rejects claiming the escrow without a finalize in the same transaction", async () => {
+    try {
+      await claim(escrowed.publicKey).rpc();
+      expect.fail("Should have thrown MigrationNotFinalized");
+    } catch (e: any) {
+      expect(e.message).to.include("MigrationNotFinalized");
+    }
+  });
+
+  it("Rolls the claim back when finalize rejects the pool", async () => {
+    const accounts = curveAccounts(program, escrowed.publicKey);
+    const walletTokens = ata2022(escrowed.publicKey, migrationWallet.publicKey);
+    const finalize = await program.methods
+      .finalizeMigration({ burned: {} })
+      .accountsPartial({
+        operator: provider.wallet.publicKey,
+        config: configPDA(program),
+        bondingCurve: accounts.bondingCurve,
+        // A system account, not owned by any allow-listed AMM
+        pool: migrationWallet.publicKey,
+        poolSolVault: getAssociatedTokenAddressSync(NATIVE_MINT, migrationWallet.publicKey, false, TOKEN_PROGRAM_ID),
+        poolTokenVault: walletTokens,
+        migrationWallet: migrationWallet.publicKey,
+        lpMint: escrowed.publicKey,
+        lpTokenAccount: walletTokens,
+        lpLockTokenAccount: null,
+        lpLock: PublicKey.findProgramAddressSync(
+          [Buffer.from("lp_lock"), escrowed.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        lpTokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .instruction();
+
+    try {
+      await claim(escrowed.publicKey).postInstructions([finalize]).signers([migrationWallet]).rpc();
+      expect.fail("Should have thrown InvalidMigrationPool");
+    } catch (e: any) {
+      expect(e.message).to.include("InvalidMigrationPool");
+    }
+
+    // Nothing left the escrow
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.status).to.deep.equal({ migrating: {} });
+    expect(curve.migrationWallet.toBase58()).to.equal(PublicKey.default.toBase58());
+    const escrowTokens = await provider.connection.getTokenAccountBalance(accounts.escrowTokenAccount);
+    expect(escrowTokens.value.amount).to.equal(curve.migrationTokenAmount.toString());
+  });
+
+  it("Rejects refunding the escrow before the migration timeout", async () => {
+    const accounts = curveAccounts(program, escrowed.publicKey);
+    try {
+      await program.methods
+        .refundMigration()
+        .accountsPartial({
+          signer: provider.wallet.publicKey,
+          bondingCurve: accounts.bondingCurve,
+          mint: escrowed.publicKey,
+          migrationEscrow: accounts.migrationEscrow,
+          escrowTokenAccount: accounts.escrowTokenAccount,
+          solVault: accounts.solVault,
+          tokenVault: accounts.tokenVault,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Should have thrown MigrationTimeoutNotReached");
+    } catch (e: any) {
+      expect(e.message).to.include("MigrationTimeoutNotReached");
+    }
+  });
+});
+
+describe("migration destinations", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const wallet = Keypair.generate().publicKey;
+  const accounts = () => ({ authority: provider.wallet.publicKey, config: configPDA(program) });
+
+  const setWallet = (allowed: boolean) =>
+    program.methods.setMigrationDestination({ wallet: {} }, wallet, allowed).accountsPartial(accounts()).rpc();
+
+  const applyWallet = () =>
+    program.methods.applyMigrationDestination({ wallet: {} }, wallet).accountsPartial(accounts()).rpc();
+
+  const config = () => program.account.config.fetch(configPDA(program));
+
+  before(async () => {
+    await ensureConfig(program, provider);
+  });
+
+  it("Queues an approval instead of adding it to the allow-list", async () => {
+    const now = await validatorTime(provider);
+    await setWallet(true);
+
+    const state = await config();
+    expect(state.migrationWallets.map((key) => key.toBase58())).to.not.include(wallet.toBase58());
+    const pending = state.pendingMigrationDestinations.find((entry) => entry.destination.equals(wallet));
+    expect(pending!.kind).to.deep.equal({ wallet: {} });
+    expect(pending!.activatesAt.toNumber()).to.be.greaterThan(now);
+  });
+
+  it("Rejects applying an approval before its delay has passed", async () => {
+    try {
+      await applyWallet();
+      expect.fail("Should have thrown TimelockNotElapsed");
+    } catch (e: any) {
+      expect(e.message).to.include("TimelockNotElapsed");
+    }
+  });
+
+  it("Cancels a queued approval on revoke", async () => {
+    await setWallet(false);
+
+    const state = await config();
+    expect(state.pendingMigrationDestinations.find((entry) => entry.destination.equals(wallet))).to.be.undefined;
+    try {
+      await applyWallet();
+      expect.fail("Should have thrown MigrationDestinationNotQueued");
+    } catch (e: any) {
+      expect(e.message).to.include("MigrationDestinationNotQueued");
+    }
+  });
+
+  it("Rejects approving an AMM program whose pools can't be decoded", async () => {
+    const orcaWhirlpool = new PublicKey("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+    try {
+      await program.methods
+        .setMigrationDestination({ ammProgram: {} }, orcaWhirlpool, true)
+        .accountsPartial(accounts())
+        .rpc();
+      expect.fail("Should have thrown UnsupportedMigrationDex");
+    } catch (e: any) {
+      expect(e.message).to.include("UnsupportedMigrationDex");
+    }
+  });
+
+  it("Applies an approval once its delay has passed and revokes it immediately", async () => {
+    await approveMigrationDestination(program, provider, { wallet: {} }, wallet);
+
+    let state = await config();
+    expect(state.migrationWallets.map((key) => key.toBase58())).to.include(wallet.toBase58());
+    expect(state.pendingMigrationDestinations.find((entry) => entry.destination.equals(wallet))).to.be.undefined;
+
+    await setWallet(false);
+
+    state = await config();
+    expect(state.migrationWallets.map((key) => key.toBase58())).to.not.include(wallet.toBase58());
+  });
+});
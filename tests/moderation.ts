@@ -0,0 +1,186 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  GRADUATING_BUY,
+  airdrop,
+  ata2022,
+  buy2022,
+  configPDA,
+  crankMigration,
+  createToken2022,
+  curveAccounts,
+  ensureConfig,
+} from "./helpers/launch";
+
+const FLAG_BUYS_HALTED = 16;
+
+describe("moderation", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const trader = Keypair.generate();
+  const moderator = Keypair.generate();
+  const mint = Keypair.generate();
+  const accounts = curveAccounts(program, mint.publicKey);
+
+  let feeRecipient: PublicKey;
+  let oldModerator: PublicKey;
+
+  const setModerator = (newModerator: PublicKey) =>
+    program.methods
+      .setModerator(newModerator)
+      .accountsPartial({
+        authority: provider.wallet.publicKey,
+        config: configPDA(program),
+      })
+      .rpc();
+
+  const setCurveFlags = (signer: Keypair, flags: number, bondingCurve = accounts.bondingCurve) =>
+    program.methods
+      .setCurveFlags(flags)
+      .accountsPartial({
+        moderator: signer.publicKey,
+        config: configPDA(program),
+        bondingCurve,
+      })
+      .signers([signer])
+      .rpc();
+
+  const buy = () =>
+    buy2022(program, trader, mint.publicKey, creator.publicKey, feeRecipient, new BN(LAMPORTS_PER_SOL / 10)).rpc();
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, trader.publicKey, 135);
+    await airdrop(provider, moderator.publicKey, 1);
+    feeRecipient = await ensureConfig(program, provider);
+
+    oldModerator = (await program.account.config.fetch(configPDA(program))).moderator;
+    await createToken2022(program, creator, mint);
+    await buy();
+  });
+
+  after(async () => {
+    await setModerator(oldModerator);
+  });
+
+  it("Rejects flags from a wallet that isn't the moderator", async () => {
+    try {
+      await setCurveFlags(moderator, FLAG_BUYS_HALTED);
+      expect.fail("Should have thrown Unauthorized");
+    } catch (e: any) {
+      expect(e.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Lets the moderator halt buys while holders can still sell", async () => {
+    await setModerator(moderator.publicKey);
+    await setCurveFlags(moderator, FLAG_BUYS_HALTED);
+
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.flags).to.equal(FLAG_BUYS_HALTED);
+
+    try {
+      await buy();
+      expect.fail("Should have thrown BuysHalted");
+    } catch (e: any) {
+      expect(e.message).to.include("BuysHalted");
+    }
+
+    const tokenAccount = ata2022(mint.publicKey, trader.publicKey);
+    const balance = (await provider.connection.getTokenAccountBalance(tokenAccount)).value.amount;
+    await program.methods
+      .sell(new BN(balance), new BN(0))
+      .accountsPartial({
+        seller: trader.publicKey,
+        bondingCurve: accounts.bondingCurve,
+        config: configPDA(program),
+        mint: mint.publicKey,
+        solVault: accounts.solVault,
+        tokenVault: accounts.tokenVault,
+        sellerTokenAccount: tokenAccount,
+        feeRecipient,
+        creator: creator.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([trader])
+      .rpc();
+    expect((await provider.connection.getTokenAccountBalance(tokenAccount)).value.amount).to.equal("0");
+  });
+
+  it("Rejects unknown flag bits", async () => {
+    try {
+      await setCurveFlags(moderator, 1 << 7);
+      expect.fail("Should have thrown InvalidCurveFlags");
+    } catch (e: any) {
+      expect(e.message).to.include("InvalidCurveFlags");
+    }
+  });
+
+  it("Resumes buys once the flag is cleared", async () => {
+    await setCurveFlags(moderator, 0);
+    await buy();
+
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.flags).to.equal(0);
+  });
+
+  it("Lets the authority set flags without being the moderator", async () => {
+    await setCurveFlags((provider.wallet as anchor.Wallet).payer, FLAG_BUYS_HALTED);
+    expect((await program.account.bondingCurve.fetch(accounts.bondingCurve)).flags).to.equal(FLAG_BUYS_HALTED);
+
+    await setCurveFlags((provider.wallet as anchor.Wallet).payer, 0);
+    expect((await program.account.bondingCurve.fetch(accounts.bondingCurve)).flags).to.equal(0);
+  });
+
+  it("Halts buys from the pool of a migrated curve but not sells", async () => {
+    const poolMint = Keypair.generate();
+    const poolAccounts = curveAccounts(program, poolMint.publicKey);
+    await createToken2022(program, creator, poolMint);
+    await buy2022(program, trader, poolMint.publicKey, creator.publicKey, feeRecipient, GRADUATING_BUY).rpc();
+    await crankMigration(program, trader, poolMint.publicKey, creator.publicKey, feeRecipient).rpc();
+
+    const tokenAccount = ata2022(poolMint.publicKey, trader.publicKey);
+    const swap = (isBuy: boolean, amountIn: BN) =>
+      program.methods
+        .swap(isBuy, amountIn, new BN(0))
+        .accountsPartial({
+          user: trader.publicKey,
+          config: configPDA(program),
+          bondingCurve: poolAccounts.bondingCurve,
+          pool: poolAccounts.pool,
+          mint: poolMint.publicKey,
+          poolSolVault: poolAccounts.poolSolVault,
+          poolTokenVault: poolAccounts.poolTokenVault,
+          userTokenAccount: tokenAccount,
+          feeRecipient,
+          creator: creator.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([trader])
+        .rpc();
+
+    await setCurveFlags(moderator, FLAG_BUYS_HALTED, poolAccounts.bondingCurve);
+    try {
+      await swap(true, new BN(LAMPORTS_PER_SOL / 10));
+      expect.fail("Should have thrown BuysHalted");
+    } catch (e: any) {
+      expect(e.message).to.include("BuysHalted");
+    }
+
+    const before = new BN((await provider.connection.getTokenAccountBalance(tokenAccount)).value.amount);
+    await swap(false, before.divn(2));
+    const after = new BN((await provider.connection.getTokenAccountBalance(tokenAccount)).value.amount);
+    expect(after.lt(before)).to.be.true;
+
+    await setCurveFlags(moderator, 0, poolAccounts.bondingCurve);
+  });
+});
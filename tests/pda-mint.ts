@@ -0,0 +1,181 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair, SystemProgram, Transaction, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+  getMint,
+  getTokenMetadata,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { airdrop, ata2022, configPDA, curveAccounts, ensureConfig, uniqueSymbol } from "./helpers/launch";
+
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+describe("PDA mints", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const nonce = new BN(42);
+
+  const mintPDA = (nonce: BN) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("mint"), creator.publicKey.toBuffer(), nonce.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  // Only the creator signs: the program signs for the mint with its seeds
+  const createTokenPda = (nonce: BN, mint: PublicKey) => {
+    const accounts = curveAccounts(program, mint);
+    const ata = (owner: PublicKey) => getAssociatedTokenAddressSync(mint, owner, true, TOKEN_PROGRAM_ID);
+    return program.methods
+      .createTokenPda("PDA Token", uniqueSymbol("M"), "https://example.com/pda.json", new BN(0), new BN(0), nonce, null)
+      .accountsPartial({
+        creator: creator.publicKey,
+        payer: creator.publicKey,
+        config: configPDA(program),
+        mint,
+        metadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+          TOKEN_METADATA_PROGRAM_ID
+        )[0],
+        bondingCurve: accounts.bondingCurve,
+        solVault: accounts.solVault,
+        tokenVault: ata(accounts.bondingCurve),
+        creatorTokenAccount: ata(creator.publicKey),
+        symbolRecord: null,
+        tokenProfile: null,
+        collectionMint: null,
+        collectionMetadata: null,
+        collectionMasterEdition: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+  };
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await ensureConfig(program, provider);
+  });
+
+  it("Creates the token at the address derived from the creator and nonce", async () => {
+    const mint = mintPDA(nonce);
+    expect(await provider.connection.getAccountInfo(mint)).to.be.null;
+
+    await createTokenPda(nonce, mint);
+
+    const mintAccount = await getMint(provider.connection, mint, undefined, TOKEN_PROGRAM_ID);
+    expect(mintAccount.mintAuthority).to.be.null;
+    const curve = await program.account.bondingCurve.fetch(curveAccounts(program, mint).bondingCurve);
+    expect(curve.mint.toBase58()).to.equal(mint.toBase58());
+    expect(curve.creator.toBase58()).to.equal(creator.publicKey.toBase58());
+  });
+
+  it("Rejects a mint that isn't the PDA of the nonce", async () => {
+    try {
+      await createTokenPda(new BN(43), mintPDA(new BN(44)));
+      expect.fail("Should have thrown InvalidAccountData");
+    } catch (e: any) {
+      expect(e.message).to.include("InvalidAccountData");
+    }
+  });
+
+  it("Rejects reusing a nonce", async () => {
+    try {
+      await createTokenPda(nonce, mintPDA(nonce));
+      expect.fail("Should have failed on the existing accounts");
+    } catch (e: any) {
+      // System program AccountAlreadyInUse
+      expect(e.message).to.match(/already in use|custom program error: 0x0/);
+    }
+  });
+});
+
+describe("Token-2022 PDA mints", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+
+  const mintPDA = (nonce: BN) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("mint"), creator.publicKey.toBuffer(), nonce.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  const createToken2022Pda = (nonce: BN, mint: PublicKey) => {
+    const accounts = curveAccounts(program, mint);
+    return program.methods
+      .createToken2022Pda("PDA 2022", uniqueSymbol("Q"), "https://example.com/pda.json", new BN(0), new BN(0), 0, nonce, null)
+      .accountsPartial({
+        creator: creator.publicKey,
+        payer: creator.publicKey,
+        config: configPDA(program),
+        mint,
+        bondingCurve: accounts.bondingCurve,
+        solVault: accounts.solVault,
+        tokenVault: accounts.tokenVault,
+        creatorTokenAccount: ata2022(mint, creator.publicKey),
+        symbolRecord: null,
+        tokenProfile: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+  };
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await ensureConfig(program, provider);
+  });
+
+  it("Creates a Token-2022 token with in-mint metadata at the derived address", async () => {
+    const nonce = new BN(7);
+    const mint = mintPDA(nonce);
+
+    await createToken2022Pda(nonce, mint);
+
+    expect((await provider.connection.getAccountInfo(mint))!.owner.toBase58()).to.equal(
+      TOKEN_2022_PROGRAM_ID.toBase58()
+    );
+    const metadata = await getTokenMetadata(provider.connection, mint);
+    expect(metadata!.name).to.equal("PDA 2022");
+    const curve = await program.account.bondingCurve.fetch(curveAccounts(program, mint).bondingCurve);
+    expect(curve.tokenProgram.toBase58()).to.equal(TOKEN_2022_PROGRAM_ID.toBase58());
+  });
+
+  it("Takes over a derived address that was funded before the launch", async () => {
+    const nonce = new BN(8);
+    const mint = mintPDA(nonce);
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({ fromPubkey: provider.wallet.publicKey, toPubkey: mint, lamports: LAMPORTS_PER_SOL / 100 })
+      )
+    );
+
+    await createToken2022Pda(nonce, mint);
+
+    const mintAccount = await getMint(provider.connection, mint, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(mintAccount.mintAuthority).to.be.null;
+    expect((await getTokenMetadata(provider.connection, mint))!.name).to.equal("PDA 2022");
+  });
+
+  it("Rejects a mint that isn't the PDA of the nonce", async () => {
+    try {
+      await createToken2022Pda(new BN(9), mintPDA(new BN(10)));
+      expect.fail("Should have thrown InvalidAccountData");
+    } catch (e: any) {
+      expect(e.message).to.include("InvalidAccountData");
+    }
+  });
+});
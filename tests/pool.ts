@@ -0,0 +1,181 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { cuLimit, expectWithinCuLimit } from "./helpers/compute-units";
+import {
+  GRADUATING_BUY,
+  airdrop,
+  ata2022,
+  buy2022,
+  configPDA,
+  crankMigration,
+  createToken2022,
+  curveAccounts,
+  ensureConfig,
+} from "./helpers/launch";
+
+describe("pool", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const trader = Keypair.generate();
+  const mint = Keypair.generate();
+  const accounts = curveAccounts(program, mint.publicKey);
+
+  let feeRecipient: PublicKey;
+
+  const tokenBalance = async (account: PublicKey) =>
+    new BN((await provider.connection.getTokenAccountBalance(account)).value.amount);
+
+  const swap = (isBuy: boolean, amountIn: BN, minOut: BN) =>
+    program.methods
+      .swap(isBuy, amountIn, minOut)
+      .accountsPartial({
+        user: trader.publicKey,
+        config: configPDA(program),
+        bondingCurve: accounts.bondingCurve,
+        pool: accounts.pool,
+        mint: mint.publicKey,
+        poolSolVault: accounts.poolSolVault,
+        poolTokenVault: accounts.poolTokenVault,
+        userTokenAccount: ata2022(mint.publicKey, trader.publicKey),
+        feeRecipient,
+        creator: creator.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([trader]);
+
+  const liquidityAccounts = () => ({
+    user: trader.publicKey,
+    pool: accounts.pool,
+    mint: mint.publicKey,
+    lpMint: accounts.lpMint,
+    poolSolVault: accounts.poolSolVault,
+    poolTokenVault: accounts.poolTokenVault,
+    userTokenAccount: ata2022(mint.publicKey, trader.publicKey),
+    userLpAccount: ata2022(accounts.lpMint, trader.publicKey),
+    tokenProgram: TOKEN_2022_PROGRAM_ID,
+  });
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, trader.publicKey, 140);
+    feeRecipient = await ensureConfig(program, provider);
+
+    await createToken2022(program, creator, mint);
+    await buy2022(program, trader, mint.publicKey, creator.publicKey, feeRecipient, GRADUATING_BUY).rpc();
+    await crankMigration(program, trader, mint.publicKey, creator.publicKey, feeRecipient).rpc();
+
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.status).to.deep.equal({ migrated: {} });
+  });
+
+  it("Buys from the pool", async () => {
+    const poolBefore = await program.account.pool.fetch(accounts.pool);
+    const tokensBefore = await tokenBalance(ata2022(mint.publicKey, trader.publicKey));
+    const amountIn = new BN(LAMPORTS_PER_SOL);
+
+    const signature = await swap(true, amountIn, new BN(1)).rpc();
+    await expectWithinCuLimit(provider.connection, signature, cuLimit(program, "CU_LIMIT_SWAP"), "swap (buy)");
+
+    const poolAfter = await program.account.pool.fetch(accounts.pool);
+    // 0.25% pool fee stays out of the reserves
+    const solIn = poolAfter.solReserves.sub(poolBefore.solReserves);
+    expect(solIn.toNumber()).to.equal(amountIn.toNumber() * 9975 / 10000);
+    const tokensOut = poolBefore.tokenReserves.sub(poolAfter.tokenReserves);
+    expect(tokensOut.gtn(0)).to.be.true;
+
+    const tokensAfter = await tokenBalance(ata2022(mint.publicKey, trader.publicKey));
+    expect(tokensAfter.sub(tokensBefore).toString()).to.equal(tokensOut.toString());
+  });
+
+  it("Sells into the pool", async () => {
+    const poolBefore = await program.account.pool.fetch(accounts.pool);
+    const solBefore = await provider.connection.getBalance(trader.publicKey);
+    const amountIn = (await tokenBalance(ata2022(mint.publicKey, trader.publicKey))).divn(10);
+
+    const signature = await swap(false, amountIn, new BN(1)).rpc();
+    await expectWithinCuLimit(provider.connection, signature, cuLimit(program, "CU_LIMIT_SWAP"), "swap (sell)");
+
+    const poolAfter = await program.account.pool.fetch(accounts.pool);
+    expect(poolAfter.tokenReserves.sub(poolBefore.tokenReserves).toString()).to.equal(amountIn.toString());
+    expect(poolAfter.solReserves.lt(poolBefore.solReserves)).to.be.true;
+    const solAfter = await provider.connection.getBalance(trader.publicKey);
+    expect(solAfter).to.be.greaterThan(solBefore);
+  });
+
+  it("Rejects a swap below the minimum output", async () => {
+    try {
+      await swap(true, new BN(LAMPORTS_PER_SOL), new BN("18446744073709551615")).rpc();
+      expect.fail("Should have thrown SlippageExceeded");
+    } catch (e: any) {
+      expect(e.message).to.include("SlippageExceeded");
+    }
+  });
+
+  it("Deposits liquidity at the pool ratio", async () => {
+    const poolBefore = await program.account.pool.fetch(accounts.pool);
+    const lpAmount = poolBefore.lpSupply.divn(100);
+
+    const signature = await program.methods
+      .deposit(lpAmount, new BN(10 * LAMPORTS_PER_SOL), new BN("18446744073709551615"))
+      .accountsPartial(liquidityAccounts())
+      .signers([trader])
+      .rpc();
+    await expectWithinCuLimit(provider.connection, signature, cuLimit(program, "CU_LIMIT_DEPOSIT"), "deposit");
+
+    const poolAfter = await program.account.pool.fetch(accounts.pool);
+    expect(poolAfter.lpSupply.sub(poolBefore.lpSupply).toString()).to.equal(lpAmount.toString());
+    // Rounded up, so existing LPs are never diluted
+    const solIn = poolAfter.solReserves.sub(poolBefore.solReserves);
+    expect(solIn.mul(poolBefore.lpSupply).gte(lpAmount.mul(poolBefore.solReserves))).to.be.true;
+    const tokensIn = poolAfter.tokenReserves.sub(poolBefore.tokenReserves);
+    expect(tokensIn.mul(poolBefore.lpSupply).gte(lpAmount.mul(poolBefore.tokenReserves))).to.be.true;
+
+    const lpBalance = await tokenBalance(ata2022(accounts.lpMint, trader.publicKey));
+    expect(lpBalance.toString()).to.equal(lpAmount.toString());
+  });
+
+  it("Rejects a deposit above the maximum SOL in", async () => {
+    const pool = await program.account.pool.fetch(accounts.pool);
+    try {
+      await program.methods
+        .deposit(pool.lpSupply.divn(100), new BN(1), new BN("18446744073709551615"))
+        .accountsPartial(liquidityAccounts())
+        .signers([trader])
+        .rpc();
+      expect.fail("Should have thrown SlippageExceeded");
+    } catch (e: any) {
+      expect(e.message).to.include("SlippageExceeded");
+    }
+  });
+
+  it("Withdraws liquidity", async () => {
+    const poolBefore = await program.account.pool.fetch(accounts.pool);
+    const lpAmount = await tokenBalance(ata2022(accounts.lpMint, trader.publicKey));
+    const tokensBefore = await tokenBalance(ata2022(mint.publicKey, trader.publicKey));
+
+    const signature = await program.methods
+      .withdraw(lpAmount, new BN(1), new BN(1))
+      .accountsPartial(liquidityAccounts())
+      .signers([trader])
+      .rpc();
+    await expectWithinCuLimit(provider.connection, signature, cuLimit(program, "CU_LIMIT_WITHDRAW"), "withdraw");
+
+    const poolAfter = await program.account.pool.fetch(accounts.pool);
+    expect(poolBefore.lpSupply.sub(poolAfter.lpSupply).toString()).to.equal(lpAmount.toString());
+    expect(poolAfter.solReserves.lt(poolBefore.solReserves)).to.be.true;
+    expect(poolAfter.tokenReserves.lt(poolBefore.tokenReserves)).to.be.true;
+
+    expect((await tokenBalance(ata2022(accounts.lpMint, trader.publicKey))).toNumber()).to.equal(0);
+    const tokensAfter = await tokenBalance(ata2022(mint.publicKey, trader.publicKey));
+    expect(tokensAfter.gt(tokensBefore)).to.be.true;
+  });
+});
@@ -0,0 +1,137 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { Keypair } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  ProfileArgs,
+  airdrop,
+  ata2022,
+  configPDA,
+  createToken2022,
+  curveAccounts,
+  ensureConfig,
+  profilePDA,
+  uniqueSymbol,
+} from "./helpers/launch";
+
+describe("token profile", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const stranger = Keypair.generate();
+  const mint = Keypair.generate();
+  const accounts = curveAccounts(program, mint.publicKey);
+  const tokenProfile = profilePDA(program, mint.publicKey);
+
+  const launchProfile: ProfileArgs = {
+    website: "https://example.com",
+    twitter: "clawdvault",
+    telegram: "clawdvault_chat",
+    agentId: "agent-7",
+    description: "Launched by an agent",
+  };
+
+  const setProfile = (signer: Keypair, args: ProfileArgs) =>
+    program.methods
+      .setTokenProfile(args.website, args.twitter, args.telegram, args.agentId, args.description)
+      .accountsPartial({
+        creator: signer.publicKey,
+        bondingCurve: accounts.bondingCurve,
+        tokenProfile,
+      })
+      .signers([signer])
+      .rpc();
+
+  const expectProfile = async (args: ProfileArgs) => {
+    const profile = await program.account.tokenProfile.fetch(tokenProfile);
+    expect(profile.mint.toBase58()).to.equal(mint.publicKey.toBase58());
+    expect(profile.website).to.equal(args.website);
+    expect(profile.twitter).to.equal(args.twitter);
+    expect(profile.telegram).to.equal(args.telegram);
+    expect(profile.agentId).to.equal(args.agentId);
+    expect(profile.description).to.equal(args.description);
+  };
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, stranger.publicKey, 1);
+    await ensureConfig(program, provider);
+  });
+
+  it("Writes the profile at launch", async () => {
+    await createToken2022(program, creator, mint, { profile: launchProfile });
+    await expectProfile(launchProfile);
+  });
+
+  it("Lets the creator replace every field", async () => {
+    const edited = { ...launchProfile, website: "", telegram: "", description: "Now with a pool" };
+    await setProfile(creator, edited);
+    await expectProfile(edited);
+  });
+
+  it("Rejects edits from anyone but the creator", async () => {
+    try {
+      await setProfile(stranger, launchProfile);
+      expect.fail("Should have thrown Unauthorized");
+    } catch (e: any) {
+      expect(e.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Rejects fields over their size bounds", async () => {
+    try {
+      await setProfile(creator, { ...launchProfile, twitter: "x".repeat(33) });
+      expect.fail("Should have thrown ProfileFieldTooLong");
+    } catch (e: any) {
+      expect(e.message).to.include("ProfileFieldTooLong");
+    }
+
+    try {
+      await setProfile(creator, { ...launchProfile, description: "x".repeat(201) });
+      expect.fail("Should have thrown DescriptionTooLong");
+    } catch (e: any) {
+      expect(e.message).to.include("DescriptionTooLong");
+    }
+  });
+
+  it("Rejects a profile passed without its account at launch", async () => {
+    const otherMint = Keypair.generate();
+    const otherAccounts = curveAccounts(program, otherMint.publicKey);
+    try {
+      await program.methods
+        .createToken2022(
+          "Test Token",
+          uniqueSymbol("P"),
+          "https://example.com/metadata.json",
+          new BN(0),
+          new BN(0),
+          0,
+          launchProfile
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          payer: creator.publicKey,
+          config: configPDA(program),
+          mint: otherMint.publicKey,
+          bondingCurve: otherAccounts.bondingCurve,
+          solVault: otherAccounts.solVault,
+          tokenVault: otherAccounts.tokenVault,
+          creatorTokenAccount: ata2022(otherMint.publicKey, creator.publicKey),
+          symbolRecord: null,
+          tokenProfile: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([creator, otherMint])
+        .rpc();
+      expect.fail("Should have thrown InvalidProfileAccount");
+    } catch (e: any) {
+      expect(e.message).to.include("InvalidProfileAccount");
+    }
+  });
+});
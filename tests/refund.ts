@@ -0,0 +1,322 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotent,
+  transferChecked,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { cuLimit, expectWithinCuLimit } from "./helpers/compute-units";
+import {
+  airdrop,
+  ata2022,
+  buy2022,
+  configPDA,
+  createToken2022,
+  curveAccounts,
+  ensureConfig,
+} from "./helpers/launch";
+
+const TRANSFER_FEE_BPS = 100;
+const DEADLINE_SECONDS = 8;
+
+describe("refund", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const holder = Keypair.generate();
+  const otherHolder = Keypair.generate();
+  const mint = Keypair.generate();
+  const accounts = curveAccounts(program, mint.publicKey);
+
+  let oldMaxTransferFee: number;
+  let deadline: number;
+
+  const validatorTime = async () =>
+    (await provider.connection.getBlockTime(await provider.connection.getSlot()))!;
+
+  const tokenBalance = async (owner: PublicKey) =>
+    new BN((await provider.connection.getTokenAccountBalance(ata2022(mint.publicKey, owner))).value.amount);
+
+  const setMaxTransferFee = (maxBps: number) =>
+    program.methods
+      .setMaxTransferFee(maxBps)
+      .accountsPartial({
+        authority: provider.wallet.publicKey,
+        config: configPDA(program),
+      })
+      .rpc();
+
+  const startRefund = () =>
+    program.methods
+      .startRefund()
+      .accountsPartial({
+        signer: provider.wallet.publicKey,
+        bondingCurve: accounts.bondingCurve,
+      })
+      .rpc();
+
+  const redeem = (user: Keypair, amount: BN, harvestSources: PublicKey[] = []) =>
+    program.methods
+      .redeem(amount)
+      .accountsPartial({
+        user: user.publicKey,
+        bondingCurve: accounts.bondingCurve,
+        mint: mint.publicKey,
+        solVault: accounts.solVault,
+        tokenVault: accounts.tokenVault,
+        userTokenAccount: ata2022(mint.publicKey, user.publicKey),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(harvestSources.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, holder.publicKey, 5);
+    await airdrop(provider, otherHolder.publicKey, 1);
+    const feeRecipient = await ensureConfig(program, provider);
+
+    oldMaxTransferFee = (await program.account.config.fetch(configPDA(program))).maxTransferFeeBps;
+    await setMaxTransferFee(TRANSFER_FEE_BPS);
+
+    deadline = (await validatorTime()) + DEADLINE_SECONDS;
+    await createToken2022(program, creator, mint, { deadline, transferFeeBps: TRANSFER_FEE_BPS });
+    await buy2022(program, holder, mint.publicKey, creator.publicKey, feeRecipient, new BN(LAMPORTS_PER_SOL)).rpc();
+
+    // A holder-to-holder transfer leaves a withheld fee in the receiving account
+    await createAssociatedTokenAccountIdempotent(
+      provider.connection,
+      otherHolder,
+      mint.publicKey,
+      otherHolder.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const amount = BigInt((await tokenBalance(holder.publicKey)).divn(3).toString());
+    await transferChecked(
+      provider.connection,
+      holder,
+      ata2022(mint.publicKey, holder.publicKey),
+      mint.publicKey,
+      ata2022(mint.publicKey, otherHolder.publicKey),
+      holder,
+      amount,
+      6,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+  });
+
+  after(async () => {
+    await setMaxTransferFee(oldMaxTransferFee);
+  });
+
+  it("Rejects starting a refund before the deadline", async () => {
+    try {
+      await startRefund();
+      expect.fail("Should have thrown DeadlineNotReached");
+    } catch (e: any) {
+      expect(e.message).to.include("DeadlineNotReached");
+    }
+  });
+
+  it("Starts the refund once the deadline has passed", async () => {
+    while ((await validatorTime()) <= deadline) {
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+    }
+    await startRefund();
+
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.status).to.deep.equal({ refunding: {} });
+  });
+
+  it("Redeems pro rata, leaving withheld transfer fees out of the denominator", async () => {
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    const holderTokens = await tokenBalance(holder.publicKey);
+    const otherTokens = await tokenBalance(otherHolder.publicKey);
+    const solBefore = await provider.connection.getBalance(holder.publicKey);
+
+    const signature = await redeem(holder, holderTokens, [ata2022(mint.publicKey, otherHolder.publicKey)]);
+    await expectWithinCuLimit(
+      provider.connection,
+      signature,
+      cuLimit(program, "CU_LIMIT_REDEEM") + 5_000,
+      "redeem"
+    );
+
+    // Only balances count: fees withheld on the buy and the transfer were harvested to the mint
+    const expected = holderTokens.mul(curve.realSolReserves).div(holderTokens.add(otherTokens));
+    const solAfter = await provider.connection.getBalance(holder.publicKey);
+    expect(solAfter - solBefore).to.be.closeTo(expected.toNumber(), 10_000);
+    expect((await tokenBalance(holder.publicKey)).toNumber()).to.equal(0);
+
+    const after = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(after.status).to.deep.equal({ refunding: {} });
+  });
+
+  it("Closes the curve once every circulating token is redeemed", async () => {
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    const tokens = await tokenBalance(otherHolder.publicKey);
+    const solBefore = await provider.connection.getBalance(otherHolder.publicKey);
+
+    await redeem(otherHolder, tokens);
+
+    const solAfter = await provider.connection.getBalance(otherHolder.publicKey);
+    expect(solAfter - solBefore).to.be.closeTo(curve.realSolReserves.toNumber(), 10_000);
+
+    const after = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(after.status).to.deep.equal({ closed: {} });
+    expect(after.realSolReserves.toNumber()).to.equal(0);
+  });
+});
+
+describe("refund close", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const holder = Keypair.generate();
+  const relay = Keypair.generate();
+  const mint = Keypair.generate();
+  const accounts = curveAccounts(program, mint.publicKey);
+
+  let feeRecipient: PublicKey;
+  let oldMaxTransferFee: number;
+
+  const validatorTime = async () =>
+    (await provider.connection.getBlockTime(await provider.connection.getSlot()))!;
+
+  const tokenBalance = async (owner: PublicKey) =>
+    BigInt((await provider.connection.getTokenAccountBalance(ata2022(mint.publicKey, owner))).value.amount);
+
+  const setMaxTransferFee = (maxBps: number) =>
+    program.methods
+      .setMaxTransferFee(maxBps)
+      .accountsPartial({
+        authority: provider.wallet.publicKey,
+        config: configPDA(program),
+      })
+      .rpc();
+
+  const transfer = async (from: Keypair, to: PublicKey, amount: bigint) =>
+    transferChecked(
+      provider.connection,
+      from,
+      ata2022(mint.publicKey, from.publicKey),
+      mint.publicKey,
+      ata2022(mint.publicKey, to),
+      from,
+      amount,
+      6,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+  const closeRefund = (harvestSources: PublicKey[] = []) =>
+    program.methods
+      .closeRefund()
+      .accountsPartial({
+        signer: provider.wallet.publicKey,
+        config: configPDA(program),
+        bondingCurve: accounts.bondingCurve,
+        mint: mint.publicKey,
+        solVault: accounts.solVault,
+        tokenVault: accounts.tokenVault,
+        feeRecipient,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(harvestSources.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+      .rpc();
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, holder.publicKey, 5);
+    await airdrop(provider, relay.publicKey, 1);
+    feeRecipient = await ensureConfig(program, provider);
+
+    oldMaxTransferFee = (await program.account.config.fetch(configPDA(program))).maxTransferFeeBps;
+    await setMaxTransferFee(TRANSFER_FEE_BPS);
+
+    const deadline = (await validatorTime()) + DEADLINE_SECONDS;
+    await createToken2022(program, creator, mint, { deadline, transferFeeBps: TRANSFER_FEE_BPS });
+    await buy2022(program, holder, mint.publicKey, creator.publicKey, feeRecipient, new BN(LAMPORTS_PER_SOL)).rpc();
+
+    // Tokens passing through the relay leave a withheld fee behind in its emptied account
+    await createAssociatedTokenAccountIdempotent(
+      provider.connection,
+      relay,
+      mint.publicKey,
+      relay.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await transfer(holder, relay.publicKey, (await tokenBalance(holder.publicKey)) / 2n);
+    await transfer(relay, holder.publicKey, await tokenBalance(relay.publicKey));
+
+    while ((await validatorTime()) <= deadline) {
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+    }
+    await program.methods
+      .startRefund()
+      .accountsPartial({ signer: provider.wallet.publicKey, bondingCurve: accounts.bondingCurve })
+      .rpc();
+
+    await program.methods
+      .redeem(new BN((await tokenBalance(holder.publicKey)).toString()))
+      .accountsPartial({
+        user: holder.publicKey,
+        bondingCurve: accounts.bondingCurve,
+        mint: mint.publicKey,
+        solVault: accounts.solVault,
+        tokenVault: accounts.tokenVault,
+        userTokenAccount: ata2022(mint.publicKey, holder.publicKey),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([holder])
+      .rpc();
+  });
+
+  after(async () => {
+    await setMaxTransferFee(oldMaxTransferFee);
+  });
+
+  it("Leaves the curve refunding while a withheld fee is unharvested", async () => {
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.status).to.deep.equal({ refunding: {} });
+    expect(curve.realSolReserves.toNumber()).to.be.greaterThan(0);
+    expect(await tokenBalance(relay.publicKey)).to.equal(0n);
+  });
+
+  it("Rejects closing with tokens circulating before the grace period ends", async () => {
+    try {
+      await closeRefund();
+      expect.fail("Should have thrown RefundGracePeriodActive");
+    } catch (e: any) {
+      expect(e.message).to.include("RefundGracePeriodActive");
+    }
+  });
+
+  it("Harvests the stranded fee, closes the curve and sweeps the dust", async () => {
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    const before = await provider.connection.getBalance(feeRecipient);
+
+    await closeRefund([ata2022(mint.publicKey, relay.publicKey)]);
+
+    const after = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(after.status).to.deep.equal({ closed: {} });
+    expect(after.realSolReserves.toNumber()).to.equal(0);
+    expect(await provider.connection.getBalance(feeRecipient) - before).to.equal(curve.realSolReserves.toNumber());
+  });
+});
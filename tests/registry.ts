@@ -0,0 +1,213 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  airdrop,
+  configPDA,
+  createToken2022,
+  curveAccounts,
+  ensureConfig,
+  symbolRecordPDA,
+  uniqueSymbol,
+} from "./helpers/launch";
+
+const FLAG_IMPERSONATION = 1;
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+describe("symbol registry", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const verifiedSymbol = uniqueSymbol("V");
+  const verifiedMint = Keypair.generate().publicKey;
+  const reservedSymbol = uniqueSymbol("R");
+
+  const setSymbolPolicy = (policy: any) =>
+    program.methods
+      .setSymbolPolicy(policy)
+      .accountsPartial({
+        authority: provider.wallet.publicKey,
+        config: configPDA(program),
+      })
+      .rpc();
+
+  const setSymbolStatus = (symbol: string, status: any, mint: PublicKey) =>
+    program.methods
+      .setSymbolStatus(symbol, status, mint)
+      .accountsPartial({
+        authority: provider.wallet.publicKey,
+        config: configPDA(program),
+        symbolRecord: symbolRecordPDA(program, symbol),
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+  const rename = (mint: PublicKey, currentSymbol: string, symbol: string, oldSymbolPayer = creator.publicKey) =>
+    program.methods
+      .updateTokenMetadata("Renamed Token", symbol, "https://example.com/renamed.json")
+      .accountsPartial({
+        creator: creator.publicKey,
+        config: configPDA(program),
+        bondingCurve: curveAccounts(program, mint).bondingCurve,
+        mint,
+        symbolRecord: symbolRecordPDA(program, symbol),
+        oldSymbolRecord: symbolRecordPDA(program, currentSymbol),
+        oldSymbolPayer,
+        metadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+          TOKEN_METADATA_PROGRAM_ID
+        )[0],
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 5);
+    await ensureConfig(program, provider);
+
+    await setSymbolStatus(verifiedSymbol, { verified: {} }, verifiedMint);
+    await setSymbolStatus(reservedSymbol, { reserved: {} }, PublicKey.default);
+
+    const record = await program.account.symbolRecord.fetch(symbolRecordPDA(program, verifiedSymbol));
+    expect(record.symbol).to.equal(verifiedSymbol);
+    expect(record.mint.toBase58()).to.equal(verifiedMint.toBase58());
+    expect(record.status).to.deep.equal({ verified: {} });
+  });
+
+  after(async () => {
+    await setSymbolPolicy({ off: {} });
+  });
+
+  it("Claims an unused ticker for the first launch", async () => {
+    await setSymbolPolicy({ reject: {} });
+    const symbol = uniqueSymbol("C");
+    const mint = Keypair.generate();
+
+    await createToken2022(program, creator, mint, { symbol, symbolRecord: symbolRecordPDA(program, symbol) });
+
+    const record = await program.account.symbolRecord.fetch(symbolRecordPDA(program, symbol));
+    expect(record.mint.toBase58()).to.equal(mint.publicKey.toBase58());
+    expect(record.payer.toBase58()).to.equal(creator.publicKey.toBase58());
+    expect(record.status).to.deep.equal({ claimed: {} });
+    const curve = await program.account.bondingCurve.fetch(curveAccounts(program, mint.publicKey).bondingCurve);
+    expect(curve.flags & FLAG_IMPERSONATION).to.equal(0);
+  });
+
+  it("Rejects a launch on a verified ticker", async () => {
+    await setSymbolPolicy({ reject: {} });
+    try {
+      // Matching is case-insensitive
+      await createToken2022(program, creator, Keypair.generate(), {
+        symbol: verifiedSymbol.toLowerCase(),
+        symbolRecord: symbolRecordPDA(program, verifiedSymbol),
+      });
+      expect.fail("Should have thrown SymbolReserved");
+    } catch (e: any) {
+      expect(e.message).to.include("SymbolReserved");
+    }
+  });
+
+  it("Requires the registry record while the policy is on", async () => {
+    await setSymbolPolicy({ flag: {} });
+    try {
+      await createToken2022(program, creator, Keypair.generate(), { symbol: verifiedSymbol });
+      expect.fail("Should have thrown SymbolRecordRequired");
+    } catch (e: any) {
+      expect(e.message).to.include("SymbolRecordRequired");
+    }
+  });
+
+  it("Flags a launch on a verified ticker as an impersonation", async () => {
+    await setSymbolPolicy({ flag: {} });
+    const mint = Keypair.generate();
+
+    await createToken2022(program, creator, mint, {
+      symbol: verifiedSymbol,
+      symbolRecord: symbolRecordPDA(program, verifiedSymbol),
+    });
+
+    const curve = await program.account.bondingCurve.fetch(curveAccounts(program, mint.publicKey).bondingCurve);
+    expect(curve.flags & FLAG_IMPERSONATION).to.equal(FLAG_IMPERSONATION);
+    // The verified record is left alone
+    const record = await program.account.symbolRecord.fetch(symbolRecordPDA(program, verifiedSymbol));
+    expect(record.mint.toBase58()).to.equal(verifiedMint.toBase58());
+    expect(record.status).to.deep.equal({ verified: {} });
+  });
+
+  it("Rejects renaming a token to a reserved ticker", async () => {
+    await setSymbolPolicy({ reject: {} });
+    const symbol = uniqueSymbol("N");
+    const mint = Keypair.generate();
+    await createToken2022(program, creator, mint, { symbol, symbolRecord: symbolRecordPDA(program, symbol) });
+
+    try {
+      await rename(mint.publicKey, symbol, reservedSymbol);
+      expect.fail("Should have thrown SymbolReserved");
+    } catch (e: any) {
+      expect(e.message).to.include("SymbolReserved");
+    }
+
+    // The original claim is untouched
+    const record = await program.account.symbolRecord.fetch(symbolRecordPDA(program, symbol));
+    expect(record.mint.toBase58()).to.equal(mint.publicKey.toBase58());
+  });
+
+  it("Claims the new ticker on rename and releases the old one", async () => {
+    await setSymbolPolicy({ reject: {} });
+    const oldSymbol = uniqueSymbol("O");
+    const newSymbol = uniqueSymbol("P");
+    const mint = Keypair.generate();
+    await createToken2022(program, creator, mint, {
+      symbol: oldSymbol,
+      symbolRecord: symbolRecordPDA(program, oldSymbol),
+    });
+
+    await rename(mint.publicKey, oldSymbol, newSymbol);
+
+    const record = await program.account.symbolRecord.fetch(symbolRecordPDA(program, newSymbol));
+    expect(record.mint.toBase58()).to.equal(mint.publicKey.toBase58());
+    expect(record.status).to.deep.equal({ claimed: {} });
+    expect(await provider.connection.getAccountInfo(symbolRecordPDA(program, oldSymbol))).to.be.null;
+  });
+
+  it("Refunds a released ticker's rent to the sponsor that paid for it", async () => {
+    await setSymbolPolicy({ reject: {} });
+    const sponsor = Keypair.generate();
+    await airdrop(provider, sponsor.publicKey, 2);
+    const oldSymbol = uniqueSymbol("S");
+    const newSymbol = uniqueSymbol("T");
+    const mint = Keypair.generate();
+    await createToken2022(program, creator, mint, {
+      symbol: oldSymbol,
+      symbolRecord: symbolRecordPDA(program, oldSymbol),
+      payer: sponsor,
+    });
+
+    const oldRecord = symbolRecordPDA(program, oldSymbol);
+    expect((await program.account.symbolRecord.fetch(oldRecord)).payer.toBase58()).to.equal(
+      sponsor.publicKey.toBase58()
+    );
+
+    try {
+      await rename(mint.publicKey, oldSymbol, newSymbol);
+      expect.fail("Should have thrown InvalidSymbolPayer");
+    } catch (e: any) {
+      expect(e.message).to.include("InvalidSymbolPayer");
+    }
+
+    const rent = (await provider.connection.getAccountInfo(oldRecord))!.lamports;
+    const before = await provider.connection.getBalance(sponsor.publicKey);
+    await rename(mint.publicKey, oldSymbol, newSymbol, sponsor.publicKey);
+
+    expect(await provider.connection.getAccountInfo(oldRecord)).to.be.null;
+    expect((await provider.connection.getBalance(sponsor.publicKey)) - before).to.equal(rent);
+  });
+});
@@ -0,0 +1,64 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  airdrop,
+  ata2022,
+  buy2022,
+  createToken2022,
+  curveAccounts,
+  ensureConfig,
+  profilePDA,
+} from "./helpers/launch";
+
+describe("sponsored create", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  // The creator never holds SOL before its first creator fee
+  const creator = Keypair.generate();
+  const sponsor = Keypair.generate();
+  const buyer = Keypair.generate();
+  const mint = Keypair.generate();
+  const accounts = curveAccounts(program, mint.publicKey);
+
+  let feeRecipient: PublicKey;
+
+  before(async () => {
+    await airdrop(provider, sponsor.publicKey, 2);
+    await airdrop(provider, buyer.publicKey, 2);
+    feeRecipient = await ensureConfig(program, provider);
+  });
+
+  it("Lets a sponsor pay for every account of a launch", async () => {
+    const sponsorBefore = await provider.connection.getBalance(sponsor.publicKey);
+
+    await createToken2022(program, creator, mint, {
+      payer: sponsor,
+      profile: { website: "", twitter: "", telegram: "", agentId: "agent-0", description: "" },
+    });
+
+    expect(await provider.connection.getBalance(creator.publicKey)).to.equal(0);
+    expect(await provider.connection.getBalance(sponsor.publicKey)).to.be.lessThan(sponsorBefore);
+
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.creator.toBase58()).to.equal(creator.publicKey.toBase58());
+    expect(await provider.connection.getAccountInfo(ata2022(mint.publicKey, creator.publicKey))).to.not.be.null;
+    expect(await provider.connection.getAccountInfo(profilePDA(program, mint.publicKey))).to.not.be.null;
+  });
+
+  it("Pays creator fees to the creator, not the sponsor", async () => {
+    const sponsorBefore = await provider.connection.getBalance(sponsor.publicKey);
+    const solAmount = new BN(LAMPORTS_PER_SOL);
+
+    await buy2022(program, buyer, mint.publicKey, creator.publicKey, feeRecipient, solAmount).rpc();
+
+    expect(await provider.connection.getBalance(creator.publicKey)).to.be.closeTo(solAmount.toNumber() * 50 / 10000, 1000);
+    expect(await provider.connection.getBalance(sponsor.publicKey)).to.equal(sponsorBefore);
+  });
+});
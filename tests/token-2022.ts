@@ -0,0 +1,94 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  getMetadataPointerState,
+  getMint,
+  getTokenMetadata,
+  getTransferFeeConfig,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  airdrop,
+  ata2022,
+  buy2022,
+  configPDA,
+  createToken2022,
+  curveAccounts,
+  ensureConfig,
+  uniqueSymbol,
+} from "./helpers/launch";
+
+describe("Token-2022 launches", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const trader = Keypair.generate();
+  const mint = Keypair.generate();
+  const accounts = curveAccounts(program, mint.publicKey);
+  const symbol = uniqueSymbol("N");
+
+  let feeRecipient: PublicKey;
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, trader.publicKey, 2);
+    feeRecipient = await ensureConfig(program, provider);
+  });
+
+  it("Keeps the metadata in the mint itself", async () => {
+    await createToken2022(program, creator, mint, {
+      name: "Native Token",
+      symbol,
+      uri: "https://example.com/native.json",
+    });
+
+    const mintAccount = await getMint(provider.connection, mint.publicKey, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(mintAccount.decimals).to.equal(6);
+    expect(mintAccount.freezeAuthority).to.be.null;
+    expect(getTransferFeeConfig(mintAccount)).to.be.null;
+
+    const pointer = getMetadataPointerState(mintAccount);
+    expect(pointer!.metadataAddress!.toBase58()).to.equal(mint.publicKey.toBase58());
+    expect(pointer!.authority!.toBase58()).to.equal(accounts.bondingCurve.toBase58());
+
+    const metadata = await getTokenMetadata(provider.connection, mint.publicKey);
+    expect(metadata!.name).to.equal("Native Token");
+    expect(metadata!.symbol).to.equal(symbol);
+    expect(metadata!.uri).to.equal("https://example.com/native.json");
+
+    const curve = await program.account.bondingCurve.fetch(accounts.bondingCurve);
+    expect(curve.tokenProgram.toBase58()).to.equal(TOKEN_2022_PROGRAM_ID.toBase58());
+  });
+
+  it("Trades through the Token-2022 program both ways", async () => {
+    await buy2022(program, trader, mint.publicKey, creator.publicKey, feeRecipient, new BN(LAMPORTS_PER_SOL / 2)).rpc();
+    const tokenAccount = ata2022(mint.publicKey, trader.publicKey);
+    const bought = (await provider.connection.getTokenAccountBalance(tokenAccount)).value.amount;
+    expect(new BN(bought).gtn(0)).to.be.true;
+
+    await program.methods
+      .sell(new BN(bought), new BN(0))
+      .accountsPartial({
+        seller: trader.publicKey,
+        bondingCurve: accounts.bondingCurve,
+        config: configPDA(program),
+        mint: mint.publicKey,
+        solVault: accounts.solVault,
+        tokenVault: accounts.tokenVault,
+        sellerTokenAccount: tokenAccount,
+        feeRecipient,
+        creator: creator.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([trader])
+      .rpc();
+    expect((await provider.connection.getTokenAccountBalance(tokenAccount)).value.amount).to.equal("0");
+  });
+});
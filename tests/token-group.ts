@@ -0,0 +1,81 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  getGroupMemberPointerState,
+  getMint,
+  getTokenGroupMemberState,
+  getTokenGroupState,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { airdrop, configPDA, createToken2022, ensureConfig } from "./helpers/launch";
+
+describe("token group", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const stranger = Keypair.generate();
+  const tokenGroup = PublicKey.findProgramAddressSync([Buffer.from("token_group")], program.programId)[0];
+
+  const createTokenGroup = (authority: PublicKey, signers: Keypair[] = []) =>
+    program.methods
+      .createTokenGroup()
+      .accountsPartial({
+        authority,
+        config: configPDA(program),
+        tokenGroup,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers(signers)
+      .rpc();
+
+  const groupSize = async () =>
+    Number(getTokenGroupState(await getMint(provider.connection, tokenGroup, undefined, TOKEN_2022_PROGRAM_ID))!.size);
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, stranger.publicKey, 1);
+    await ensureConfig(program, provider);
+  });
+
+  it("Rejects creating the group from a wallet that isn't the authority", async () => {
+    try {
+      await createTokenGroup(stranger.publicKey, [stranger]);
+      expect.fail("Should have thrown Unauthorized");
+    } catch (e: any) {
+      expect(e.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Creates the group under the config PDA", async () => {
+    if (!(await provider.connection.getAccountInfo(tokenGroup))) {
+      await createTokenGroup(provider.wallet.publicKey);
+    }
+
+    const group = getTokenGroupState(await getMint(provider.connection, tokenGroup, undefined, TOKEN_2022_PROGRAM_ID));
+    expect(group).to.not.be.null;
+    expect(group!.mint.toBase58()).to.equal(tokenGroup.toBase58());
+    expect(group!.updateAuthority!.toBase58()).to.equal(configPDA(program).toBase58());
+  });
+
+  it("Adds each new Token-2022 launch to the group", async () => {
+    const sizeBefore = await groupSize();
+    const mint = Keypair.generate();
+    await createToken2022(program, creator, mint);
+
+    const mintAccount = await getMint(provider.connection, mint.publicKey, undefined, TOKEN_2022_PROGRAM_ID);
+    const pointer = getGroupMemberPointerState(mintAccount);
+    expect(pointer!.memberAddress!.toBase58()).to.equal(mint.publicKey.toBase58());
+
+    const member = getTokenGroupMemberState(mintAccount);
+    expect(member!.mint.toBase58()).to.equal(mint.publicKey.toBase58());
+    expect(member!.group.toBase58()).to.equal(tokenGroup.toBase58());
+    expect(Number(member!.memberNumber)).to.equal(sizeBefore + 1);
+    expect(await groupSize()).to.equal(sizeBefore + 1);
+  });
+});
@@ -0,0 +1,171 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Clawdvault } from "../target/types/clawdvault";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  getAccount,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+  getTransferFeeAmount,
+  getTransferFeeConfig,
+  transferCheckedWithFee,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  airdrop,
+  ata2022,
+  buy2022,
+  configPDA,
+  createToken2022,
+  ensureConfig,
+} from "./helpers/launch";
+
+const TRANSFER_FEE_BPS = 100;
+
+describe("transfer-fee launches", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawdvault as Program<Clawdvault>;
+
+  const creator = Keypair.generate();
+  const buyer = Keypair.generate();
+  const recipient = Keypair.generate();
+  const mint = Keypair.generate();
+
+  let feeRecipient: PublicKey;
+  let oldMaxTransferFee: number;
+
+  const setMaxTransferFee = (maxBps: number) =>
+    program.methods
+      .setMaxTransferFee(maxBps)
+      .accountsPartial({ authority: provider.wallet.publicKey, config: configPDA(program) })
+      .rpc();
+
+  const claim = (signer: Keypair, tokenMint: PublicKey, harvest: PublicKey[]) =>
+    program.methods
+      .claimTransferFees()
+      .accountsPartial({
+        creator: signer.publicKey,
+        mint: tokenMint,
+        creatorTokenAccount: ata2022(tokenMint, signer.publicKey),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(harvest.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+      .signers([signer])
+      .rpc();
+
+  const withheld = async (account: PublicKey) =>
+    getTransferFeeAmount(await getAccount(provider.connection, account, undefined, TOKEN_2022_PROGRAM_ID))!
+      .withheldAmount;
+
+  before(async () => {
+    await airdrop(provider, creator.publicKey, 2);
+    await airdrop(provider, buyer.publicKey, 3);
+    feeRecipient = await ensureConfig(program, provider);
+    oldMaxTransferFee = (await program.account.config.fetch(configPDA(program))).maxTransferFeeBps;
+    await setMaxTransferFee(TRANSFER_FEE_BPS);
+  });
+
+  after(async () => {
+    await setMaxTransferFee(oldMaxTransferFee);
+  });
+
+  it("Rejects a transfer fee above the configured maximum", async () => {
+    try {
+      await createToken2022(program, creator, Keypair.generate(), { transferFeeBps: TRANSFER_FEE_BPS + 1 });
+      expect.fail("Should have thrown TransferFeeTooHigh");
+    } catch (e: any) {
+      expect(e.message).to.include("TransferFeeTooHigh");
+    }
+  });
+
+  it("Withholds the fee on every transfer", async () => {
+    await createToken2022(program, creator, mint, { transferFeeBps: TRANSFER_FEE_BPS });
+    const feeConfig = getTransferFeeConfig(
+      await getMint(provider.connection, mint.publicKey, undefined, TOKEN_2022_PROGRAM_ID)
+    );
+    expect(feeConfig!.newerTransferFee.transferFeeBasisPoints).to.equal(TRANSFER_FEE_BPS);
+
+    await buy2022(program, buyer, mint.publicKey, creator.publicKey, feeRecipient, new BN(LAMPORTS_PER_SOL)).rpc();
+    const buyerAccount = ata2022(mint.publicKey, buyer.publicKey);
+    expect(await withheld(buyerAccount) > 0n).to.be.true;
+
+    const recipientAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      buyer,
+      mint.publicKey,
+      recipient.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const amount = 1_000_000_000n;
+    await transferCheckedWithFee(
+      provider.connection,
+      buyer,
+      buyerAccount,
+      mint.publicKey,
+      recipientAccount.address,
+      buyer,
+      amount,
+      6,
+      (amount * BigInt(TRANSFER_FEE_BPS)) / 10_000n,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(await withheld(recipientAccount.address)).to.equal((amount * BigInt(TRANSFER_FEE_BPS)) / 10_000n);
+  });
+
+  it("Rejects claims from anyone but the creator", async () => {
+    try {
+      await claim(buyer, mint.publicKey, []);
+      expect.fail("Should have thrown Unauthorized");
+    } catch (e: any) {
+      expect(e.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Harvests withheld fees into the creator's account", async () => {
+    const accounts = [ata2022(mint.publicKey, buyer.publicKey), ata2022(mint.publicKey, recipient.publicKey)];
+    let expected = 0n;
+    for (const account of accounts) {
+      expected += await withheld(account);
+    }
+
+    await claim(creator, mint.publicKey, accounts);
+
+    const creatorAccount = await getAccount(
+      provider.connection,
+      ata2022(mint.publicKey, creator.publicKey),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(creatorAccount.amount).to.equal(expected);
+    for (const account of accounts) {
+      expect(await withheld(account)).to.equal(0n);
+    }
+
+    try {
+      await claim(creator, mint.publicKey, []);
+      expect.fail("Should have thrown ZeroAmount");
+    } catch (e: any) {
+      expect(e.message).to.include("ZeroAmount");
+    }
+  });
+
+  it("Rejects claims on a token launched without a transfer fee", async () => {
+    const plainMint = Keypair.generate();
+    await createToken2022(program, creator, plainMint);
+    try {
+      await claim(creator, plainMint.publicKey, []);
+      expect.fail("Should have thrown NoTransferFee");
+    } catch (e: any) {
+      expect(e.message).to.include("NoTransferFee");
+    }
+  });
+});